@@ -0,0 +1,282 @@
+//! Chain specification of custom BSC networks, like private devnets, read from a geth genesis.
+//!
+//! The `config` of the genesis follows bsc-geth: the Ethereum hardforks use the standard fields,
+//! the BSC hardforks their `<name>Block` or `<name>Time` field, and the Parlia parameters are read
+//! from the `parlia` field. Hardforks without a field are never activated.
+use super::BscChainSpec;
+use crate::{
+    consensus::parlia::{
+        constants::BACKOFF_TIME_OF_INITIAL,
+        snapshot::{DEFAULT_EPOCH_LENGTH, DEFAULT_TURN_LENGTH},
+        timing::{ParliaTiming, ParliaTimingSchedule},
+    },
+    hardforks::bsc::BscHardfork,
+};
+use alloy_genesis::Genesis;
+use alloy_primitives::U256;
+use reth_chainspec::{
+    make_genesis_header, BaseFeeParams, BaseFeeParamsKind, Chain, ChainSpec, ForkCondition,
+};
+use reth_ethereum_forks::{ChainHardforks, EthereumHardfork, Hardfork};
+use reth_primitives::SealedHeader;
+use serde::Deserialize;
+use std::{collections::BTreeMap, str::FromStr};
+
+/// BSC hardforks activated at a block and their field in the genesis config.
+const BLOCK_FORKS: [(BscHardfork, &str); 13] = [
+    (BscHardfork::Ramanujan, "ramanujanBlock"),
+    (BscHardfork::Niels, "nielsBlock"),
+    (BscHardfork::MirrorSync, "mirrorSyncBlock"),
+    (BscHardfork::Bruno, "brunoBlock"),
+    (BscHardfork::Euler, "eulerBlock"),
+    (BscHardfork::Nano, "nanoBlock"),
+    (BscHardfork::Moran, "moranBlock"),
+    (BscHardfork::Gibbs, "gibbsBlock"),
+    (BscHardfork::Planck, "planckBlock"),
+    (BscHardfork::Luban, "lubanBlock"),
+    (BscHardfork::Plato, "platoBlock"),
+    (BscHardfork::Hertz, "hertzBlock"),
+    (BscHardfork::HertzFix, "hertzfixBlock"),
+];
+
+/// BSC hardforks activated at a timestamp and their field in the genesis config.
+const TIME_FORKS: [(BscHardfork, &str); 10] = [
+    (BscHardfork::Kepler, "keplerTime"),
+    (BscHardfork::Feynman, "feynmanTime"),
+    (BscHardfork::FeynmanFix, "feynmanFixTime"),
+    (BscHardfork::Haber, "haberTime"),
+    (BscHardfork::HaberFix, "haberFixTime"),
+    (BscHardfork::Bohr, "bohrTime"),
+    (BscHardfork::Pascal, "pascalTime"),
+    (BscHardfork::Lorentz, "lorentzTime"),
+    (BscHardfork::Maxwell, "maxwellTime"),
+    (BscHardfork::Fermi, "fermiTime"),
+];
+
+/// Parameters of the Parlia consensus, the `parlia` field of the genesis config.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+struct ParliaConfig {
+    /// Number of blocks between two checkpoints before the first hardfork of the schedule.
+    epoch: u64,
+    /// Block period in seconds before the first hardfork of the schedule.
+    period: u64,
+    /// Timing from a hardfork on, by hardfork name, replacing the one of the public networks.
+    schedule: BTreeMap<String, ParliaTiming>,
+}
+
+impl Default for ParliaConfig {
+    fn default() -> Self {
+        Self { epoch: DEFAULT_EPOCH_LENGTH, period: 3, schedule: BTreeMap::new() }
+    }
+}
+
+impl ParliaConfig {
+    /// Returns the timing schedule of the chain.
+    fn timing_schedule(self) -> eyre::Result<ParliaTimingSchedule> {
+        let mut schedule = ParliaTimingSchedule {
+            base: ParliaTiming {
+                epoch_length: self.epoch,
+                turn_length: DEFAULT_TURN_LENGTH,
+                block_interval: self.period * 1000,
+                initial_back_off: BACKOFF_TIME_OF_INITIAL,
+            },
+            ..Default::default()
+        };
+        for (name, timing) in self.schedule {
+            let fork = BscHardfork::from_str(&name)
+                .map_err(|_| eyre::eyre!("unknown hardfork {name} in the parlia schedule"))?;
+            schedule.forks.insert(fork, timing);
+        }
+        Ok(schedule)
+    }
+}
+
+impl BscChainSpec {
+    /// Creates the chain spec of a custom network from its genesis.
+    pub fn from_genesis(genesis: Genesis) -> eyre::Result<Self> {
+        let parlia = genesis
+            .config
+            .extra_fields
+            .get_deserialized::<ParliaConfig>("parlia")
+            .transpose()?
+            .unwrap_or_default()
+            .timing_schedule()?;
+        let hardforks = hardforks(&genesis)?;
+        let genesis_header = SealedHeader::seal_slow(make_genesis_header(&genesis, &hardforks));
+
+        let inner = ChainSpec {
+            chain: Chain::from_id(genesis.config.chain_id),
+            genesis_header,
+            genesis,
+            paris_block_and_final_difficulty: Some((0, U256::from(0))),
+            hardforks,
+            deposit_contract: None,
+            base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::new(1, 1)),
+            prune_delete_limit: 3500,
+            ..Default::default()
+        };
+        Ok(Self { inner, parlia })
+    }
+}
+
+/// Returns the hardforks activated by the genesis config, ordered by activation.
+fn hardforks(genesis: &Genesis) -> eyre::Result<ChainHardforks> {
+    let config = &genesis.config;
+    let extra_field = |name: &str| config.extra_fields.get_deserialized::<u64>(name).transpose();
+
+    let mut forks = vec![(EthereumHardfork::Frontier.boxed(), ForkCondition::Block(0))];
+    let eth_block_forks = [
+        (EthereumHardfork::Homestead, config.homestead_block),
+        (EthereumHardfork::Tangerine, config.eip150_block),
+        (EthereumHardfork::SpuriousDragon, config.eip158_block),
+        (EthereumHardfork::Byzantium, config.byzantium_block),
+        (EthereumHardfork::Constantinople, config.constantinople_block),
+        (EthereumHardfork::Petersburg, config.petersburg_block),
+        (EthereumHardfork::Istanbul, config.istanbul_block),
+        (EthereumHardfork::MuirGlacier, config.muir_glacier_block),
+        (EthereumHardfork::Berlin, config.berlin_block),
+        (EthereumHardfork::London, config.london_block),
+    ];
+    for (fork, block) in eth_block_forks {
+        if let Some(block) = block {
+            forks.push((fork.boxed(), ForkCondition::Block(block)));
+        }
+    }
+    for (fork, name) in BLOCK_FORKS {
+        if let Some(block) = extra_field(name)? {
+            forks.push((fork.boxed(), ForkCondition::Block(block)));
+        }
+    }
+
+    // BSC activates its own Cancun and Tycho, which brought the blob transactions, with Cancun
+    let time_forks = [
+        (EthereumHardfork::Shanghai.boxed(), config.shanghai_time),
+        (EthereumHardfork::Cancun.boxed(), config.cancun_time),
+        (BscHardfork::Cancun.boxed(), config.cancun_time),
+        (BscHardfork::Tycho.boxed(), config.cancun_time),
+        (EthereumHardfork::Prague.boxed(), config.prague_time),
+    ];
+    for (fork, time) in time_forks {
+        if let Some(time) = time {
+            forks.push((fork, ForkCondition::Timestamp(time)));
+        }
+    }
+    for (fork, name) in TIME_FORKS {
+        if let Some(time) = extra_field(name)? {
+            forks.push((fork.boxed(), ForkCondition::Timestamp(time)));
+        }
+    }
+
+    // Block activations come first, the fork ids expect the forks in activation order
+    forks.sort_by_key(|(_, condition)| match condition {
+        ForkCondition::Block(block) => (0, *block),
+        ForkCondition::Timestamp(time) => (1, *time),
+        _ => (2, 0),
+    });
+    Ok(ChainHardforks::new(forks))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        chainspec::parser::chain_value_parser,
+        hardforks::{bsc::BscHardfork, BscHardforks},
+    };
+    use reth_chainspec::{EthChainSpec, ForkCondition, Hardforks};
+
+    const DEVNET_GENESIS: &str = r#"{
+        "config": {
+            "chainId": 714714,
+            "homesteadBlock": 0,
+            "eip150Block": 0,
+            "eip155Block": 0,
+            "eip158Block": 0,
+            "byzantiumBlock": 0,
+            "constantinopleBlock": 0,
+            "petersburgBlock": 0,
+            "istanbulBlock": 0,
+            "muirGlacierBlock": 0,
+            "ramanujanBlock": 0,
+            "nielsBlock": 0,
+            "mirrorSyncBlock": 1,
+            "brunoBlock": 1,
+            "eulerBlock": 2,
+            "nanoBlock": 3,
+            "moranBlock": 3,
+            "gibbsBlock": 4,
+            "planckBlock": 5,
+            "lubanBlock": 6,
+            "platoBlock": 7,
+            "berlinBlock": 8,
+            "londonBlock": 8,
+            "hertzBlock": 8,
+            "hertzfixBlock": 8,
+            "shanghaiTime": 1000,
+            "keplerTime": 1000,
+            "feynmanTime": 1000,
+            "feynmanFixTime": 1000,
+            "cancunTime": 2000,
+            "haberTime": 2000,
+            "lorentzTime": 3000,
+            "parlia": {
+                "period": 1,
+                "epoch": 100,
+                "schedule": {
+                    "lorentz": {
+                        "epochLength": 300,
+                        "turnLength": 4,
+                        "blockInterval": 750,
+                        "initialBackOff": 500
+                    }
+                }
+            }
+        },
+        "nonce": "0x0",
+        "timestamp": "0x0",
+        "extraData": "0x",
+        "gasLimit": "0x2625a00",
+        "difficulty": "0x1",
+        "mixHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+        "coinbase": "0x0000000000000000000000000000000000000000",
+        "alloc": {}
+    }"#;
+
+    #[test]
+    fn test_parse_custom_genesis() {
+        let spec = chain_value_parser(DEVNET_GENESIS).unwrap();
+
+        assert_eq!(spec.chain().id(), 714714);
+        assert_eq!(spec.parlia.base.epoch_length, 100);
+        assert_eq!(spec.parlia_timing().base.block_interval, 1000);
+        let lorentz = spec.parlia_timing_at(100, 3000);
+        assert_eq!(lorentz.epoch_length, 300);
+        assert_eq!(lorentz.turn_length, 4);
+        assert_eq!(lorentz.block_interval, 750);
+        assert_eq!(spec.parlia_timing_at(100, 2999).block_interval, 1000);
+        assert!(spec.bootnodes().is_none());
+        assert_eq!(spec.head().hash, spec.genesis_hash());
+
+        assert_eq!(spec.bsc_fork_activation(BscHardfork::Luban), ForkCondition::Block(6));
+        assert_eq!(spec.bsc_fork_activation(BscHardfork::Tycho), ForkCondition::Timestamp(2000));
+        assert_eq!(spec.bsc_fork_activation(BscHardfork::Lorentz), ForkCondition::Timestamp(3000));
+        assert_eq!(spec.bsc_fork_activation(BscHardfork::Maxwell), ForkCondition::Never);
+        assert!(spec.is_plato_active_at_block(7));
+        assert!(!spec.is_plato_active_at_block(6));
+
+        // the forks are ordered by activation
+        let conditions = spec.forks_iter().map(|(_, condition)| condition).collect::<Vec<_>>();
+        let mut sorted = conditions.clone();
+        sorted.sort_by_key(|condition| match condition {
+            ForkCondition::Block(block) => (0, *block),
+            ForkCondition::Timestamp(time) => (1, *time),
+            _ => (2, 0),
+        });
+        assert_eq!(conditions, sorted);
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_chain() {
+        assert!(chain_value_parser("bsc-devnet").is_err());
+    }
+}
@@ -1,5 +1,8 @@
 //! Chain specification for BSC, credits to: <https://github.com/bnb-chain/reth/blob/main/crates/bsc/chainspec/src/bsc.rs>
-use crate::hardforks::{bsc::BscHardfork, BscHardforks};
+use crate::{
+    consensus::parlia::timing::ParliaTimingSchedule,
+    hardforks::{bsc::BscHardfork, BscHardforks},
+};
 use alloy_consensus::Header;
 use alloy_eips::eip7840::BlobParams;
 use alloy_genesis::Genesis;
@@ -15,6 +18,8 @@ use std::{fmt::Display, sync::Arc};
 pub mod bsc;
 pub mod bsc_chapel;
 pub mod bsc_rialto;
+pub mod genesis;
+pub mod overrides;
 pub mod parser;
 
 pub use bsc_chapel::bsc_testnet;
@@ -24,6 +29,8 @@ pub use bsc_chapel::bsc_testnet;
 pub struct BscChainSpec {
     /// [`ChainSpec`].
     pub inner: ChainSpec,
+    /// Parlia timing of the chain.
+    pub parlia: ParliaTimingSchedule,
 }
 
 impl EthChainSpec for BscChainSpec {
@@ -125,7 +132,7 @@ impl Hardforks for BscChainSpec {
 
 impl From<ChainSpec> for BscChainSpec {
     fn from(value: ChainSpec) -> Self {
-        Self { inner: value }
+        Self { inner: value, parlia: ParliaTimingSchedule::default() }
     }
 }
 
@@ -139,6 +146,10 @@ impl BscHardforks for BscChainSpec {
     fn bsc_fork_activation(&self, fork: BscHardfork) -> ForkCondition {
         self.fork(fork)
     }
+
+    fn parlia_timing(&self) -> &ParliaTimingSchedule {
+        &self.parlia
+    }
 }
 
 impl EthExecutorSpec for BscChainSpec {
@@ -160,7 +171,17 @@ impl BscChainSpec {
             ChainKind::Id(bsc_rialto::RIALTO_CHAIN_ID) => {
                 bsc_rialto::head()
             }
-            _ => bsc::head(),
+            // Custom chains start from their genesis
+            _ => {
+                let genesis = self.genesis_header();
+                Head {
+                    number: 0,
+                    hash: self.genesis_hash(),
+                    difficulty: genesis.difficulty,
+                    total_difficulty: genesis.difficulty,
+                    timestamp: genesis.timestamp,
+                }
+            }
         }
     }
 }
@@ -175,6 +196,10 @@ impl BscHardforks for Arc<BscChainSpec> {
     fn bsc_fork_activation(&self, fork: BscHardfork) -> ForkCondition {
         self.as_ref().bsc_fork_activation(fork)
     }
+
+    fn parlia_timing(&self) -> &ParliaTimingSchedule {
+        self.as_ref().parlia_timing()
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,152 @@
+//! Overrides of the hardfork activations, to rehearse upcoming forks on shadow forks like the
+//! `--override.<fork>` flags of bsc-geth.
+use super::BscChainSpec;
+use crate::hardforks::bsc::BscHardfork;
+use clap::Args;
+use reth_chainspec::{EthereumHardfork, ForkCondition};
+
+/// The hardforks activated by timestamp, in activation order.
+///
+/// Tycho is left out, it shipped along Cancun but is scheduled apart from the other forks.
+const TIMESTAMP_FORKS: [BscHardfork; 11] = [
+    BscHardfork::Kepler,
+    BscHardfork::Feynman,
+    BscHardfork::FeynmanFix,
+    BscHardfork::Cancun,
+    BscHardfork::Haber,
+    BscHardfork::HaberFix,
+    BscHardfork::Bohr,
+    BscHardfork::Pascal,
+    BscHardfork::Lorentz,
+    BscHardfork::Maxwell,
+    BscHardfork::Fermi,
+];
+
+/// Activation timestamps replacing the ones of the chain spec.
+#[derive(Debug, Clone, Default, Args)]
+#[command(next_help_heading = "Hardfork overrides")]
+pub struct BscHardforkOverrides {
+    /// Activation timestamp of Pascal, which also activates Prague.
+    #[arg(long = "override.pascal", value_name = "TIMESTAMP")]
+    pub pascal: Option<u64>,
+
+    /// Activation timestamp of Lorentz.
+    #[arg(long = "override.lorentz", value_name = "TIMESTAMP")]
+    pub lorentz: Option<u64>,
+
+    /// Activation timestamp of Maxwell.
+    #[arg(long = "override.maxwell", value_name = "TIMESTAMP")]
+    pub maxwell: Option<u64>,
+
+    /// Activation timestamp of Fermi.
+    #[arg(long = "override.fermi", value_name = "TIMESTAMP")]
+    pub fermi: Option<u64>,
+}
+
+impl BscHardforkOverrides {
+    /// Returns the overridden hardforks and their activation timestamp.
+    fn overrides(&self) -> Vec<(BscHardfork, u64)> {
+        [
+            (BscHardfork::Pascal, self.pascal),
+            (BscHardfork::Lorentz, self.lorentz),
+            (BscHardfork::Maxwell, self.maxwell),
+            (BscHardfork::Fermi, self.fermi),
+        ]
+        .into_iter()
+        .filter_map(|(fork, timestamp)| Some((fork, timestamp?)))
+        .collect()
+    }
+
+    /// Returns whether no hardfork is overridden.
+    pub fn is_empty(&self) -> bool {
+        self.overrides().is_empty()
+    }
+
+    /// Returns `spec` with the overridden activations.
+    ///
+    /// Fails if an overridden hardfork would activate before a previous hardfork or after a next
+    /// one, since the fork ids and the hardfork checks expect the hardforks to activate in order.
+    pub fn apply(&self, spec: &BscChainSpec) -> eyre::Result<BscChainSpec> {
+        let mut spec = spec.clone();
+        let overrides = self.overrides();
+        for (fork, timestamp) in &overrides {
+            tracing::info!(target: "reth::cli", %fork, timestamp, "Overriding hardfork activation");
+            let condition = ForkCondition::Timestamp(*timestamp);
+            spec.inner.hardforks.insert(*fork, condition);
+            if *fork == BscHardfork::Pascal {
+                spec.inner.hardforks.insert(EthereumHardfork::Prague, condition);
+            }
+        }
+
+        for (fork, timestamp) in overrides {
+            let position = TIMESTAMP_FORKS.iter().position(|f| *f == fork).expect("listed fork");
+            for previous in &TIMESTAMP_FORKS[..position] {
+                match spec.inner.hardforks.fork(*previous) {
+                    ForkCondition::Timestamp(activation) if activation <= timestamp => {}
+                    condition => eyre::bail!(
+                        "{fork} at {timestamp} would activate before {previous} ({condition:?})"
+                    ),
+                }
+            }
+            for next in &TIMESTAMP_FORKS[position + 1..] {
+                if let ForkCondition::Timestamp(activation) = spec.inner.hardforks.fork(*next) {
+                    if activation < timestamp {
+                        eyre::bail!(
+                            "{fork} at {timestamp} would activate after {next} ({activation})"
+                        );
+                    }
+                }
+            }
+        }
+        Ok(spec)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        chainspec::bsc_chapel::{self, bsc_testnet},
+        hardforks::BscHardforks,
+    };
+    use reth_chainspec::{EthereumHardforks, Hardforks, Head};
+
+    #[test]
+    fn test_override_moves_fork_id() {
+        let spec = BscChainSpec::from(bsc_testnet());
+        let maxwell = match spec.bsc_fork_activation(BscHardfork::Maxwell) {
+            ForkCondition::Timestamp(timestamp) => timestamp,
+            condition => panic!("unexpected maxwell activation {condition:?}"),
+        };
+        let head = Head { timestamp: maxwell, ..bsc_chapel::head() };
+
+        let overrides = BscHardforkOverrides { maxwell: Some(maxwell + 100), ..Default::default() };
+        let patched = overrides.apply(&spec).unwrap();
+        assert!(!patched.is_maxwell_active_at_timestamp(u64::MAX, maxwell));
+        assert_ne!(patched.fork_id(&head), spec.fork_id(&head));
+        assert_eq!(patched.fork_id(&head).next, maxwell + 100);
+        assert!(patched.fork_filter(head).validate(spec.fork_id(&head)).is_err());
+
+        let overrides = BscHardforkOverrides { pascal: Some(maxwell), ..Default::default() };
+        let err = overrides.apply(&spec).unwrap_err();
+        assert!(err.to_string().contains("after Lorentz"), "{err}");
+    }
+
+    #[test]
+    fn test_override_pascal_moves_prague() {
+        let spec = BscChainSpec::from(bsc_testnet());
+        let lorentz = match spec.bsc_fork_activation(BscHardfork::Lorentz) {
+            ForkCondition::Timestamp(timestamp) => timestamp,
+            condition => panic!("unexpected lorentz activation {condition:?}"),
+        };
+
+        let overrides = BscHardforkOverrides { pascal: Some(lorentz), ..Default::default() };
+        let patched = overrides.apply(&spec).unwrap();
+        assert!(!patched.is_prague_active_at_timestamp(lorentz - 1));
+        assert!(patched.is_prague_active_at_timestamp(lorentz));
+
+        let overrides =
+            BscHardforkOverrides { lorentz: Some(1), maxwell: Some(2), ..Default::default() };
+        assert!(overrides.apply(&spec).is_err());
+    }
+}
@@ -1,5 +1,5 @@
 use super::{bsc::bsc_mainnet, bsc_chapel::bsc_testnet, bsc_rialto::bsc_qanet, BscChainSpec};
-use reth_cli::chainspec::ChainSpecParser;
+use reth_cli::chainspec::{parse_genesis, ChainSpecParser};
 use std::sync::Arc;
 
 /// Bsc chain specification parser.
@@ -20,12 +20,13 @@ impl ChainSpecParser for BscChainSpecParser {
 /// Clap value parser for [`BscChainSpec`]s.
 ///
 /// The value parser matches either a known chain, the path
-/// to a json file, or a json formatted string in-memory. The json needs to be a Genesis struct.
+/// to a json file, or a json formatted string in-memory. The json needs to be a Genesis struct,
+/// see [`BscChainSpec::from_genesis`].
 pub fn chain_value_parser(s: &str) -> eyre::Result<Arc<BscChainSpec>> {
     match s {
-        "bsc" => Ok(Arc::new(BscChainSpec { inner: bsc_mainnet() })),
-        "bsc-testnet" => Ok(Arc::new(BscChainSpec { inner: bsc_testnet() })),
-        "bsc-qanet" => Ok(Arc::new(BscChainSpec { inner: bsc_qanet() })),
-        _ => Err(eyre::eyre!("Unsupported chain: {}", s)),
+        "bsc" => Ok(Arc::new(BscChainSpec::from(bsc_mainnet()))),
+        "bsc-testnet" => Ok(Arc::new(BscChainSpec::from(bsc_testnet()))),
+        "bsc-qanet" => Ok(Arc::new(BscChainSpec::from(bsc_qanet()))),
+        _ => Ok(Arc::new(BscChainSpec::from_genesis(parse_genesis(s)?)?)),
     }
 }
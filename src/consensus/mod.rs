@@ -1,7 +1,12 @@
+use crate::{chainspec::BscChainSpec, hardforks::BscHardforks};
 use alloy_consensus::constants::ETH_TO_WEI;
-use alloy_primitives::{address, Address, BlockNumber, B256};
+use alloy_primitives::{address, Address, BlockNumber, B256, U256};
+use alloy_rpc_types::engine::ForkchoiceState;
+use crate::node::evm::error::BscBlockExecutionError;
+use parlia::{vote::MAX_ATTESTATION_EXTRA_LENGTH, Parlia, SnapshotProvider, VoteData};
+use reth_primitives::{GotExpected, SealedHeader};
 use reth_provider::{BlockNumReader, ProviderError};
-use std::cmp::Ordering;
+use std::{cmp::Ordering, sync::Arc};
 
 pub const SYSTEM_ADDRESS: Address = address!("0xfffffffffffffffffffffffffffffffffffffffe");
 /// The reward percent to system
@@ -24,25 +29,218 @@ pub enum ParliaConsensusErr {
 pub struct ParliaConsensus<P> {
     /// The provider for reading block information
     pub provider: P,
+    /// The Parlia engine, used to read the vote attestations of the headers
+    pub parlia: Arc<Parlia<BscChainSpec>>,
 }
 
 impl<P> ParliaConsensus<P>
 where
     P: BlockNumReader + Clone,
 {
-    /// Determines the head block hash according to Parlia consensus rules:
-    /// 1. Follow the highest block number
-    /// 2. For same height blocks, pick the one with lower hash
+    /// Creates a new [`ParliaConsensus`]
+    pub fn new(provider: P, chain_spec: Arc<BscChainSpec>) -> Self {
+        let parlia = Arc::new(Parlia::new(chain_spec.clone(), chain_spec.parlia.base.epoch_length));
+        Self { provider, parlia }
+    }
+
+    /// Returns the fast finality votes attested as of `header`.
+    ///
+    /// A header announced by the network is usually not imported yet, in that case its votes are
+    /// the ones of the snapshot of its parent updated with the attestation of the header.
+    fn attested_vote_data_in(
+        &self,
+        snapshot_provider: &dyn SnapshotProvider,
+        header: &SealedHeader,
+    ) -> Option<VoteData> {
+        if let Some(snap) = snapshot_provider.snapshot_by_hash(header.number, header.hash()) {
+            return Some(snap.vote_data)
+        }
+
+        let mut parent =
+            snapshot_provider.snapshot_by_hash(header.number.checked_sub(1)?, header.parent_hash)?;
+        let attestation =
+            self.parlia.get_vote_attestation_from_header(header.header(), parent.epoch_num).ok()?;
+        parent.update_attestation(header.header(), attestation);
+        Some(parent.vote_data)
+    }
+
+    /// Checks the seal and the vote attestation of a header announced by the network against the
+    /// snapshot of its parent, before the block is handed to the engine.
+    ///
+    /// Checks that need state the node does not have yet, like an unknown parent, are left to the
+    /// execution of the block.
+    pub(crate) fn verify_header(
+        &self,
+        header: &SealedHeader,
+    ) -> Result<(), BscBlockExecutionError> {
+        match crate::shared::get_snapshot_provider() {
+            Some(snapshot_provider) => self.verify_header_in(snapshot_provider.as_ref(), header),
+            None => Ok(()),
+        }
+    }
+
+    fn verify_header_in(
+        &self,
+        snapshot_provider: &dyn SnapshotProvider,
+        header: &SealedHeader,
+    ) -> Result<(), BscBlockExecutionError> {
+        let Some(parent_number) = header.number.checked_sub(1) else { return Ok(()) };
+        let Some(snap) = snapshot_provider.snapshot_by_hash(parent_number, header.parent_hash)
+        else {
+            return Ok(())
+        };
+        self.parlia.verify_seal(&snap, header.header())?;
+
+        if !self.parlia.spec.is_plato_active_at_block(header.number) {
+            return Ok(())
+        }
+        let attestation = self
+            .parlia
+            .get_vote_attestation_from_header(header.header(), snap.epoch_num)
+            .map_err(|err| BscBlockExecutionError::ParliaConsensusInnerError {
+                error: err.into(),
+            })?;
+        let Some(attestation) = attestation else { return Ok(()) };
+        if attestation.extra.len() > MAX_ATTESTATION_EXTRA_LENGTH {
+            return Err(BscBlockExecutionError::TooLargeAttestationExtraLen {
+                extra_len: MAX_ATTESTATION_EXTRA_LENGTH,
+            })
+        }
+
+        // the attestation targets the parent and its source is the highest justified block
+        let data = attestation.data;
+        if data.target_number != parent_number || data.target_hash != header.parent_hash {
+            return Err(BscBlockExecutionError::InvalidAttestationTarget {
+                block_number: GotExpected { got: data.target_number, expected: parent_number },
+                block_hash: GotExpected { got: data.target_hash, expected: header.parent_hash }
+                    .into(),
+            })
+        }
+        let justified = snap.vote_data;
+        if justified.target_hash != B256::ZERO &&
+            (data.source_number != justified.target_number ||
+                data.source_hash != justified.target_hash)
+        {
+            return Err(BscBlockExecutionError::InvalidAttestationSource {
+                block_number: GotExpected {
+                    got: data.source_number,
+                    expected: justified.target_number,
+                },
+                block_hash: GotExpected { got: data.source_hash, expected: justified.target_hash }
+                    .into(),
+            })
+        }
+
+        // the votes are checked against the validators before the parent
+        let pre_snap = snapshot_provider.get_header_by_hash(&header.parent_hash).and_then(|parent| {
+            snapshot_provider.snapshot_by_hash(parent_number.checked_sub(1)?, parent.parent_hash)
+        });
+        match pre_snap {
+            Some(pre_snap) => self.parlia.verify_attestation_votes(&attestation, &pre_snap),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the fast finality votes attested as of the canonical head with the given hash.
+    fn canonical_vote_data_in(
+        &self,
+        snapshot_provider: &dyn SnapshotProvider,
+        head_hash: B256,
+    ) -> Option<VoteData> {
+        let head_number = self.provider.best_block_number().ok()?;
+        snapshot_provider.snapshot_by_hash(head_number, head_hash).map(|snap| snap.vote_data)
+    }
+
+    /// Returns the forkchoice state to send once `header` is imported, `head_block_hash` being
+    /// the head chosen by [`Self::canonical_head`].
+    ///
+    /// The justified block is safe and its source is finalized. Zero hashes leave the safe and
+    /// finalized blocks unchanged while no attestation is known.
+    pub(crate) fn forkchoice_state(
+        &self,
+        header: &SealedHeader,
+        head_block_hash: B256,
+    ) -> ForkchoiceState {
+        let snapshot_provider = crate::shared::get_snapshot_provider()
+            .map(|provider| provider.as_ref() as &dyn SnapshotProvider);
+        self.forkchoice_state_in(snapshot_provider, header, head_block_hash)
+    }
+
+    fn forkchoice_state_in(
+        &self,
+        snapshot_provider: Option<&dyn SnapshotProvider>,
+        header: &SealedHeader,
+        head_block_hash: B256,
+    ) -> ForkchoiceState {
+        let vote_data = snapshot_provider
+            .and_then(|snapshot_provider| {
+                if head_block_hash == header.hash() {
+                    self.attested_vote_data_in(snapshot_provider, header)
+                } else {
+                    self.canonical_vote_data_in(snapshot_provider, head_block_hash)
+                }
+            })
+            .unwrap_or_default();
+        ForkchoiceState {
+            head_block_hash,
+            safe_block_hash: vote_data.target_hash,
+            finalized_block_hash: vote_data.source_hash,
+        }
+    }
+
+    /// Determines the head block hash according to the fast finality rules of BEP-126, like
+    /// bsc-geth:
+    /// 1. Follow the chain with the highest justified block
+    /// 2. Follow the chain with the highest total difficulty
+    /// 3. For the same total difficulty, pick the lower block
+    /// 4. Then pick the one with lower hash
+    ///
+    /// The justified blocks are only compared when the snapshots of both blocks, or of the parent
+    /// of `header`, are known. A block whose total difficulty is unknown, as its parent is not
+    /// imported yet, does not replace the current head unless it justifies a higher block.
     pub(crate) fn canonical_head(
         &self,
-        hash: B256,
-        number: BlockNumber,
+        header: &SealedHeader,
+    ) -> Result<(B256, B256), ParliaConsensusErr> {
+        let snapshot_provider = crate::shared::get_snapshot_provider()
+            .map(|provider| provider.as_ref() as &dyn SnapshotProvider);
+        self.canonical_head_in(snapshot_provider, &crate::shared::get_header_td, header)
+    }
+
+    fn canonical_head_in(
+        &self,
+        snapshot_provider: Option<&dyn SnapshotProvider>,
+        header_td: &dyn Fn(&B256) -> Option<U256>,
+        header: &SealedHeader,
     ) -> Result<(B256, B256), ParliaConsensusErr> {
         let current_head = self.provider.best_block_number()?;
         let current_hash =
             self.provider.block_hash(current_head)?.ok_or(ParliaConsensusErr::HeadHashNotFound)?;
 
-        match number.cmp(&current_head) {
+        let (justified, current_justified) = match snapshot_provider {
+            Some(snapshot_provider) => (
+                self.attested_vote_data_in(snapshot_provider, header)
+                    .map(|data| data.target_number),
+                snapshot_provider
+                    .snapshot_by_hash(current_head, current_hash)
+                    .map(|snap| snap.vote_data.target_number),
+            ),
+            None => (None, None),
+        };
+        let justified_ordering = match (justified, current_justified) {
+            (Some(justified), Some(current_justified)) => justified.cmp(&current_justified),
+            _ => Ordering::Equal,
+        };
+
+        let td = header_td(&header.parent_hash).map(|td| td + header.difficulty);
+        let td_ordering = match (td, header_td(&current_hash)) {
+            (Some(td), Some(current_td)) => td.cmp(&current_td),
+            _ => Ordering::Less,
+        };
+
+        let ordering = justified_ordering.then(td_ordering).then(current_head.cmp(&header.number));
+        let hash = header.hash();
+        match ordering {
             Ordering::Greater => Ok((hash, current_hash)),
             Ordering::Equal => Ok((hash.min(current_hash), current_hash)),
             Ordering::Less => Ok((current_hash, current_hash)),
@@ -55,7 +253,10 @@ pub mod parlia;
 #[cfg(test)]
 mod tests {
     use super::*;
-    use alloy_primitives::hex;
+    use crate::chainspec::bsc::bsc_mainnet;
+    use alloy_consensus::Header;
+    use alloy_primitives::{hex, U256};
+    use parlia::{Snapshot, VoteAttestation, EXTRA_SEAL_LEN, EXTRA_VANITY_LEN};
     use reth_chainspec::ChainInfo;
     use reth_provider::BlockHashReader;
     use std::collections::HashMap;
@@ -116,20 +317,240 @@ mod tests {
             "2222222222222222222222222222222222222222222222222222222222222222"
         ));
 
+        let parent_hash = B256::repeat_byte(0x33);
+
         let test_cases = [
-            ((hash1, 2, 1, hash2), hash1), // Higher block wins
-            ((hash1, 1, 2, hash2), hash2), // Lower block stays
-            ((hash1, 1, 1, hash2), hash1), // Same height, lower hash wins
-            ((hash2, 1, 1, hash1), hash1), // Same height, lower hash stays
+            ((hash1, 2, 12, 1, 11, hash2), hash1), // Higher total difficulty wins
+            ((hash1, 2, 11, 1, 12, hash2), hash2), // Lower total difficulty stays
+            ((hash1, 2, 12, 1, 12, hash2), hash2), // Same total difficulty, lower block stays
+            ((hash1, 1, 12, 2, 12, hash2), hash1), // Same total difficulty, lower block wins
+            ((hash1, 1, 12, 1, 12, hash2), hash1), // Same height, lower hash wins
+            ((hash2, 1, 12, 1, 12, hash1), hash1), // Same height, lower hash stays
         ];
 
-        for ((curr_hash, curr_num, head_num, head_hash), expected) in test_cases {
+        for ((curr_hash, curr_num, curr_td, head_num, head_td, head_hash), expected) in test_cases
+        {
             let provider = MockProvider::new(head_num, head_hash);
-            let consensus = ParliaConsensus { provider };
-            let (head_block_hash, current_hash) =
-                consensus.canonical_head(curr_hash, curr_num).unwrap();
+            let consensus =
+                ParliaConsensus::new(provider, Arc::new(BscChainSpec::from(bsc_mainnet())));
+            let tds = HashMap::from([
+                (parent_hash, U256::from(curr_td - 2)),
+                (head_hash, U256::from(head_td)),
+            ]);
+            let header_td = |hash: &B256| tds.get(hash).copied();
+            let header = Header {
+                parent_hash,
+                number: curr_num,
+                difficulty: U256::from(2),
+                ..Default::default()
+            };
+            let (head_block_hash, current_hash) = consensus
+                .canonical_head_in(None, &header_td, &SealedHeader::new(header, curr_hash))
+                .unwrap();
             assert_eq!(head_block_hash, expected);
             assert_eq!(current_hash, head_hash);
         }
+
+        // a block whose parent is not imported stays out
+        let provider = MockProvider::new(1, hash2);
+        let consensus = ParliaConsensus::new(provider, Arc::new(BscChainSpec::from(bsc_mainnet())));
+        let header =
+            Header { parent_hash, number: 2, difficulty: U256::from(2), ..Default::default() };
+        let header_td = |hash: &B256| (*hash == hash2).then_some(U256::from(1));
+        let (head_block_hash, _) = consensus
+            .canonical_head_in(None, &header_td, &SealedHeader::new(header, hash1))
+            .unwrap();
+        assert_eq!(head_block_hash, hash2);
+    }
+
+    /// Snapshots and headers of the canonical chain
+    #[derive(Default)]
+    struct MockSnapshots {
+        snapshots: HashMap<BlockNumber, Snapshot>,
+        headers: HashMap<BlockNumber, Header>,
+    }
+
+    impl MockSnapshots {
+        fn add(&mut self, header: &SealedHeader, vote_data: VoteData) {
+            let snapshot = Snapshot {
+                block_number: header.number,
+                block_hash: header.hash(),
+                epoch_num: 1000,
+                vote_data,
+                ..Default::default()
+            };
+            self.snapshots.insert(header.number, snapshot);
+            self.headers.insert(header.number, header.header().clone());
+        }
+    }
+
+    impl SnapshotProvider for MockSnapshots {
+        fn snapshot(&self, block_number: u64) -> Option<Snapshot> {
+            self.snapshots.get(&block_number).cloned()
+        }
+
+        fn insert(&self, _snapshot: Snapshot) {}
+
+        fn get_header(&self, block_number: u64) -> Option<Header> {
+            self.headers.get(&block_number).cloned()
+        }
+    }
+
+    /// Seals a child of `parent`, attesting the votes for the parent when `source` is given
+    fn child(parent: &SealedHeader, difficulty: u64, source: Option<&VoteData>) -> SealedHeader {
+        let mut extra_data = vec![0u8; EXTRA_VANITY_LEN];
+        if let Some(source) = source {
+            let attestation = VoteAttestation {
+                vote_address_set: 0,
+                agg_signature: Default::default(),
+                data: VoteData {
+                    source_number: source.target_number,
+                    source_hash: source.target_hash,
+                    target_number: parent.number,
+                    target_hash: parent.hash(),
+                },
+                extra: Default::default(),
+            };
+            extra_data.extend(alloy_rlp::encode(&attestation));
+        }
+        extra_data.extend([0u8; EXTRA_SEAL_LEN]);
+        SealedHeader::seal_slow(Header {
+            parent_hash: parent.hash(),
+            number: parent.number + 1,
+            difficulty: U256::from(difficulty),
+            extra_data: extra_data.into(),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_canonical_head_of_competing_branches() {
+        let ancestor = SealedHeader::seal_slow(Header { number: 40_000_001, ..Default::default() });
+        let justified = VoteData {
+            source_number: 39_999_999,
+            target_number: 40_000_000,
+            target_hash: B256::repeat_byte(0x01),
+            ..Default::default()
+        };
+
+        // the canonical branch is two out-of-turn blocks without attestation
+        let canonical_1 = child(&ancestor, 1, None);
+        let canonical_2 = child(&canonical_1, 1, None);
+        let mut snapshots = MockSnapshots::default();
+        snapshots.add(&ancestor, justified);
+        snapshots.add(&canonical_1, justified);
+        snapshots.add(&canonical_2, justified);
+        let mut tds = HashMap::from([
+            (ancestor.hash(), U256::from(100)),
+            (canonical_1.hash(), U256::from(101)),
+            (canonical_2.hash(), U256::from(102)),
+        ]);
+        let provider = MockProvider::new(canonical_2.number, canonical_2.hash());
+        let consensus = ParliaConsensus::new(provider, Arc::new(BscChainSpec::from(bsc_mainnet())));
+
+        // a lighter branch justifying the ancestor wins
+        let fork = child(&ancestor, 1, Some(&justified));
+        let header_td = |hash: &B256| tds.get(hash).copied();
+        let (head, current) =
+            consensus.canonical_head_in(Some(&snapshots), &header_td, &fork).unwrap();
+        assert_eq!(head, fork.hash());
+        assert_eq!(current, canonical_2.hash());
+
+        // without a higher justified block the heavier branch stays
+        let fork = child(&ancestor, 1, None);
+        let (head, _) = consensus.canonical_head_in(Some(&snapshots), &header_td, &fork).unwrap();
+        assert_eq!(head, canonical_2.hash());
+
+        // at the same justified block the heavier branch wins, even at the same height
+        let fork = child(&canonical_1, 2, None);
+        let (head, _) = consensus.canonical_head_in(Some(&snapshots), &header_td, &fork).unwrap();
+        assert_eq!(head, fork.hash());
+
+        // a branch whose parent is not imported is not followed
+        let imported = fork;
+        let fork = child(&imported, 2, None);
+        let (head, _) = consensus.canonical_head_in(Some(&snapshots), &header_td, &fork).unwrap();
+        assert_eq!(head, canonical_2.hash());
+
+        // a parent without snapshot leaves the justified block unknown, not zero, and the heavier
+        // branch wins
+        tds.insert(imported.hash(), U256::from(103));
+        let header_td = |hash: &B256| tds.get(hash).copied();
+        let (head, _) = consensus.canonical_head_in(Some(&snapshots), &header_td, &fork).unwrap();
+        assert_eq!(head, fork.hash());
+    }
+
+    #[test]
+    fn test_forkchoice_state() {
+        let ancestor = SealedHeader::seal_slow(Header { number: 40_000_001, ..Default::default() });
+        let justified = VoteData {
+            source_number: 39_999_999,
+            source_hash: B256::repeat_byte(0x02),
+            target_number: 40_000_000,
+            target_hash: B256::repeat_byte(0x01),
+        };
+        let mut snapshots = MockSnapshots::default();
+        snapshots.add(&ancestor, justified);
+        let provider = MockProvider::new(ancestor.number, ancestor.hash());
+        let consensus = ParliaConsensus::new(provider, Arc::new(BscChainSpec::from(bsc_mainnet())));
+
+        // the justified block of the snapshot is safe and its source finalized
+        let state = consensus.forkchoice_state_in(Some(&snapshots), &ancestor, ancestor.hash());
+        assert_eq!(
+            state,
+            ForkchoiceState {
+                head_block_hash: ancestor.hash(),
+                safe_block_hash: justified.target_hash,
+                finalized_block_hash: justified.source_hash,
+            }
+        );
+
+        // a new head not imported yet justifies its parent through its attestation
+        let head = child(&ancestor, 2, Some(&justified));
+        let state = consensus.forkchoice_state_in(Some(&snapshots), &head, head.hash());
+        assert_eq!(
+            state,
+            ForkchoiceState {
+                head_block_hash: head.hash(),
+                safe_block_hash: ancestor.hash(),
+                finalized_block_hash: justified.target_hash,
+            }
+        );
+
+        // a block that did not become the head keeps the votes of the canonical head
+        let state = consensus.forkchoice_state_in(Some(&snapshots), &head, ancestor.hash());
+        assert_eq!(state.safe_block_hash, justified.target_hash);
+        assert_eq!(state.finalized_block_hash, justified.source_hash);
+
+        // without a known snapshot the safe and finalized blocks are left unchanged
+        let orphan = child(&head, 2, None);
+        let unchanged = ForkchoiceState {
+            head_block_hash: orphan.hash(),
+            safe_block_hash: B256::ZERO,
+            finalized_block_hash: B256::ZERO,
+        };
+        let state = consensus.forkchoice_state_in(Some(&snapshots), &orphan, orphan.hash());
+        assert_eq!(state, unchanged);
+        assert_eq!(consensus.forkchoice_state_in(None, &ancestor, orphan.hash()), unchanged);
+    }
+
+    #[test]
+    fn test_verify_header_against_parent_snapshot() {
+        let parent = SealedHeader::seal_slow(Header { number: 40_000_001, ..Default::default() });
+        let mut snapshots = MockSnapshots::default();
+        snapshots.add(&parent, VoteData::default());
+        let provider = MockProvider::new(parent.number, parent.hash());
+        let consensus = ParliaConsensus::new(provider, Arc::new(BscChainSpec::from(bsc_mainnet())));
+
+        // an unsigned child of a known parent is rejected
+        let unsigned = child(&parent, 2, None);
+        assert!(matches!(
+            consensus.verify_header_in(&snapshots, &unsigned),
+            Err(BscBlockExecutionError::ParliaConsensusInnerError { .. })
+        ));
+
+        // the child of an unknown parent is left to the execution
+        let orphan = child(&unsigned, 2, None);
+        assert!(consensus.verify_header_in(&snapshots, &orphan).is_ok());
     }
 }
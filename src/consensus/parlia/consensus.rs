@@ -19,7 +19,15 @@ use super::{
     },
     hash_with_chain_id,
     provider::ValidatorsInfo,
-    BACKOFF_TIME_OF_INITIAL, BACKOFF_TIME_OF_WIGGLE, DEFAULT_TURN_LENGTH,LORENTZ_BACKOFF_TIME_OF_INITIAL,
+    BACKOFF_TIME_OF_WIGGLE, DEFAULT_TURN_LENGTH,
+    DIFF_INTURN, DIFF_NOTURN, vote::BLST_DST,
+};
+use crate::node::evm::error::BscBlockExecutionError;
+use reth_primitives::GotExpected;
+use bit_set::BitSet;
+use blst::{
+    min_pk::{PublicKey, Signature},
+    BLST_ERROR,
 };
 use crate::consensus::parlia::go_rng::{RngSource, Shuffle};
 use tracing::{trace, debug};
@@ -52,13 +60,7 @@ where ChainSpec: EthChainSpec + BscHardforks + 'static,
 
     /// Get epoch length from header
     pub fn get_epoch_length(&self, header: &Header) -> u64 {
-        if self.spec.is_maxwell_active_at_timestamp(header.number(), header.timestamp()) {
-            return crate::consensus::parlia::snapshot::MAXWELL_EPOCH_LENGTH;
-        }
-        if self.spec.is_lorentz_active_at_timestamp(header.number(), header.timestamp()) {
-            return crate::consensus::parlia::snapshot::LORENTZ_EPOCH_LENGTH;
-        }
-        self.epoch
+        self.spec.parlia_timing_at(header.number(), header.timestamp()).epoch_length
     }
 
     /// Get validator bytes from header extra data
@@ -356,11 +358,10 @@ where ChainSpec: EthChainSpec + BscHardforks + 'static,
             return 0;
         }
 
-        let mut delay = BACKOFF_TIME_OF_INITIAL;
+        let timing = self.spec.parlia_timing_at(parent.number, parent.timestamp);
+        let initial_back_off = timing.initial_back_off;
+        let mut delay = initial_back_off;
         let is_parent_lorentz = self.spec.is_lorentz_active_at_timestamp(parent.number, parent.timestamp);
-        if is_parent_lorentz {
-            delay = LORENTZ_BACKOFF_TIME_OF_INITIAL;
-        }
         let mut validators = snap.validators.clone();
 
         if self.spec.is_planck_active_at_block(header.number) {
@@ -405,7 +406,7 @@ where ChainSpec: EthChainSpec + BscHardforks + 'static,
                     if back_off_steps[idx] == 0 {
                         return 0;
                     }
-                    return LORENTZ_BACKOFF_TIME_OF_INITIAL + (back_off_steps[idx]- 1) * BACKOFF_TIME_OF_WIGGLE
+                    return initial_back_off + (back_off_steps[idx]- 1) * BACKOFF_TIME_OF_WIGGLE
                 }
                 return delay + back_off_steps[idx] * BACKOFF_TIME_OF_WIGGLE
             }
@@ -415,4 +416,104 @@ where ChainSpec: EthChainSpec + BscHardforks + 'static,
         0
     }
 
+    /// Checks the header is sealed by the beneficiary, an authorized validator of the snapshot of
+    /// the parent that has not proposed too recently, with the difficulty matching its turn.
+    pub fn verify_seal(&self, snap: &Snapshot, header: &Header) -> Result<(), BscBlockExecutionError> {
+        let proposer = self.recover_proposer(header).map_err(|err| {
+            tracing::error!("Failed to recover proposer from header, block_number: {}, error: {:?}", header.number(), err);
+            BscBlockExecutionError::ParliaConsensusInnerError { error: err.into() }
+        })?;
+
+        if proposer != header.beneficiary {
+            return Err(BscBlockExecutionError::WrongHeaderSigner {
+                block_number: header.number(),
+                signer: GotExpected { got: proposer, expected: header.beneficiary }.into(),
+            });
+        }
+
+        if !snap.validators.contains(&proposer) {
+            return Err(BscBlockExecutionError::SignerUnauthorized {
+                block_number: header.number(),
+                proposer,
+            });
+        }
+
+        if snap.sign_recently(proposer) {
+            return Err(BscBlockExecutionError::SignerOverLimit { proposer });
+        }
+
+        let is_inturn = snap.is_inturn(proposer);
+        if (is_inturn && header.difficulty != DIFF_INTURN) ||
+            (!is_inturn && header.difficulty != DIFF_NOTURN)
+        {
+            return Err(BscBlockExecutionError::InvalidDifficulty { difficulty: header.difficulty });
+        }
+
+        Ok(())
+    }
+
+    /// Checks the attestation is signed by at least 2/3 of the validators of `pre_snap`, the
+    /// snapshot before the attested block.
+    pub fn verify_attestation_votes(
+        &self,
+        attestation: &VoteAttestation,
+        pre_snap: &Snapshot,
+    ) -> Result<(), BscBlockExecutionError> {
+        // query bls keys from snapshot.
+        let validators_count = pre_snap.validators.len();
+        let vote_bit_set: BitSet<usize> = BitSet::from_iter(
+            (0..64).filter(|&i| (attestation.vote_address_set >> i) & 1 != 0)
+        );
+        let bit_set_count = vote_bit_set.len();
+        if bit_set_count > validators_count {
+            return Err(BscBlockExecutionError::InvalidAttestationVoteCount(GotExpected {
+                got: bit_set_count as u64,
+                expected: validators_count as u64,
+            }));
+        }
+
+        let mut vote_addrs: Vec<VoteAddress> = Vec::with_capacity(bit_set_count);
+        for (i, val) in pre_snap.validators.iter().enumerate() {
+            if !vote_bit_set.contains(i) {
+                continue;
+            }
+
+            let val_info = pre_snap
+                .validators_map
+                .get(val)
+                .ok_or(BscBlockExecutionError::VoteAddrNotFoundInSnap { address: *val })?;
+            vote_addrs.push(val_info.vote_addr);
+        }
+
+        // check if voted validator count satisfied 2/3 + 1
+        let at_least_votes = (validators_count * 2).div_ceil(3); // ceil division
+        if vote_addrs.len() < at_least_votes {
+            return Err(BscBlockExecutionError::InvalidAttestationVoteCount(GotExpected {
+                got: vote_addrs.len() as u64,
+                expected: at_least_votes as u64,
+            }));
+        }
+
+        // check bls aggregate sig
+        let vote_addrs = vote_addrs
+            .iter()
+            .map(|addr| PublicKey::from_bytes(addr.as_slice()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| BscBlockExecutionError::BLSTInnerError)?;
+        let vote_addrs_ref: Vec<&PublicKey> = vote_addrs.iter().collect();
+
+        let sig = Signature::from_bytes(&attestation.agg_signature[..])
+            .map_err(|_| BscBlockExecutionError::BLSTInnerError)?;
+        let err = sig.fast_aggregate_verify(
+            true,
+            attestation.data.hash().as_slice(),
+            BLST_DST,
+            &vote_addrs_ref,
+        );
+
+        match err {
+            BLST_ERROR::BLST_SUCCESS => Ok(()),
+            _ => Err(BscBlockExecutionError::BLSTInnerError),
+        }
+    }
 }
@@ -2,6 +2,8 @@ use alloy_primitives::U256;
 
 /// Fixed 32-byte vanity prefix present in every header.
 pub const EXTRA_VANITY_LEN: usize = 32;
+/// Trailing 4 bytes of the vanity holding the hash of the next fork the proposer supports.
+pub const NEXT_FORK_HASH_SIZE: usize = 4;
 /// Fixed 65-byte ECDSA signature suffix (r,s,v).
 pub const EXTRA_SEAL_LEN: usize = 65;
 /// 1-byte length field preceding validator bytes since Luban.
@@ -1,6 +1,13 @@
-use reth_db::table::Table;
+use alloy_primitives::{BlockNumber, B256};
+use reth_db::{
+    table::{Decode, Encode, Table},
+    DatabaseError,
+};
+use serde::{Deserialize, Serialize};
 
 /// Table: epoch boundary block number (u64) -> compressed snapshot bytes.
+///
+/// Superseded by [`ParliaSnapshotsByHash`], only read for the checkpoints persisted before.
 #[derive(Debug)]
 pub struct ParliaSnapshots;
 
@@ -10,4 +17,48 @@ impl Table for ParliaSnapshots {
     type Key = u64;
     /// Raw compressed bytes produced by `Snapshot::compress()`.
     type Value = reth_db::models::ParliaSnapshotBlob;
-} 
\ No newline at end of file
+}
+
+/// Table: epoch boundary block number and hash -> compressed snapshot bytes.
+///
+/// Sibling blocks at the same height each keep their snapshot.
+#[derive(Debug)]
+pub struct ParliaSnapshotsByHash;
+
+impl Table for ParliaSnapshotsByHash {
+    const NAME: &'static str = "ParliaSnapshotsByHash";
+    const DUPSORT: bool = false;
+    type Key = ParliaSnapshotKey;
+    /// Raw compressed bytes produced by `Snapshot::compress()`.
+    type Value = reth_db::models::ParliaSnapshotBlob;
+}
+
+/// Key of [`ParliaSnapshotsByHash`], ordered by block number first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ParliaSnapshotKey {
+    /// Number of the snapshot block.
+    pub number: BlockNumber,
+    /// Hash of the snapshot block.
+    pub hash: B256,
+}
+
+impl Encode for ParliaSnapshotKey {
+    type Encoded = [u8; 40];
+
+    fn encode(self) -> Self::Encoded {
+        let mut buf = [0u8; 40];
+        buf[..8].copy_from_slice(&self.number.to_be_bytes());
+        buf[8..].copy_from_slice(self.hash.as_slice());
+        buf
+    }
+}
+
+impl Decode for ParliaSnapshotKey {
+    fn decode(value: &[u8]) -> Result<Self, DatabaseError> {
+        if value.len() != 40 {
+            return Err(DatabaseError::Decode)
+        }
+        let number = u64::from_be_bytes(value[..8].try_into().map_err(|_| DatabaseError::Decode)?);
+        Ok(Self { number, hash: B256::from_slice(&value[8..]) })
+    }
+}
@@ -0,0 +1,203 @@
+//! Detection of the validators sealing two different headers at the same height.
+//!
+//! Like the double sign monitor of bsc-geth, the signer of the headers received from the network
+//! is remembered for the recent heights. A second header sealed by the same validator at the same
+//! height on the same parent is the evidence the SlashIndicator contract expects in
+//! `submitDoubleSignEvidence`.
+use super::{util::hash_with_chain_id, Parlia};
+use crate::{evm::precompiles::double_sign::DoubleSignEvidence, hardforks::BscHardforks};
+use alloy_consensus::Header;
+use alloy_primitives::{Address, BlockNumber, Bytes};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use reth_chainspec::EthChainSpec;
+use schnellru::{ByLength, LruMap};
+use std::collections::VecDeque;
+use tokio::sync::broadcast;
+
+/// Number of headers remembered, about one per height.
+const MAX_SEEN_HEADERS: u32 = 4096;
+
+/// Number of double signs kept for the RPC.
+const MAX_KEPT_DOUBLE_SIGNS: usize = 64;
+
+/// Number of double signs buffered for the slow subscribers.
+const DOUBLE_SIGNS_BUFFER: usize = 16;
+
+/// Two different headers sealed by the same validator at the same height.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DoubleSign {
+    /// The validator that sealed both headers.
+    pub signer: Address,
+    /// Chain id the headers are sealed for.
+    pub chain_id: u64,
+    /// The header seen first.
+    pub header1: Header,
+    /// The header revealing the double sign.
+    pub header2: Header,
+}
+
+impl DoubleSign {
+    /// Returns the height of the headers.
+    pub fn number(&self) -> BlockNumber {
+        self.header1.number
+    }
+
+    /// Returns the RLP encoded headers, the arguments of `submitDoubleSignEvidence`.
+    pub fn header_bytes(&self) -> (Bytes, Bytes) {
+        (alloy_rlp::encode(&self.header1).into(), alloy_rlp::encode(&self.header2).into())
+    }
+
+    /// Returns the evidence as checked by the double sign evidence precompile.
+    pub fn evidence(&self) -> Bytes {
+        let (header_bytes1, header_bytes2) = self.header_bytes();
+        let evidence = DoubleSignEvidence { chain_id: self.chain_id, header_bytes1, header_bytes2 };
+        alloy_rlp::encode(evidence).into()
+    }
+}
+
+/// Signers of the recent headers and the double signs found.
+struct Detector {
+    /// First header seen by height and signer.
+    seen: LruMap<(BlockNumber, Address), Header, ByLength>,
+    /// Latest double signs found, the oldest first.
+    found: VecDeque<DoubleSign>,
+}
+
+impl Detector {
+    fn new() -> Self {
+        Self { seen: LruMap::new(ByLength::new(MAX_SEEN_HEADERS)), found: VecDeque::new() }
+    }
+
+    /// Remembers `header` sealed by `signer`, returns the double sign it reveals if it is new.
+    fn check(&mut self, signer: Address, chain_id: u64, header: &Header) -> Option<DoubleSign> {
+        let key = (header.number, signer);
+        let Some(seen) = self.seen.get(&key) else {
+            self.seen.insert(key, header.clone());
+            return None
+        };
+        // the precompile only accepts headers on the same parent and with different seal hashes
+        if seen.parent_hash != header.parent_hash ||
+            hash_with_chain_id(seen, chain_id) == hash_with_chain_id(header, chain_id)
+        {
+            return None
+        }
+
+        let double_sign =
+            DoubleSign { signer, chain_id, header1: seen.clone(), header2: header.clone() };
+        if self.found.contains(&double_sign) {
+            return None
+        }
+        if self.found.len() == MAX_KEPT_DOUBLE_SIGNS {
+            self.found.pop_front();
+        }
+        self.found.push_back(double_sign.clone());
+        Some(double_sign)
+    }
+}
+
+/// Global detector.
+static DETECTOR: Lazy<Mutex<Detector>> = Lazy::new(|| Mutex::new(Detector::new()));
+
+/// Double signs found by the detector.
+static DOUBLE_SIGNS: Lazy<broadcast::Sender<DoubleSign>> =
+    Lazy::new(|| broadcast::channel(DOUBLE_SIGNS_BUFFER).0);
+
+/// Checks the signer of a header received from the network, returns the double sign it reveals.
+///
+/// The header is expected to have passed the seal checks, so only the validators can forge
+/// evidences against themselves.
+pub fn check_header<ChainSpec>(parlia: &Parlia<ChainSpec>, header: &Header) -> Option<DoubleSign>
+where
+    ChainSpec: EthChainSpec + BscHardforks + 'static,
+{
+    let signer = parlia.recover_proposer(header).ok()?;
+    let chain_id = parlia.chain_spec().chain().id();
+    let double_sign = DETECTOR.lock().check(signer, chain_id, header)?;
+    tracing::warn!(
+        target: "bsc::slash",
+        %signer,
+        number = header.number,
+        hash1 = %double_sign.header1.hash_slow(),
+        hash2 = %double_sign.header2.hash_slow(),
+        evidence = %double_sign.evidence(),
+        "Detected double sign"
+    );
+    // sending only fails when nobody listens
+    let _ = DOUBLE_SIGNS.send(double_sign.clone());
+    Some(double_sign)
+}
+
+/// Returns the latest double signs found, the oldest first.
+pub fn recent_double_signs() -> Vec<DoubleSign> {
+    DETECTOR.lock().found.iter().cloned().collect()
+}
+
+/// Subscribes to the double signs found from now on.
+pub fn subscribe_double_signs() -> broadcast::Receiver<DoubleSign> {
+    DOUBLE_SIGNS.subscribe()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        chainspec::{bsc::bsc_mainnet, BscChainSpec},
+        consensus::parlia::{
+            signer::{Signer, MIMETYPE_PARLIA},
+            util::encode_header_with_chain_id,
+            EXTRA_SEAL_LEN, EXTRA_VANITY_LEN,
+        },
+        evm::precompiles::double_sign::double_sign_evidence_validation_run,
+        node::miner::signer::LocalSigner,
+    };
+    use alloy_primitives::B256;
+    use bytes::BytesMut;
+    use std::sync::Arc;
+
+    /// Seals a header at height 100 on `parent_hash` with the given signer and gas used.
+    fn sealed(signer: &LocalSigner, parent_hash: B256, gas_used: u64) -> Header {
+        let mut header = Header {
+            parent_hash,
+            number: 100,
+            beneficiary: signer.address(),
+            gas_used,
+            extra_data: vec![0u8; EXTRA_VANITY_LEN + EXTRA_SEAL_LEN].into(),
+            ..Default::default()
+        };
+        let mut out = BytesMut::new();
+        encode_header_with_chain_id(&header, &mut out, 56);
+        let seal = signer.sign_data(MIMETYPE_PARLIA, &out).unwrap();
+        let mut extra_data = header.extra_data.to_vec();
+        extra_data[EXTRA_VANITY_LEN..].copy_from_slice(&seal);
+        header.extra_data = extra_data.into();
+        header
+    }
+
+    #[test]
+    fn test_detect_double_sign() {
+        let parlia = Parlia::new(Arc::new(BscChainSpec::from(bsc_mainnet())), 200);
+        let signer = LocalSigner::from_private_key(B256::repeat_byte(0x01)).unwrap();
+        let parent_hash = B256::repeat_byte(0x01);
+        let (header1, header2) = (sealed(&signer, parent_hash, 1), sealed(&signer, parent_hash, 2));
+        let recover = |header: &Header| parlia.recover_proposer(header).unwrap();
+
+        let mut detector = Detector::new();
+        assert!(detector.check(recover(&header1), 56, &header1).is_none());
+        assert!(detector.check(recover(&header1), 56, &header1).is_none());
+        let double_sign = detector.check(recover(&header2), 56, &header2).unwrap();
+        assert_eq!(double_sign.signer, signer.address());
+        assert!(detector.check(recover(&header2), 56, &header2).is_none());
+
+        // the precompile returns the signer and the height of the evidence
+        let output = double_sign_evidence_validation_run(&double_sign.evidence(), 10_000).unwrap();
+        assert!(!output.reverted);
+        assert_eq!(&output.bytes[..20], signer.address().as_slice());
+        assert_eq!(output.bytes[51], 100);
+
+        // headers on different parents are no evidence
+        let other = sealed(&signer, B256::repeat_byte(0x02), 3);
+        assert_eq!(recover(&other), signer.address());
+        assert!(detector.check(recover(&other), 56, &other).is_none());
+    }
+}
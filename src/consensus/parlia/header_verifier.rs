@@ -0,0 +1,303 @@
+//! Snapshot-aware verification of the headers synced by the headers stage.
+//!
+//! The headers stage downloads the headers from the sync target down to the local head, so the
+//! snapshot a header is checked against is usually not known when the header arrives. Such headers
+//! are kept until their chain connects to a block with a known snapshot, then the snapshots are
+//! rebuilt forward and every kept header is checked on the way, before any body is fetched.
+//!
+//! Known limitation: at most [`MAX_PENDING_HEADERS`] headers are kept while waiting. When the
+//! sync target is further away, the highest headers are dropped unchecked and only the execution
+//! of their blocks checks them.
+use super::{provider::ValidatorsInfo, Parlia, ParliaConsensusError, Snapshot, SnapshotProvider};
+use crate::{hardforks::BscHardforks, node::evm::error::BscBlockExecutionError};
+use alloy_consensus::Header;
+use alloy_primitives::{BlockNumber, B256};
+use parking_lot::Mutex;
+use reth_chainspec::EthChainSpec;
+use reth_primitives::SealedHeader;
+use schnellru::{ByLength, LruMap};
+use std::sync::Arc;
+
+/// Maximum number of headers waiting for the snapshot of their parent.
+///
+/// The oldest headers received, the highest ones, are dropped first and left to the execution,
+/// see the module docs.
+const MAX_PENDING_HEADERS: u32 = 32_768;
+
+/// Number of verified headers and snapshots kept to check their descendants.
+const VERIFIED_CACHE_SIZE: u32 = 4096;
+
+/// Highest turn length the StakeHub contract accepts.
+const MAX_TURN_LENGTH: u8 = 64;
+
+/// A header rejected by the [`ParliaHeaderVerifier`].
+#[derive(Debug, thiserror::Error)]
+#[error("invalid header {number} ({hash}): {source}")]
+pub struct InvalidHeader {
+    /// Number of the rejected header.
+    pub number: BlockNumber,
+    /// Hash of the rejected header.
+    pub hash: B256,
+    /// Why the header was rejected.
+    pub source: BscBlockExecutionError,
+}
+
+/// Headers kept by the verifier.
+#[derive(Debug)]
+struct VerifierState {
+    /// Headers whose parent snapshot is not known yet, by hash of their parent.
+    pending: LruMap<B256, SealedHeader, ByLength>,
+    /// Verified headers, to find the checkpoint headers of their descendants.
+    headers: LruMap<B256, Header, ByLength>,
+    /// Snapshots after the verified headers, by hash.
+    snapshots: LruMap<B256, Snapshot, ByLength>,
+}
+
+/// Checks the signer, the difficulty and the turn length of the headers against the snapshots
+/// rebuilt from the headers themselves.
+#[derive(Debug)]
+pub struct ParliaHeaderVerifier<ChainSpec> {
+    parlia: Arc<Parlia<ChainSpec>>,
+    state: Mutex<VerifierState>,
+}
+
+impl<ChainSpec> ParliaHeaderVerifier<ChainSpec>
+where
+    ChainSpec: EthChainSpec + BscHardforks + 'static,
+{
+    /// Creates a verifier using the given Parlia engine.
+    pub fn new(parlia: Arc<Parlia<ChainSpec>>) -> Self {
+        Self {
+            parlia,
+            state: Mutex::new(VerifierState {
+                pending: LruMap::new(ByLength::new(MAX_PENDING_HEADERS)),
+                headers: LruMap::new(ByLength::new(VERIFIED_CACHE_SIZE)),
+                snapshots: LruMap::new(ByLength::new(VERIFIED_CACHE_SIZE)),
+            }),
+        }
+    }
+
+    /// Verifies `header` once the snapshot of `parent` is known.
+    ///
+    /// If it is, the header and the kept headers descending from it are verified. Otherwise the
+    /// header is kept until its parent gets verified. A rejected header and the kept headers
+    /// descending from it are dropped, so they are fetched again, and the error names the rejected
+    /// header rather than `header`.
+    pub fn verify(
+        &self,
+        snapshot_provider: &dyn SnapshotProvider,
+        header: &SealedHeader,
+        parent: &SealedHeader,
+    ) -> Result<(), InvalidHeader> {
+        let mut state = self.state.lock();
+        let cached = state.snapshots.get(&parent.hash()).cloned();
+        let Some(mut snap) = cached
+            .or_else(|| snapshot_provider.known_snapshot(parent.number, parent.hash()))
+        else {
+            state.pending.insert(header.parent_hash, header.clone());
+            return Ok(())
+        };
+
+        let mut next = Some(header.clone());
+        while let Some(header) = next {
+            snap = match self.verify_header(&mut state, snapshot_provider, &snap, &header) {
+                Ok(snap) => snap,
+                Err(source) => {
+                    let (number, hash) = (header.number, header.hash());
+                    let mut dropped = Some(hash);
+                    while let Some(hash) = dropped {
+                        dropped = state.pending.remove(&hash).map(|header| header.hash());
+                    }
+                    return Err(InvalidHeader { number, hash, source })
+                }
+            };
+            state.snapshots.insert(header.hash(), snap.clone());
+            state.headers.insert(header.hash(), header.header().clone());
+            next = state.pending.remove(&header.hash());
+        }
+        Ok(())
+    }
+
+    /// Verifies `header` against the snapshot of its parent and returns the snapshot after it.
+    fn verify_header(
+        &self,
+        state: &mut VerifierState,
+        snapshot_provider: &dyn SnapshotProvider,
+        snap: &Snapshot,
+        header: &SealedHeader,
+    ) -> Result<Snapshot, BscBlockExecutionError> {
+        self.parlia.verify_seal(snap, header.header())?;
+
+        let inner = |error: ParliaConsensusError| {
+            BscBlockExecutionError::ParliaConsensusInnerError { error: error.into() }
+        };
+        let turn_length = self
+            .parlia
+            .get_turn_length_from_header(header.header(), snap.epoch_num)
+            .map_err(inner)?;
+        if turn_length.is_some_and(|turn_length| turn_length == 0 || turn_length > MAX_TURN_LENGTH)
+        {
+            return Err(inner(ParliaConsensusError::ExtraInvalidTurnLength))
+        }
+
+        // the validator set changes once the proposers of the previous epoch are done
+        let miner_check_len = snap.miner_history_check_len();
+        let (info, turn_length) = if header.number > 0 &&
+            header.number % snap.epoch_num == miner_check_len
+        {
+            let checkpoint = self
+                .ancestor(state, snapshot_provider, header, miner_check_len)
+                .ok_or(BscBlockExecutionError::ParentUnknown { hash: header.parent_hash })?;
+            let info = self
+                .parlia
+                .parse_validators_from_header(&checkpoint, snap.epoch_num)
+                .map_err(inner)?;
+            let turn_length = self
+                .parlia
+                .get_turn_length_from_header(&checkpoint, snap.epoch_num)
+                .map_err(inner)?;
+            (info, turn_length)
+        } else {
+            (ValidatorsInfo { consensus_addrs: Vec::new(), vote_addrs: None }, None)
+        };
+        let attestation = self
+            .parlia
+            .get_vote_attestation_from_header(header.header(), snap.epoch_num)
+            .map_err(inner)?;
+
+        snap.apply(
+            header.beneficiary,
+            header.header(),
+            info.consensus_addrs,
+            info.vote_addrs,
+            attestation,
+            turn_length,
+            self.parlia.chain_spec(),
+        )
+        .ok_or(BscBlockExecutionError::ApplySnapshotFailed)
+    }
+
+    /// Returns the ancestor `depth` blocks before `header`.
+    fn ancestor(
+        &self,
+        state: &mut VerifierState,
+        snapshot_provider: &dyn SnapshotProvider,
+        header: &SealedHeader,
+        depth: u64,
+    ) -> Option<Header> {
+        let mut ancestor = header.header().clone();
+        for _ in 0..depth {
+            let parent_hash = ancestor.parent_hash;
+            ancestor = match state.headers.get(&parent_hash) {
+                Some(parent) => parent.clone(),
+                None => snapshot_provider.get_header_by_hash(&parent_hash)?,
+            };
+        }
+        Some(ancestor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        chainspec::{bsc::bsc_mainnet, BscChainSpec},
+        consensus::parlia::{
+            signer::{Signer, MIMETYPE_PARLIA},
+            util::encode_header_with_chain_id,
+            DIFF_INTURN, DIFF_NOTURN, EXTRA_SEAL_LEN, EXTRA_VANITY_LEN,
+        },
+        node::miner::signer::LocalSigner,
+    };
+    use alloy_primitives::U256;
+    use bytes::BytesMut;
+
+    /// Provider knowing the snapshot of a single block.
+    struct SingleSnapshot(Snapshot);
+
+    impl SnapshotProvider for SingleSnapshot {
+        fn snapshot(&self, block_number: u64) -> Option<Snapshot> {
+            (block_number == self.0.block_number).then(|| self.0.clone())
+        }
+
+        fn insert(&self, _snapshot: Snapshot) {}
+
+        fn get_header(&self, _block_number: u64) -> Option<Header> {
+            None
+        }
+    }
+
+    /// Seals a child of `parent` with the given signer and difficulty.
+    fn child(parent: &SealedHeader, signer: &LocalSigner, difficulty: U256) -> SealedHeader {
+        let mut header = Header {
+            parent_hash: parent.hash(),
+            number: parent.number + 1,
+            beneficiary: signer.address(),
+            difficulty,
+            extra_data: vec![0u8; EXTRA_VANITY_LEN + EXTRA_SEAL_LEN].into(),
+            ..Default::default()
+        };
+        let mut out = BytesMut::new();
+        encode_header_with_chain_id(&header, &mut out, 56);
+        let seal = signer.sign_data(MIMETYPE_PARLIA, &out).unwrap();
+        let mut extra_data = header.extra_data.to_vec();
+        extra_data[EXTRA_VANITY_LEN..].copy_from_slice(&seal);
+        header.extra_data = extra_data.into();
+        SealedHeader::seal_slow(header)
+    }
+
+    fn verifier() -> ParliaHeaderVerifier<BscChainSpec> {
+        let spec = Arc::new(BscChainSpec::from(bsc_mainnet()));
+        ParliaHeaderVerifier::new(Arc::new(Parlia::new(spec, 200)))
+    }
+
+    #[test]
+    fn test_verify_headers_in_reverse_order() {
+        let signer = LocalSigner::from_private_key(B256::repeat_byte(0x01)).unwrap();
+        let parent = SealedHeader::seal_slow(Header { number: 100, ..Default::default() });
+        let snap = Snapshot::new(vec![signer.address()], 100, parent.hash(), 200, None);
+        let provider = SingleSnapshot(snap);
+        let verifier = verifier();
+
+        // the headers arrive from the highest, they are verified once they connect to the parent
+        let first = child(&parent, &signer, DIFF_INTURN);
+        let second = child(&first, &signer, DIFF_INTURN);
+        assert!(verifier.verify(&provider, &second, &first).is_ok());
+        assert!(verifier.state.lock().snapshots.get(&second.hash()).is_none());
+        assert!(verifier.verify(&provider, &first, &parent).is_ok());
+        assert!(verifier.state.lock().snapshots.get(&second.hash()).is_some());
+
+        // a forged difficulty deep in the range fails the header connecting it and names the
+        // forged header, which is dropped with its descendants until they are fetched again
+        let verifier = self::verifier();
+        let forged = child(&first, &signer, DIFF_NOTURN);
+        let third = child(&forged, &signer, DIFF_INTURN);
+        assert!(verifier.verify(&provider, &third, &forged).is_ok());
+        assert!(verifier.verify(&provider, &forged, &first).is_ok());
+        let err = verifier.verify(&provider, &first, &parent).unwrap_err();
+        assert_eq!((err.number, err.hash), (forged.number, forged.hash()));
+        assert!(matches!(err.source, BscBlockExecutionError::InvalidDifficulty { .. }));
+        assert!(verifier.state.lock().pending.get(&first.hash()).is_none());
+        assert!(verifier.state.lock().pending.get(&forged.hash()).is_none());
+
+        // fetched again, the forged header fails on its own
+        assert!(verifier.verify(&provider, &third, &forged).is_ok());
+        let err = verifier.verify(&provider, &forged, &first).unwrap_err();
+        assert_eq!((err.number, err.hash), (forged.number, forged.hash()));
+        assert!(verifier.state.lock().pending.get(&forged.hash()).is_none());
+    }
+
+    #[test]
+    fn test_reject_unauthorized_signer() {
+        let signer = LocalSigner::from_private_key(B256::repeat_byte(0x01)).unwrap();
+        let intruder = LocalSigner::from_private_key(B256::repeat_byte(0x02)).unwrap();
+        let parent = SealedHeader::seal_slow(Header { number: 100, ..Default::default() });
+        let snap = Snapshot::new(vec![signer.address()], 100, parent.hash(), 200, None);
+        let provider = SingleSnapshot(snap);
+
+        let header = child(&parent, &intruder, DIFF_INTURN);
+        let err = verifier().verify(&provider, &header, &parent).unwrap_err();
+        assert_eq!(err.number, 101);
+        assert!(matches!(err.source, BscBlockExecutionError::SignerUnauthorized { .. }));
+    }
+}
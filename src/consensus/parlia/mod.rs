@@ -9,7 +9,11 @@ pub mod error;
 pub mod validation;
 pub mod db;
 pub mod seal;
+pub mod signer;
 pub mod go_rng;
+pub mod timing;
+pub mod header_verifier;
+pub mod double_sign;
 
 #[cfg(test)]     
 mod tests;  
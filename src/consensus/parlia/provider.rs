@@ -21,12 +21,29 @@ use reth_db::table::{Compress, Decompress};
 use reth_db::models::ParliaSnapshotBlob;
 use reth_db::transaction::{DbTx, DbTxMut};
 use reth_db::cursor::DbCursorRO;
+use crate::consensus::parlia::db::{ParliaSnapshotKey, ParliaSnapshots, ParliaSnapshotsByHash};
 use schnellru::{ByLength, LruMap};
 
 pub trait SnapshotProvider: Send + Sync {
     /// Returns the snapshot that is valid for the given `block_number` (usually parent block).
     fn snapshot(&self, block_number: u64) -> Option<Snapshot>;
 
+    /// Returns the snapshot after the block `block_hash` at `block_number`.
+    ///
+    /// Unlike [`SnapshotProvider::snapshot`], this follows the branch of the block rather than
+    /// the canonical chain, so sibling blocks get their own snapshots.
+    fn snapshot_by_hash(&self, block_number: u64, block_hash: B256) -> Option<Snapshot> {
+        self.snapshot(block_number).filter(|snap| snap.block_hash == block_hash)
+    }
+
+    /// Returns the snapshot after the block `block_hash` at `block_number` if it is already known,
+    /// without building the snapshots of a branch.
+    ///
+    /// Meant for the hot paths where a missing snapshot is expected, like the header sync.
+    fn known_snapshot(&self, block_number: u64, block_hash: B256) -> Option<Snapshot> {
+        self.snapshot_by_hash(block_number, block_hash)
+    }
+
     /// Inserts (or replaces) the snapshot in the provider.
     fn insert(&self, snapshot: Snapshot);
     
@@ -40,13 +57,44 @@ pub trait SnapshotProvider: Send + Sync {
 }
 
 /// `DbSnapshotProvider` wraps an MDBX database; it keeps a small in-memory LRU to avoid hitting
-/// storage for hot epochs. The DB layer persists snapshots as CBOR blobs via the `ParliaSnapshots`
-/// table that is already defined in `db.rs`.
+/// storage for hot epochs. The DB layer persists snapshots as CBOR blobs via the
+/// `ParliaSnapshotsByHash` table defined in `db.rs`.
 #[derive(Debug)]
 pub struct DbSnapshotProvider<DB: Database> {
     db: DB,
-    /// Front cache keyed by *block number*.
-    cache: RwLock<LruMap<u64, Snapshot, ByLength>>,
+    /// Front cache keyed by *block hash*.
+    cache: RwLock<SnapshotCache>,
+}
+
+/// Snapshots keyed by the hash of their block, so sibling blocks do not overwrite each other.
+#[derive(Debug)]
+struct SnapshotCache {
+    by_hash: LruMap<B256, Snapshot, ByLength>,
+    /// Hash of the latest snapshot inserted at each height.
+    by_number: LruMap<u64, B256, ByLength>,
+}
+
+impl SnapshotCache {
+    fn new(capacity: u32) -> Self {
+        Self {
+            by_hash: LruMap::new(ByLength::new(capacity)),
+            by_number: LruMap::new(ByLength::new(capacity)),
+        }
+    }
+
+    fn get(&mut self, block_hash: &B256) -> Option<Snapshot> {
+        self.by_hash.get(block_hash).cloned()
+    }
+
+    fn get_by_number(&mut self, block_number: u64) -> Option<Snapshot> {
+        let block_hash = *self.by_number.get(&block_number)?;
+        self.get(&block_hash)
+    }
+
+    fn insert(&mut self, snap: Snapshot) {
+        self.by_number.insert(snap.block_number, snap.block_hash);
+        self.by_hash.insert(snap.block_hash, snap);
+    }
 }
 
 /// Enhanced version with backward walking capability
@@ -63,7 +111,7 @@ impl<DB: Database> DbSnapshotProvider<DB> {
     pub fn new(db: DB, capacity: usize) -> Self {
         Self { 
             db, 
-            cache: RwLock::new(LruMap::new(ByLength::new(capacity as u32))),
+            cache: RwLock::new(SnapshotCache::new(capacity as u32)),
         }
     }
 }
@@ -74,7 +122,7 @@ impl<DB: Database> EnhancedDbSnapshotProvider<DB> {
         capacity: usize, 
         chain_spec: Arc<BscChainSpec>,
     ) -> Self {
-        let parlia = Arc::new(Parlia::new(chain_spec.clone(), 200));
+        let parlia = Arc::new(Parlia::new(chain_spec.clone(), chain_spec.parlia.base.epoch_length));
         Self { 
             base: DbSnapshotProvider::new(db, capacity),
             chain_spec,
@@ -101,24 +149,56 @@ impl<DB: Database + Clone> Clone for EnhancedDbSnapshotProvider<DB> {
 }
 
 impl<DB: Database> DbSnapshotProvider<DB> {
+    /// Returns the latest snapshot persisted at or before `block_number`.
+    ///
+    /// Siblings at the same height come in hash order, the caller checks the hash it expects.
     fn load_from_db(&self, block_number: u64) -> Option<Snapshot> {
         let tx = self.db.tx().ok()?;
-        
+        let mut cursor = tx.cursor_read::<ParliaSnapshotsByHash>().ok()?;
+        let next = ParliaSnapshotKey { number: block_number.saturating_add(1), hash: B256::ZERO };
+        let entry = match cursor.seek(next).ok()? {
+            Some(_) => cursor.prev().ok()?,
+            None => cursor.last().ok()?,
+        };
+        if let Some((key, raw_blob)) = entry {
+            if let Ok(decoded) = Snapshot::decompress(&raw_blob.0) {
+                tracing::debug!("Succeed to find snapshot for block {} from DB (snapshot_block={})", block_number, key.number);
+                return Some(decoded);
+            }
+        }
+
+        self.load_legacy_from_db(&tx, block_number)
+    }
+
+    /// Returns the snapshot persisted for the block `block_hash` at `block_number`.
+    fn load_from_db_by_hash(&self, block_number: u64, block_hash: B256) -> Option<Snapshot> {
+        let tx = self.db.tx().ok()?;
+        let key = ParliaSnapshotKey { number: block_number, hash: block_hash };
+        if let Ok(Some(raw_blob)) = tx.get::<ParliaSnapshotsByHash>(key) {
+            return Snapshot::decompress(&raw_blob.0).ok();
+        }
+
+        // checkpoints persisted by number only, a sibling may have overwritten the one we want
+        let raw_blob = tx.get::<ParliaSnapshots>(block_number).ok()??;
+        Snapshot::decompress(&raw_blob.0).ok().filter(|snap| snap.block_hash == block_hash)
+    }
+
+    /// Returns the latest snapshot at or before `block_number` among the checkpoints persisted by
+    /// number only, before the siblings were kept apart.
+    fn load_legacy_from_db(&self, tx: &DB::TX, block_number: u64) -> Option<Snapshot> {
         // Try to get the exact snapshot for the requested block number
-        if let Ok(Some(raw_blob)) = tx.get::<crate::consensus::parlia::db::ParliaSnapshots>(block_number) {
+        if let Ok(Some(raw_blob)) = tx.get::<ParliaSnapshots>(block_number) {
             let raw = &raw_blob.0;
             if let Ok(decoded) = Snapshot::decompress(raw) {
-                tracing::debug!("Succeed to find snapshot for block {} from DB (snapshot_block={})", block_number, decoded.block_number);
+                tracing::debug!("Succeed to find legacy snapshot for block {} from DB (snapshot_block={})", block_number, decoded.block_number);
                 return Some(decoded);
             }
         }
         
-        tracing::warn!("Failed to find snapshot for block {}, searching for fallback...", block_number);
+        tracing::debug!("Failed to find snapshot for block {}, searching for fallback...", block_number);
         
         // If exact snapshot not found, look for the most recent snapshot before this block
-        let mut cursor = tx
-            .cursor_read::<crate::consensus::parlia::db::ParliaSnapshots>()
-            .ok()?;
+        let mut cursor = tx.cursor_read::<ParliaSnapshots>().ok()?;
         let mut iter = cursor.walk_range(..block_number).ok()?;
         let mut last: Option<Snapshot> = None;
         let mut found_count = 0;
@@ -135,16 +215,17 @@ impl<DB: Database> DbSnapshotProvider<DB> {
         if let Some(ref snap) = last {
             tracing::debug!("Succeed to find fallback snapshot for block {} at block {} in DB (searched {} snapshots)", block_number, snap.block_number, found_count);
         } else {
-            tracing::warn!("Failed to find snapshot for block {} from DB", block_number);
+            tracing::debug!("Failed to find snapshot for block {} from DB", block_number);
         }
         last
     }
 
     fn persist_to_db(&self, snap: &Snapshot) -> Result<(), DatabaseError> {
         let tx = self.db.tx_mut()?;
-        tx.put::<crate::consensus::parlia::db::ParliaSnapshots>(snap.block_number, ParliaSnapshotBlob(snap.clone().compress()))?;
+        let key = ParliaSnapshotKey { number: snap.block_number, hash: snap.block_hash };
+        tx.put::<ParliaSnapshotsByHash>(key, ParliaSnapshotBlob(snap.clone().compress()))?;
         tx.commit()?;
-        tracing::debug!("Succeed to insert snapshot block {} to DB", snap.block_number);
+        tracing::debug!("Succeed to insert snapshot block {} ({}) to DB", snap.block_number, snap.block_hash);
         Ok(())
     }
 }
@@ -152,20 +233,29 @@ impl<DB: Database> DbSnapshotProvider<DB> {
 impl<DB: Database + 'static> SnapshotProvider for DbSnapshotProvider<DB> {
     fn snapshot(&self, block_number: u64) -> Option<Snapshot> {
         { // fast path: cache
-            let mut guard = self.cache.write();
-            if let Some(snap) = guard.get(&block_number) {
-                return Some(snap.clone());
+            if let Some(snap) = self.cache.write().get_by_number(block_number) {
+                return Some(snap);
             }
         }
 
         // slow path: DB scan
         let snap = self.load_from_db(block_number)?;
-        self.cache.write().insert(block_number, snap.clone());
+        self.cache.write().insert(snap.clone());
+        Some(snap)
+    }
+
+    fn snapshot_by_hash(&self, block_number: u64, block_hash: B256) -> Option<Snapshot> {
+        if let Some(snap) = self.cache.write().get(&block_hash) {
+            return Some(snap);
+        }
+
+        let snap = self.load_from_db_by_hash(block_number, block_hash)?;
+        self.cache.write().insert(snap.clone());
         Some(snap)
     }
 
     fn insert(&self, snapshot: Snapshot) {
-        self.cache.write().insert(snapshot.block_number, snapshot.clone());
+        self.cache.write().insert(snapshot.clone());
         if snapshot.block_number.is_multiple_of(crate::consensus::parlia::snapshot::CHECKPOINT_INTERVAL) {
             match self.persist_to_db(&snapshot) {
                 Ok(()) => {
@@ -187,36 +277,118 @@ impl<DB: Database + 'static> SnapshotProvider for DbSnapshotProvider<DB> {
 impl<DB: Database + 'static> SnapshotProvider for EnhancedDbSnapshotProvider<DB>
 {
     fn snapshot(&self, block_number: u64) -> Option<Snapshot> {
-        { // fast path query.
-            let mut cache_guard = self.base.cache.write();
-            if let Some(cached_snap) = cache_guard.get(&block_number) {
-                tracing::debug!("Succeed to query snapshot from cache, request {} -> found snapshot for block {}", block_number, cached_snap.block_number);
-                return Some(cached_snap.clone());
+        // the latest snapshot built at the height, the canonical one unless a sibling got built
+        // after it without being made canonical
+        if let Some(snap) = self.base.cache.write().get_by_number(block_number) {
+            return Some(snap);
+        }
+        match Self::canonical_hash(block_number) {
+            Some(block_hash) => self.snapshot_by_hash(block_number, block_hash),
+            // without headers only the persisted snapshots are known
+            None => self.base.load_from_db(block_number).filter(|snap| snap.block_number == block_number),
+        }
+    }
+
+    fn snapshot_by_hash(&self, block_number: u64, block_hash: B256) -> Option<Snapshot> {
+        if let Some(snap) = self.base.cache.write().get(&block_hash) {
+            return Some(snap);
+        }
+
+        // Walk back the branch of the block until a known snapshot, or until it joins the
+        // canonical chain whose snapshots are built by number.
+        let mut branch = Vec::new();
+        let (mut number, mut hash) = (block_number, block_hash);
+        let base_snapshot = loop {
+            if let Some(snap) = self.base.cache.write().get(&hash) {
+                break snap;
             }
+            if Self::canonical_hash(number) == Some(hash) {
+                break self.canonical_snapshot(number)?;
+            }
+
+            let header = crate::node::evm::util::HEADER_CACHE_READER.lock().unwrap().get_header_by_hash(&hash);
+            let Some(header) = header else {
+                tracing::debug!("Failed to find header {} of block {} while walking back its branch", hash, number);
+                return None;
+            };
+            hash = header.parent_hash;
+            number = number.checked_sub(1)?;
+            branch.push(header);
+        };
+
+        tracing::debug!("Building snapshot of non-canonical block {} ({}) from {} branch headers", block_number, block_hash, branch.len());
+        branch.reverse();
+        let mut working_snapshot = base_snapshot;
+        for header in branch.iter() {
+            working_snapshot = self.apply_header(&working_snapshot, header, &branch)?;
+            self.base.cache.write().insert(working_snapshot.clone());
         }
-        
+        Some(working_snapshot)
+    }
+
+    fn known_snapshot(&self, block_number: u64, block_hash: B256) -> Option<Snapshot> {
+        if let Some(snap) = self.base.cache.write().get(&block_hash) {
+            return Some(snap);
+        }
+        // only a canonical block has its snapshot rebuilt, from the persisted checkpoints
+        if crate::shared::get_canonical_hash(block_number) == Some(block_hash) {
+            return self.canonical_snapshot(block_number)
+        }
+        None
+    }
+
+    fn insert(&self, snapshot: Snapshot) {
+        self.base.insert(snapshot);
+    }
+    
+    fn get_header(&self, block_number: u64) -> Option<alloy_consensus::Header> {
+        let header = crate::node::evm::util::HEADER_CACHE_READER.lock().unwrap().get_header_by_number(block_number);
+        tracing::debug!("Succeed to fetch header, is_none: {} for block {} in enhanced snapshot provider", header.is_none(), block_number);
+        header
+    }
+
+    fn get_header_by_hash(&self, block_hash: &B256) -> Option<alloy_consensus::Header> {
+        let header = crate::node::evm::util::HEADER_CACHE_READER.lock().unwrap().get_header_by_hash(block_hash);
+        tracing::debug!("Succeed to fetch header by hash, is_none: {} for hash {} in enhanced snapshot provider", header.is_none(), block_hash);
+        header
+    }
+}
+
+impl<DB: Database + 'static> EnhancedDbSnapshotProvider<DB> {
+    /// Returns the hash of the canonical block at `block_number`, the stored one if the block is
+    /// in the database.
+    fn canonical_hash(block_number: u64) -> Option<B256> {
+        crate::shared::get_canonical_hash(block_number).or_else(|| {
+            crate::node::evm::util::HEADER_CACHE_READER
+                .lock()
+                .unwrap()
+                .get_header_by_number(block_number)
+                .map(|header| header.hash_slow())
+        })
+    }
+
+    /// Returns the snapshot of the canonical block at `block_number`, walking back the canonical
+    /// headers by number.
+    fn canonical_snapshot(&self, block_number: u64) -> Option<Snapshot> {
         // Cache miss, starting backward walking.
         // Incremental snapshot building to avoid OOM with large header collections
         let mut current_block = block_number;
         let base_snapshot = loop {
+            let current_hash = Self::canonical_hash(current_block);
             { // fast path query.
-                let mut cache_guard = self.base.cache.write();
-                if let Some(snap) = cache_guard.get(&current_block) {
-                    break snap.clone();
+                let cached = current_hash.and_then(|hash| self.base.cache.write().get(&hash));
+                if let Some(snap) = cached {
+                    break snap;
                 }
             }
 
             // Check database at checkpoint intervals (every 1024 blocks)
             if current_block.is_multiple_of(crate::consensus::parlia::snapshot::CHECKPOINT_INTERVAL) {
-                if let Some(snap) = self.base.load_from_db(current_block) {
-                    tracing::debug!("Succeed to load snap, block_number: {}, snap_block_number: {}, wanted_block_number: {}", current_block, snap.block_number, block_number);
-                    if snap.block_number == current_block {
-                        self.base.cache.write().insert(current_block, snap.clone());
-                        break snap;
-                    } else {
-                        tracing::warn!("Returned wrong snapshot: requested block {} but got snapshot for block {} - this indicates the snapshot hasn't been created yet", current_block, snap.block_number);
-                        // Don't break here - continue backward walking to find a valid parent snapshot
-                    }
+                let persisted = current_hash.and_then(|hash| self.base.load_from_db_by_hash(current_block, hash));
+                if let Some(snap) = persisted {
+                    tracing::debug!("Succeed to load snap, block_number: {}, wanted_block_number: {}", current_block, block_number);
+                    self.base.cache.write().insert(snap.clone());
+                    break snap;
                 } else {
                     tracing::debug!("Failed to load snapshot in DB for block {}", current_block);
                 }
@@ -229,14 +401,16 @@ impl<DB: Database + 'static> SnapshotProvider for EnhancedDbSnapshotProvider<DB>
                         self.parlia.parse_validators_from_header(&header, self.parlia.epoch).map_err(|err| {
                             BscBlockExecutionError::ParliaConsensusInnerError { error: err.into() }
                         }).ok()?;
-                    let genesis_snap = Snapshot::new(
+                    let mut genesis_snap = Snapshot::new(
                         consensus_addrs,
                         0, // Genesis block number
                         header.hash_slow(),
                         self.parlia.epoch,
                         vote_addrs,
                     );
-                    self.base.cache.write().insert(0, genesis_snap.clone());
+                    // The block period of the chain applies until Lorentz
+                    genesis_snap.block_interval = self.chain_spec.parlia.base.block_interval;
+                    self.base.cache.write().insert(genesis_snap.clone());
                     self.base.persist_to_db(&genesis_snap).ok()?;
                     tracing::info!("Succeed to persist genesis snapshot for block 0 to DB");
                     break genesis_snap;
@@ -252,25 +426,67 @@ impl<DB: Database + 'static> SnapshotProvider for EnhancedDbSnapshotProvider<DB>
         // Incremental forward building from base_snapshot to target block
         self.build_snapshot_incrementally(base_snapshot, block_number)
     }
+    /// Applies `header` to `snap`, reading the validator set from the checkpoint header at epoch
+    /// boundaries. `branch` holds the non-canonical headers the header may descend from.
+    fn apply_header(&self, snap: &Snapshot, header: &alloy_consensus::Header, branch: &[alloy_consensus::Header]) -> Option<Snapshot> {
+        let epoch_remainder = header.number % snap.epoch_num;
+        let miner_check_len = snap.miner_history_check_len();
+        let is_epoch_boundary = header.number > 0 && epoch_remainder == miner_check_len;
+        let mut turn_length = None;
+        
+        let validators_info = if is_epoch_boundary {
+            let checkpoint_block_number = header.number - miner_check_len;
+            tracing::debug!("Updating validator set at epoch boundary, checkpoint_block: {}, current_block: {}", checkpoint_block_number, header.number);
+            
+            // the checkpoint is looked up in the branch first as it may not be canonical
+            let checkpoint_header = branch
+                .iter()
+                .find(|header| header.number == checkpoint_block_number)
+                .cloned()
+                .or_else(|| crate::node::evm::util::HEADER_CACHE_READER.lock().unwrap().get_header_by_number(checkpoint_block_number));
+            if let Some(checkpoint_header) = checkpoint_header {
+                let parsed = self.parlia.parse_validators_from_header(&checkpoint_header, snap.epoch_num);
+                turn_length = self.parlia.get_turn_length_from_header(&checkpoint_header, snap.epoch_num).map_err(|err| {
+                    tracing::error!("Failed to get turn length from checkpoint header, block_number: {}, checkpoint_block_number: {}, epoch_num: {}, error: {:?}", 
+                        header.number, checkpoint_block_number, snap.epoch_num, err);
+                    err
+                }).ok()?;
+                parsed
+            } else {
+                tracing::error!("Failed to find checkpoint header for block {}", checkpoint_block_number);
+                return None;
+            }
+        } else {
+            Ok(ValidatorsInfo {
+                consensus_addrs: Vec::new(),
+                vote_addrs: None,
+            })
+        }.ok()?;
 
-    fn insert(&self, snapshot: Snapshot) {
-        self.base.insert(snapshot);
-    }
-    
-    fn get_header(&self, block_number: u64) -> Option<alloy_consensus::Header> {
-        let header = crate::node::evm::util::HEADER_CACHE_READER.lock().unwrap().get_header_by_number(block_number);
-        tracing::debug!("Succeed to fetch header, is_none: {} for block {} in enhanced snapshot provider", header.is_none(), block_number);
-        header
-    }
+        let new_validators = validators_info.consensus_addrs;
+        let vote_addrs = validators_info.vote_addrs;
+        let attestation = self.parlia.get_vote_attestation_from_header(header, snap.epoch_num).map_err(|err| {
+            tracing::error!("Failed to get vote attestation from header, block_number: {}, epoch_num: {}, error: {:?}", 
+                header.number, snap.epoch_num, err);
+            err
+        }).ok()?;
 
-    fn get_header_by_hash(&self, block_hash: &B256) -> Option<alloy_consensus::Header> {
-        let header = crate::node::evm::util::HEADER_CACHE_READER.lock().unwrap().get_header_by_hash(block_hash);
-        tracing::debug!("Succeed to fetch header by hash, is_none: {} for hash {} in enhanced snapshot provider", header.is_none(), block_hash);
-        header
+        // Apply header to snapshot
+        let applied = snap.apply(
+            header.beneficiary,
+            header,
+            new_validators,
+            vote_addrs,
+            attestation,
+            turn_length,
+            &*self.chain_spec,
+        );
+        if applied.is_none() {
+            tracing::warn!("Failed to apply header {} to snapshot", header.number);
+        }
+        applied
     }
-}
 
-impl<DB: Database + 'static> EnhancedDbSnapshotProvider<DB> {
     /// Build snapshot incrementally to avoid OOM by processing headers in small chunks
     fn build_snapshot_incrementally(&self, base_snapshot: Snapshot, target_block: u64) -> Option<Snapshot> {
         const CHUNK_SIZE: u64 = 1024; // Process headers in chunks to avoid OOM
@@ -298,61 +514,10 @@ impl<DB: Database + 'static> EnhancedDbSnapshotProvider<DB> {
             
             // Apply headers in this chunk
             for header in headers_chunk.iter() {
-                let epoch_remainder = header.number % working_snapshot.epoch_num;
-                let miner_check_len = working_snapshot.miner_history_check_len();
-                let is_epoch_boundary = header.number > 0 && epoch_remainder == miner_check_len;
-                let mut turn_length = None;
-                
-                let validators_info = if is_epoch_boundary {
-                    let checkpoint_block_number = header.number - miner_check_len;
-                    tracing::debug!("Updating validator set at epoch boundary, checkpoint_block: {}, current_block: {}", checkpoint_block_number, header.number);
-                    
-                    if let Some(checkpoint_header) = crate::node::evm::util::HEADER_CACHE_READER.lock().unwrap().get_header_by_number(checkpoint_block_number) {
-                        let parsed = self.parlia.parse_validators_from_header(&checkpoint_header, working_snapshot.epoch_num);
-                        turn_length = self.parlia.get_turn_length_from_header(&checkpoint_header, working_snapshot.epoch_num).map_err(|err| {
-                            tracing::error!("Failed to get turn length from checkpoint header, block_number: {}, checkpoint_block_number: {}, epoch_num: {}, error: {:?}", 
-                                header.number, checkpoint_block_number, working_snapshot.epoch_num, err);
-                            err
-                        }).ok()?;
-                        parsed
-                    } else {
-                        tracing::error!("Failed to find checkpoint header for block {}", checkpoint_block_number);
-                        return None;
-                    }
-                } else {
-                    Ok(ValidatorsInfo {
-                        consensus_addrs: Vec::new(),
-                        vote_addrs: None,
-                    })
-                }.ok()?;
-
-                let new_validators = validators_info.consensus_addrs;
-                let vote_addrs = validators_info.vote_addrs;
-                let attestation = self.parlia.get_vote_attestation_from_header(header, working_snapshot.epoch_num).map_err(|err| {
-                    tracing::error!("Failed to get vote attestation from header, block_number: {}, epoch_num: {}, error: {:?}", 
-                        header.number, working_snapshot.epoch_num, err);
-                    err
-                }).ok()?;
-
-                // Apply header to snapshot
-                working_snapshot = match working_snapshot.apply(
-                    header.beneficiary,
-                    header,
-                    new_validators,
-                    vote_addrs,
-                    attestation,
-                    turn_length,
-                    &*self.chain_spec,
-                ) {
-                    Some(snap) => snap,
-                    None => {
-                        tracing::warn!("Failed to apply header {} to snapshot", header.number);
-                        return None;
-                    }
-                };
+                working_snapshot = self.apply_header(&working_snapshot, header, &[])?;
 
                 // Cache and persist snapshots at checkpoints
-                self.base.cache.write().insert(working_snapshot.block_number, working_snapshot.clone());
+                self.base.cache.write().insert(working_snapshot.clone());
                 if working_snapshot.block_number.is_multiple_of(crate::consensus::parlia::snapshot::CHECKPOINT_INTERVAL) {
                     tracing::info!("Persisting snapshot checkpoint for block {}", working_snapshot.block_number);
                     self.base.insert(working_snapshot.clone());
@@ -1,5 +1,6 @@
 use super::{
     constants::{DIFF_NOTURN, EXTRA_SEAL_LEN},
+    signer::{Signer, MIMETYPE_PARLIA},
     vote_pool::fetch_vote_by_block_hash,
     Snapshot, SnapshotProvider, VoteAddress, VoteAttestation, VoteData,
     VoteSignature,
@@ -7,10 +8,9 @@ use super::{
 use crate::consensus::parlia::util::encode_header_with_chain_id;
 use crate::{hardforks::BscHardforks, BscBlock};
 use alloy_consensus::{BlockHeader, Header};
-use alloy_primitives::{keccak256, map::foldhash::{HashSet, HashSetExt}, Address, Bytes, B256};
+use alloy_primitives::{map::foldhash::{HashSet, HashSetExt}, Address, Bytes, B256};
 use blst::min_pk::{AggregateSignature, Signature as blsSignature};
 use bytes::BytesMut;
-use k256::ecdsa::{SigningKey, Signature, signature::Signer};
 use rand::Rng;
 use reth::consensus::ConsensusError;
 use reth_chainspec::EthChainSpec;
@@ -19,45 +19,39 @@ use reth_primitives_traits::{Block, SealedHeader};
 use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-type SignFnPtr = fn(Address, &str, &[u8]) -> Result<Vec<u8>, ConsensusError>;
-
+/// Seals the blocks produced by the local validator.
 pub struct SealBlock<ChainSpec> {
     snapshot_provider: Arc<dyn SnapshotProvider + Send + Sync>,
     chain_spec: Arc<ChainSpec>,
 
     validator_address: Address,
-    sign_fn: SignFnPtr,
+    signer: Arc<dyn Signer>,
+}
+
+impl<ChainSpec> std::fmt::Debug for SealBlock<ChainSpec> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SealBlock")
+            .field("validator_address", &self.validator_address)
+            .finish_non_exhaustive()
+    }
 }
 
 impl<ChainSpec> SealBlock<ChainSpec>
 where
     ChainSpec: EthChainSpec + BscHardforks,
 {
-    #[allow(dead_code)]
-    pub(crate) fn new(
+    pub fn new(
         snapshot_provider: Arc<dyn SnapshotProvider + Send + Sync>,
         chain_spec: Arc<ChainSpec>,
-        validator_address: Address,
+        signer: Arc<dyn Signer>,
     ) -> Self {
-        Self { snapshot_provider, chain_spec, validator_address, sign_fn: default_sign_fn }
+        Self { snapshot_provider, chain_spec, validator_address: signer.address(), signer }
     }
 
-    #[allow(dead_code)]
-    pub(crate) fn new_with_sign_fn(
-        snapshot_provider: Arc<dyn SnapshotProvider + Send + Sync>,
-        chain_spec: Arc<ChainSpec>,
-        validator_address: Address,
-        sign_fn: SignFnPtr,
-    ) -> Self {
-        Self { snapshot_provider, chain_spec, validator_address, sign_fn }
-    }
-
-    #[allow(dead_code)]
-    fn update_sign_fn(&mut self, sign_fn: SignFnPtr) {
-        self.sign_fn = sign_fn;
-    }
-
-    pub fn seal(self, block: BscBlock) -> Result<SealedBlock<BscBlock>, ConsensusError> {
+    /// Signs the block header, waiting for its timestamp first.
+    ///
+    /// This blocks the current thread, so it must not be called from an async context.
+    pub fn seal(&self, block: BscBlock) -> Result<SealedBlock<BscBlock>, ConsensusError> {
         let header = block.header();
         if header.number == 0 {
             return Err(ConsensusError::Other(
@@ -66,12 +60,11 @@ where
         }
 
         let val = self.validator_address;
-        let sign_fn = self.sign_fn;
 
         let parent_number = header.number - 1;
         let snap = self
             .snapshot_provider
-            .snapshot(parent_number)
+            .snapshot_by_hash(parent_number, header.parent_hash)
             .ok_or_else(|| ConsensusError::Other("Snapshot not found".into()))?;
 
         if !snap.validators.contains(&val) {
@@ -102,21 +95,17 @@ where
 
         let mut out = BytesMut::new();
         encode_header_with_chain_id(&header, &mut out, self.chain_spec.chain_id());
-        match sign_fn(val, "mimetype/parlia", out.iter().as_slice()) {
-            Ok(sig) => {
-                let mut extra = header.extra_data.to_vec();
-                if extra.len() >= EXTRA_SEAL_LEN {
-                    let start = extra.len() - EXTRA_SEAL_LEN;
-                    extra[start..].copy_from_slice(&sig);
-                    header.extra_data = Bytes::from(extra);
-                } else {
-                    tracing::error!(target: "parlia::seal", "extra_data too short to insert seal");
-                }
-            }
-            Err(e) => {
-                tracing::debug!(target: "parlia::seal", "Sign for the block header failed when sealing, err {e}")
-            }
+        let sig = self
+            .signer
+            .sign_data(MIMETYPE_PARLIA, out.as_ref())
+            .map_err(|e| ConsensusError::Other(format!("failed to sign block header: {e}")))?;
+        let mut extra = header.extra_data.to_vec();
+        if extra.len() < EXTRA_SEAL_LEN {
+            return Err(ConsensusError::Other("extra_data too short to insert seal".into()));
         }
+        let start = extra.len() - EXTRA_SEAL_LEN;
+        extra[start..].copy_from_slice(&sig);
+        header.extra_data = Bytes::from(extra);
 
         let block_hash = header.hash_slow();
         Ok(BscBlock::new_sealed(SealedHeader::new(header, block_hash), block.body))
@@ -156,7 +145,7 @@ where
             .ok_or_else(|| ConsensusError::Other("parent not found".into()))?;
         let snap = self
             .snapshot_provider
-            .snapshot(parent.number - 1)
+            .snapshot_by_hash(parent.number - 1, parent.parent_hash)
             .ok_or_else(|| ConsensusError::Other("Snapshot not found".into()))?;
 
         let votes = fetch_vote_by_block_hash(header.parent_hash);
@@ -245,17 +234,8 @@ where
     ) -> Result<(u64, B256), ConsensusError> {
         let snap = self
             .snapshot_provider
-            .snapshot(header.number - 1)
+            .snapshot_by_hash(header.number - 1, header.parent_hash)
             .ok_or_else(|| ConsensusError::Other("Snapshot not found".into()))?;
         Ok((snap.vote_data.target_number, snap.vote_data.target_hash))
     }
 }
-
-pub fn default_sign_fn(_addr: Address, _: &str, data: &[u8]) -> Result<Vec<u8>, ConsensusError> {
-    let hash = keccak256(data);
-    let private_key = &[0u8; 40]; // TODO get private key by addr
-    let signing_key = SigningKey::from_slice(private_key)
-        .map_err(|e| ConsensusError::Other(format!("invalid private key, e:{e}")))?;
-    let sig_result: Signature = signing_key.sign(hash.as_slice());
-    Ok(sig_result.to_bytes().to_vec())
-}
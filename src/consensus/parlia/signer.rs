@@ -0,0 +1,48 @@
+//! Signing interface the validator key is used through, the backends live in the miner.
+use alloy_primitives::Address;
+use alloy_signer_local::LocalSignerError;
+use jsonrpsee::core::ClientError;
+use reth_primitives::{Transaction, TransactionSigned};
+use std::fmt::Debug;
+
+/// Mime type of the data signed when sealing a Parlia header, same as bsc-geth.
+pub const MIMETYPE_PARLIA: &str = "application/x-parlia-header";
+
+/// Errors returned by the signer backends.
+#[derive(Debug, thiserror::Error)]
+pub enum SignerError {
+    /// The key is not a valid secp256k1 private key.
+    #[error("invalid private key: {0}")]
+    InvalidKey(#[from] k256::ecdsa::Error),
+    /// The keystore could not be read or decrypted.
+    #[error("failed to load keystore: {0}")]
+    Keystore(#[from] LocalSignerError),
+    /// Signing with a local key failed.
+    #[error("failed to sign: {0}")]
+    Signing(#[from] alloy_signer::Error),
+    /// The request to the remote signer failed.
+    #[error("remote signer request failed: {0}")]
+    Remote(#[from] ClientError),
+    /// The remote signer answered with something we did not ask for.
+    #[error("invalid remote signer response: {0}")]
+    InvalidResponse(String),
+    /// The transaction type can't be signed by this backend.
+    #[error("unsupported transaction type: {0}")]
+    UnsupportedTransaction(u8),
+}
+
+/// Backend signing block headers and system transactions on behalf of the validator.
+///
+/// Signing is blocking, callers run on the sealing task or the payload builder which both live
+/// on blocking threads.
+pub trait Signer: Debug + Send + Sync {
+    /// Returns the address of the validator.
+    fn address(&self) -> Address;
+
+    /// Signs the keccak hash of `data`, returning the `[R || S || V]` signature where `V` is the
+    /// recovery id.
+    fn sign_data(&self, mime_type: &str, data: &[u8]) -> Result<[u8; 65], SignerError>;
+
+    /// Signs the transaction.
+    fn sign_transaction(&self, tx: Transaction) -> Result<TransactionSigned, SignerError>;
+}
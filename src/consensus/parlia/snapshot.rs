@@ -2,8 +2,11 @@ use std::collections::{BTreeMap, HashMap};
 
 //use crate::consensus::parlia::TURN_LENGTH_SIZE;
 
-use super::vote::{VoteAddress, VoteAttestation, VoteData};
-use alloy_primitives::{Address, BlockNumber, B256};
+use super::{
+    constants::{EXTRA_VANITY_LEN, NEXT_FORK_HASH_SIZE},
+    vote::{VoteAddress, VoteAttestation, VoteData},
+};
+use alloy_primitives::{Address, BlockNumber, FixedBytes, B256};
 use serde::{Deserialize, Serialize};
 use reth_db::table::{Compress, Decompress};
 use reth_db::DatabaseError;
@@ -27,9 +30,14 @@ pub const LORENTZ_TURN_LENGTH: u8 = 8;
 pub const MAXWELL_EPOCH_LENGTH: u64 = 1000;
 pub const MAXWELL_TURN_LENGTH: u8 = 16;
 
+/// Fermi hard-fork parameters, the epoch and turn length of Maxwell are kept.
+pub const FERMI_EPOCH_LENGTH: u64 = MAXWELL_EPOCH_LENGTH;
+pub const FERMI_TURN_LENGTH: u8 = MAXWELL_TURN_LENGTH;
+
 pub const DEFAULT_BLOCK_INTERVAL: u64 = 3000;   // 3000 ms
 pub const LORENTZ_BLOCK_INTERVAL: u64 = 1500;   // 1500 ms
 pub const MAXWELL_BLOCK_INTERVAL: u64 = 750;   //  750 ms
+pub const FERMI_BLOCK_INTERVAL: u64 = 450;     //  450 ms
 
 /// `ValidatorInfo` holds metadata for a validator at a given epoch.
 #[derive(Debug, Default, PartialEq, Eq, Clone, Serialize, Deserialize)]
@@ -55,6 +63,9 @@ pub struct Snapshot {
     pub validators_map: HashMap<Address, ValidatorInfo>,
     /// Map of recent proposers: block → proposer address.
     pub recent_proposers: BTreeMap<BlockNumber, Address>,
+    /// Map of recent fork hashes: block → next fork hash announced in the header extra.
+    #[serde(default)]
+    pub recent_fork_hashes: BTreeMap<BlockNumber, FixedBytes<4>>,
     /// Latest vote data attested by the validator set.
     pub vote_data: VoteData,
     /// Configurable turn-length (default = 1 before Bohr).
@@ -108,6 +119,7 @@ impl Snapshot {
             validators,
             validators_map,
             recent_proposers: Default::default(),
+            recent_fork_hashes: Default::default(),
             vote_data: Default::default(),
             turn_length: Some(DEFAULT_TURN_LENGTH),
             block_interval: DEFAULT_BLOCK_INTERVAL,
@@ -146,6 +158,10 @@ impl Snapshot {
         if block_number >= limit {
             snap.recent_proposers.remove(&(block_number - limit));
         }
+        let limit = self.version_history_check_len();
+        if block_number >= limit {
+            snap.recent_fork_hashes.remove(&(block_number - limit));
+        }
 
         // Validate proposer belongs to validator set and hasn't over-proposed.
         if !snap.validators.contains(&validator) {
@@ -170,6 +186,11 @@ impl Snapshot {
         }
         snap.update_attestation(next_header, attestation);
         snap.recent_proposers.insert(block_number, validator);
+        let extra = next_header.extra_data();
+        if extra.len() >= EXTRA_VANITY_LEN {
+            let fork_hash = &extra[EXTRA_VANITY_LEN - NEXT_FORK_HASH_SIZE..EXTRA_VANITY_LEN];
+            snap.recent_fork_hashes.insert(block_number, FixedBytes::from_slice(fork_hash));
+        }
 
         let is_maxwell_active = chain_spec.is_maxwell_active_at_timestamp(header_number, header_timestamp);
         if is_maxwell_active {
@@ -184,20 +205,15 @@ impl Snapshot {
 			}
         }
 
-        let is_lorentz_active = chain_spec.is_lorentz_active_at_timestamp(header_number, header_timestamp);
-        if is_maxwell_active {
-            snap.block_interval = MAXWELL_BLOCK_INTERVAL;
-        } else if is_lorentz_active {
-            snap.block_interval = LORENTZ_BLOCK_INTERVAL;
-        }
+        let timing = chain_spec.parlia_timing_at(header_number, header_timestamp);
+        snap.block_interval = timing.block_interval;
 
         let epoch_length = snap.epoch_num;
-        let next_block_number = block_number + 1;
-        if snap.epoch_num == DEFAULT_EPOCH_LENGTH && is_lorentz_active && next_block_number.is_multiple_of(LORENTZ_EPOCH_LENGTH) {
-            snap.epoch_num = LORENTZ_EPOCH_LENGTH;
-        }
-        if snap.epoch_num == LORENTZ_EPOCH_LENGTH && is_maxwell_active && next_block_number.is_multiple_of(MAXWELL_EPOCH_LENGTH) {
-            snap.epoch_num = MAXWELL_EPOCH_LENGTH;
+        let schedule = chain_spec.parlia_timing();
+        if let Some(next) =
+            schedule.next_epoch_length(chain_spec, epoch_length, block_number, header_timestamp)
+        {
+            snap.epoch_num = next;
         }
 
         // change validator set
@@ -284,6 +300,12 @@ impl Snapshot {
         (self.validators.len() / 2 + 1) as u64 * turn - 1
     }
 
+    /// Number of blocks to look back when checking the fork hashes of the validators.
+    pub fn version_history_check_len(&self) -> u64 {
+        let turn = u64::from(self.turn_length.unwrap_or(1));
+        self.validators.len() as u64 * turn
+    }
+
     /// Validator that should propose the **next** block.
     pub fn inturn_validator(&self) -> Address {
         let turn_length = u64::from(self.turn_length.unwrap_or(DEFAULT_TURN_LENGTH));
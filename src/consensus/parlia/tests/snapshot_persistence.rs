@@ -1,10 +1,12 @@
 //! Unit tests for Parlia snapshot database persistence and retrieval.
 
 use super::super::{
+    db::ParliaSnapshotsByHash,
     provider::DbSnapshotProvider,
     snapshot::Snapshot,
     provider::SnapshotProvider,
 };
+use crate::node::storage::BscTables;
 use alloy_primitives::{Address, B256};
 use reth_db::{init_db, mdbx::DatabaseArguments, Database, transaction::DbTx, cursor::DbCursorRO};
 use std::sync::Arc;
@@ -18,6 +20,7 @@ fn test_snapshot_database_persistence() -> eyre::Result<()> {
     std::fs::create_dir_all(&db_path)?;
     
     let database = Arc::new(init_db(&db_path, DatabaseArguments::new(Default::default()))?);
+    database.create_tables_for::<BscTables>()?;
     
     // Cleanup guard to ensure database is removed even if test fails
     let _cleanup_guard = TestCleanup { path: db_path.clone() };
@@ -72,6 +75,7 @@ fn test_snapshot_range_queries() -> eyre::Result<()> {
     std::fs::create_dir_all(&db_path)?;
     
     let database = Arc::new(init_db(&db_path, DatabaseArguments::new(Default::default()))?);
+    database.create_tables_for::<BscTables>()?;
     let _cleanup_guard = TestCleanup { path: db_path.clone() };
     
     let provider = DbSnapshotProvider::new(database.clone(), 256);
@@ -129,6 +133,7 @@ fn test_direct_database_access() -> eyre::Result<()> {
     std::fs::create_dir_all(&db_path)?;
     
     let database = Arc::new(init_db(&db_path, DatabaseArguments::new(Default::default()))?);
+    database.create_tables_for::<BscTables>()?;
     let _cleanup_guard = TestCleanup { path: db_path.clone() };
     
     let provider = DbSnapshotProvider::new(database.clone(), 256);
@@ -150,7 +155,7 @@ fn test_direct_database_access() -> eyre::Result<()> {
     
     // Check raw database table
     let tx = database.tx()?;
-    let mut cursor = tx.cursor_read::<crate::consensus::parlia::db::ParliaSnapshots>()?;
+    let mut cursor = tx.cursor_read::<ParliaSnapshotsByHash>()?;
     let mut count = 0;
     
     for item in cursor.walk(None)? {
@@ -171,6 +176,7 @@ fn test_snapshot_cache_behavior() -> eyre::Result<()> {
     std::fs::create_dir_all(&db_path)?;
     
     let database = Arc::new(init_db(&db_path, DatabaseArguments::new(Default::default()))?);
+    database.create_tables_for::<BscTables>()?;
     let _cleanup_guard = TestCleanup { path: db_path.clone() };
     
     // Small cache size to test eviction
@@ -201,6 +207,54 @@ fn test_snapshot_cache_behavior() -> eyre::Result<()> {
     Ok(())
 }
 
+/// Test sibling snapshots at the same height are kept apart
+#[test]
+fn test_sibling_snapshots_by_hash() -> eyre::Result<()> {
+    let db_path = std::env::temp_dir().join(format!("bsc_test_db_{}", Uuid::new_v4()));
+    std::fs::create_dir_all(&db_path)?;
+
+    let database = Arc::new(init_db(&db_path, DatabaseArguments::new(Default::default()))?);
+    database.create_tables_for::<BscTables>()?;
+    let _cleanup_guard = TestCleanup { path: db_path.clone() };
+
+    let provider = DbSnapshotProvider::new(database.clone(), 256);
+
+    // Two siblings at a checkpoint, both are persisted
+    let siblings: Vec<Snapshot> = (0..2)
+        .map(|_| Snapshot {
+            block_number: 1024,
+            block_hash: B256::random(),
+            validators: vec![Address::random(); 3],
+            epoch_num: 200,
+            ..Default::default()
+        })
+        .collect();
+    for snapshot in &siblings {
+        provider.insert(snapshot.clone());
+    }
+
+    for expected in &siblings {
+        let snapshot = provider
+            .snapshot_by_hash(expected.block_number, expected.block_hash)
+            .expect("sibling snapshot should be cached by hash");
+        assert_eq!(&snapshot, expected);
+    }
+    assert_eq!(provider.snapshot(1024).map(|snap| snap.block_hash), Some(siblings[1].block_hash));
+    assert!(provider.snapshot_by_hash(1024, B256::random()).is_none());
+
+    // Both siblings survive a restart
+    let provider = DbSnapshotProvider::new(database, 256);
+    for expected in &siblings {
+        let snapshot = provider
+            .snapshot_by_hash(expected.block_number, expected.block_hash)
+            .expect("sibling snapshot should be persisted by hash");
+        assert_eq!(&snapshot, expected);
+    }
+    assert!(provider.snapshot_by_hash(1024, B256::random()).is_none());
+
+    Ok(())
+}
+
 /// RAII guard to cleanup test database directory
 struct TestCleanup {
     path: std::path::PathBuf,
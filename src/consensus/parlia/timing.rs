@@ -0,0 +1,200 @@
+//! Timing parameters of Parlia and the hardforks changing them.
+//!
+//! The epoch length, turn length, block interval and back off of the validators change with the
+//! fast-block hardforks (Lorentz, Maxwell, ...). The chain spec owns a [`ParliaTimingSchedule`]
+//! listing them per hardfork, so a new hardfork only adds an entry to it.
+use super::{
+    constants::{BACKOFF_TIME_OF_INITIAL, LORENTZ_BACKOFF_TIME_OF_INITIAL},
+    snapshot::{
+        DEFAULT_BLOCK_INTERVAL, DEFAULT_EPOCH_LENGTH, DEFAULT_TURN_LENGTH, FERMI_BLOCK_INTERVAL,
+        FERMI_EPOCH_LENGTH, FERMI_TURN_LENGTH, LORENTZ_BLOCK_INTERVAL, LORENTZ_EPOCH_LENGTH,
+        LORENTZ_TURN_LENGTH, MAXWELL_BLOCK_INTERVAL, MAXWELL_EPOCH_LENGTH, MAXWELL_TURN_LENGTH,
+    },
+};
+use crate::hardforks::{bsc::BscHardfork, BscHardforks};
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// Timing parameters of Parlia from a hardfork on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParliaTiming {
+    /// Number of blocks between two checkpoints.
+    pub epoch_length: u64,
+    /// Default number of consecutive blocks proposed by a validator.
+    pub turn_length: u8,
+    /// Expected block interval in milliseconds.
+    pub block_interval: u64,
+    /// Back off of the first out of turn validator in milliseconds.
+    pub initial_back_off: u64,
+}
+
+/// Timing of the public BSC networks before the first fast-block hardfork.
+pub const DEFAULT_TIMING: ParliaTiming = ParliaTiming {
+    epoch_length: DEFAULT_EPOCH_LENGTH,
+    turn_length: DEFAULT_TURN_LENGTH,
+    block_interval: DEFAULT_BLOCK_INTERVAL,
+    initial_back_off: BACKOFF_TIME_OF_INITIAL,
+};
+
+/// Timing introduced by Lorentz.
+pub const LORENTZ_TIMING: ParliaTiming = ParliaTiming {
+    epoch_length: LORENTZ_EPOCH_LENGTH,
+    turn_length: LORENTZ_TURN_LENGTH,
+    block_interval: LORENTZ_BLOCK_INTERVAL,
+    initial_back_off: LORENTZ_BACKOFF_TIME_OF_INITIAL,
+};
+
+/// Timing introduced by Maxwell.
+pub const MAXWELL_TIMING: ParliaTiming = ParliaTiming {
+    epoch_length: MAXWELL_EPOCH_LENGTH,
+    turn_length: MAXWELL_TURN_LENGTH,
+    block_interval: MAXWELL_BLOCK_INTERVAL,
+    initial_back_off: LORENTZ_BACKOFF_TIME_OF_INITIAL,
+};
+
+/// Timing introduced by Fermi.
+pub const FERMI_TIMING: ParliaTiming = ParliaTiming {
+    epoch_length: FERMI_EPOCH_LENGTH,
+    turn_length: FERMI_TURN_LENGTH,
+    block_interval: FERMI_BLOCK_INTERVAL,
+    initial_back_off: LORENTZ_BACKOFF_TIME_OF_INITIAL,
+};
+
+/// Schedule of the public BSC networks, used by the chain specs that define none.
+static BSC_SCHEDULE: Lazy<ParliaTimingSchedule> = Lazy::new(ParliaTimingSchedule::default);
+
+/// The timing of a chain and the hardforks changing it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParliaTimingSchedule {
+    /// Timing before the first hardfork of the schedule.
+    pub base: ParliaTiming,
+    /// Timing from each hardfork on, the hardforks being ordered by activation.
+    pub forks: BTreeMap<BscHardfork, ParliaTiming>,
+}
+
+impl Default for ParliaTimingSchedule {
+    fn default() -> Self {
+        Self {
+            base: DEFAULT_TIMING,
+            forks: BTreeMap::from([
+                (BscHardfork::Lorentz, LORENTZ_TIMING),
+                (BscHardfork::Maxwell, MAXWELL_TIMING),
+                (BscHardfork::Fermi, FERMI_TIMING),
+            ]),
+        }
+    }
+}
+
+impl ParliaTimingSchedule {
+    /// Returns the schedule of the public BSC networks.
+    pub fn bsc() -> &'static Self {
+        &BSC_SCHEDULE
+    }
+
+    /// Returns the timing at the given block, the one of the last active hardfork.
+    pub fn timing_at<S>(&self, spec: &S, number: u64, timestamp: u64) -> ParliaTiming
+    where
+        S: BscHardforks + ?Sized,
+    {
+        self.forks
+            .iter()
+            .rev()
+            .find(|(fork, _)| is_active(spec, **fork, number, timestamp))
+            .map_or(self.base, |(_, timing)| *timing)
+    }
+
+    /// Returns the epoch length a snapshot switches to after the block `number`, if any.
+    ///
+    /// The snapshot moves along the hardforks of the schedule, from the one of its current
+    /// `epoch_length` to the next, once that hardfork is active and the next block starts an epoch
+    /// of the new length. It may move several hardforks at once when their epochs line up.
+    pub fn next_epoch_length<S>(
+        &self,
+        spec: &S,
+        epoch_length: u64,
+        number: u64,
+        timestamp: u64,
+    ) -> Option<u64>
+    where
+        S: BscHardforks + ?Sized,
+    {
+        let stages = std::iter::once((None, self.base))
+            .chain(self.forks.iter().map(|(fork, timing)| (Some(*fork), *timing)))
+            .collect::<Vec<_>>();
+        let current = stages.iter().rposition(|(_, timing)| timing.epoch_length == epoch_length)?;
+        stages[current + 1..]
+            .iter()
+            .map_while(|(fork, next)| {
+                let fork = (*fork)?;
+                (is_active(spec, fork, number, timestamp) &&
+                    (number + 1).is_multiple_of(next.epoch_length))
+                .then_some(next.epoch_length)
+            })
+            .last()
+    }
+}
+
+/// Returns whether a hardfork of the schedule is active at the given block.
+fn is_active<S>(spec: &S, fork: BscHardfork, number: u64, timestamp: u64) -> bool
+where
+    S: BscHardforks + ?Sized,
+{
+    spec.is_london_active_at_block(number) &&
+        spec.bsc_fork_activation(fork).active_at_timestamp_or_number(number, timestamp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chainspec::{bsc_chapel::bsc_testnet, bsc_rialto::bsc_qanet, BscChainSpec};
+    use reth_chainspec::ForkCondition;
+
+    #[test]
+    fn test_timing_follows_hardforks() {
+        let spec = BscChainSpec::from(bsc_qanet());
+        let schedule = ParliaTimingSchedule::bsc();
+        let lorentz = match spec.bsc_fork_activation(BscHardfork::Lorentz) {
+            ForkCondition::Timestamp(timestamp) => timestamp,
+            condition => panic!("unexpected lorentz activation {condition:?}"),
+        };
+        let maxwell = match spec.bsc_fork_activation(BscHardfork::Maxwell) {
+            ForkCondition::Timestamp(timestamp) => timestamp,
+            condition => panic!("unexpected maxwell activation {condition:?}"),
+        };
+
+        assert_eq!(schedule.timing_at(&spec, 100, lorentz - 1), DEFAULT_TIMING);
+        assert_eq!(schedule.timing_at(&spec, 100, lorentz), LORENTZ_TIMING);
+        assert_eq!(schedule.timing_at(&spec, 100, maxwell), MAXWELL_TIMING);
+
+        let spec = BscChainSpec::from(bsc_testnet());
+        let fermi = match spec.bsc_fork_activation(BscHardfork::Fermi) {
+            ForkCondition::Timestamp(timestamp) => timestamp,
+            condition => panic!("unexpected fermi activation {condition:?}"),
+        };
+        let number = 70_000_000;
+        assert_eq!(schedule.timing_at(&spec, number, fermi - 1), MAXWELL_TIMING);
+        assert_eq!(schedule.timing_at(&spec, number, fermi), FERMI_TIMING);
+        assert_eq!(schedule.next_epoch_length(&spec, 1000, 69_999_999, fermi), None);
+    }
+
+    #[test]
+    fn test_epoch_length_follows_hardforks() {
+        let spec = BscChainSpec::from(bsc_qanet());
+        let schedule = ParliaTimingSchedule::bsc();
+        let after_maxwell = u64::MAX;
+
+        // both hardforks are active, the snapshot goes through the Lorentz epoch length unless the
+        // epochs of both line up
+        assert_eq!(schedule.next_epoch_length(&spec, 200, 498, after_maxwell), None);
+        assert_eq!(schedule.next_epoch_length(&spec, 200, 499, after_maxwell), Some(500));
+        assert_eq!(schedule.next_epoch_length(&spec, 200, 999, after_maxwell), Some(1000));
+        assert_eq!(schedule.next_epoch_length(&spec, 500, 1499, after_maxwell), None);
+        assert_eq!(schedule.next_epoch_length(&spec, 500, 1999, after_maxwell), Some(1000));
+        assert_eq!(schedule.next_epoch_length(&spec, 1000, 2999, after_maxwell), None);
+
+        // no switch before the hardfork
+        assert_eq!(schedule.next_epoch_length(&spec, 200, 999, 0), None);
+    }
+}
@@ -1,8 +1,15 @@
 use alloy_primitives::{keccak256, BlockNumber, B256, FixedBytes};
 use alloy_rlp::{RlpDecodable, RlpEncodable, Decodable};
+use blst::{
+    min_pk::{PublicKey, Signature},
+    BLST_ERROR,
+};
 use bytes::Bytes;
 use serde::{Deserialize, Serialize};
 
+/// Domain separation tag of the BLS signatures of the votes.
+pub const BLST_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
 /// Max length allowed for the `extra` field of a [`VoteAttestation`].
 pub const MAX_ATTESTATION_EXTRA_LENGTH: usize = 256;
 
@@ -50,6 +57,16 @@ pub struct VoteEnvelope {
 impl VoteEnvelope {
     /// Returns the Keccak-256 hash of the RLP-encoded envelope.
     pub fn hash(&self) -> B256 { keccak256(alloy_rlp::encode(self)) }
+
+    /// Returns `true` if `signature` is the signature of `vote_address` over the hash of `data`.
+    pub fn verify(&self) -> bool {
+        let Ok(public_key) = PublicKey::key_validate(self.vote_address.as_slice()) else {
+            return false
+        };
+        let Ok(signature) = Signature::from_bytes(self.signature.as_slice()) else { return false };
+        signature.verify(true, self.data.hash().as_slice(), BLST_DST, &[], &public_key, false) ==
+            BLST_ERROR::BLST_SUCCESS
+    }
 }
 
 /// `VoteAttestation` is the aggregated vote of a super-majority of validators.
@@ -80,3 +97,30 @@ impl VoteAttestation {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use blst::min_pk::SecretKey;
+
+    #[test]
+    fn test_verify_vote_envelope() {
+        let secret_key = SecretKey::key_gen(&[7u8; 32], &[]).unwrap();
+        let data = VoteData { source_number: 1, target_number: 2, ..Default::default() };
+        let mut vote = VoteEnvelope {
+            vote_address: VoteAddress::from(secret_key.sk_to_pk().to_bytes()),
+            signature: VoteSignature::from(
+                secret_key.sign(data.hash().as_slice(), BLST_DST, &[]).to_bytes(),
+            ),
+            data,
+        };
+        assert!(vote.verify());
+
+        vote.data.target_number = 3;
+        assert!(!vote.verify());
+
+        vote.data.target_number = 2;
+        vote.vote_address = VoteAddress::ZERO;
+        assert!(!vote.verify());
+    }
+}
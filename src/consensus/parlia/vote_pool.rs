@@ -1,11 +1,21 @@
 use once_cell::sync::Lazy;
 use std::{collections::{BinaryHeap, HashMap, HashSet}, sync::RwLock, cmp::Reverse};
+use tokio::sync::broadcast;
 
 use alloy_primitives::{BlockNumber, B256};
 
-use super::vote::{VoteData, VoteEnvelope};
+use super::vote::{VoteAddress, VoteData, VoteEnvelope};
 
-const LOWER_LIMIT_OF_VOTE_BLOCK_NUMBER: u64 = 256;
+/// Votes with a target further than this below the head are too old to be kept.
+pub const LOWER_LIMIT_OF_VOTE_BLOCK_NUMBER: u64 = 256;
+/// Votes with a target further than this above the head are rejected.
+pub const UPPER_LIMIT_OF_VOTE_BLOCK_NUMBER: u64 = 11;
+/// Maximum number of votes kept for a known block.
+pub const MAX_CUR_VOTE_AMOUNT_PER_BLOCK: usize = 21;
+/// Maximum number of votes kept for a block that is not imported yet.
+pub const MAX_FUTURE_VOTE_AMOUNT_PER_BLOCK: usize = 50;
+/// Number of accepted votes buffered for the slow subscribers.
+const NEW_VOTES_BUFFER: usize = 256;
 
 /// Container for votes associated with a specific block hash.
 #[derive(Default)]
@@ -20,10 +30,6 @@ struct VotesPriorityQueue {
 }
 
 impl VotesPriorityQueue {
-    fn new() -> Self {
-        Self { heap: BinaryHeap::new() }
-    }
-
     fn push(&mut self, vote_data: VoteData) {
         self.heap.push(Reverse(vote_data));
     }
@@ -49,60 +55,160 @@ impl Ord for VoteData {
     }
 }
 
+/// Votes organized by the hash of their target block, with the queue of their targets.
+#[derive(Default)]
+struct VoteQueue {
+    votes: HashMap<B256, VoteMessages>,
+    pq: VotesPriorityQueue,
+}
+
+impl VoteQueue {
+    fn votes_for(&self, block_hash: &B256) -> usize {
+        self.votes.get(block_hash).map_or(0, |vm| vm.vote_messages.len())
+    }
+
+    /// Returns whether the validator with `vote_address` already voted for the block.
+    fn has_voted(&self, block_hash: &B256, vote_address: &VoteAddress) -> bool {
+        self.votes.get(block_hash).is_some_and(|vm| {
+            vm.vote_messages.iter().any(|vote| vote.vote_address == *vote_address)
+        })
+    }
+
+    fn push(&mut self, votes: Vec<VoteEnvelope>, data: VoteData) {
+        // Add to priority queue if this is a new block
+        if !self.votes.contains_key(&data.target_hash) {
+            self.pq.push(data);
+        }
+        self.votes.entry(data.target_hash).or_default().vote_messages.extend(votes);
+    }
+
+    /// Removes the votes of the block with the lowest target.
+    fn pop(&mut self) -> Option<(VoteData, Vec<VoteEnvelope>)> {
+        let data = self.pq.pop()?;
+        let votes = self.votes.remove(&data.target_hash).map(|vm| vm.vote_messages);
+        Some((data, votes.unwrap_or_default()))
+    }
+
+    fn len(&self) -> usize {
+        self.votes.values().map(|vm| vm.vote_messages.len()).sum()
+    }
+}
+
 /// Global in-memory pool of incoming Parlia votes.
 ///
-/// This mirrors the simple approach used by the slashing pool: keep votes in
-/// memory until they're consumed by another component. Votes are de-duplicated
-/// by their RLP hash and organized by block hash.
+/// Like the vote pool of bsc-geth, votes for imported blocks are kept apart from the votes for
+/// blocks not imported yet, which are promoted once their block gets imported. Votes are
+/// de-duplicated by their RLP hash and by validator for each block, and limited per block and by
+/// the distance of their target to the head, so a peer cannot fill the memory with votes for far
+/// or unknown blocks, nor a validator crowd out the others.
 struct VotePool {
     /// Hashes of votes we've already seen in this window.
     received_votes: HashSet<B256>,
-    /// Collected votes organized by block hash.
-    cur_votes: HashMap<B256, VoteMessages>,
-    /// Priority queue for efficiently finding votes to prune.
-    cur_votes_pq: VotesPriorityQueue,
+    /// Votes for imported blocks.
+    cur_votes: VoteQueue,
+    /// Votes for blocks that are not imported yet.
+    future_votes: VoteQueue,
 }
 
 impl VotePool {
     fn new() -> Self {
-        Self { 
-            received_votes: HashSet::new(), 
-            cur_votes: HashMap::new(),
-            cur_votes_pq: VotesPriorityQueue::new(),
+        Self {
+            received_votes: HashSet::new(),
+            cur_votes: VoteQueue::default(),
+            future_votes: VoteQueue::default(),
         }
     }
 
-    fn insert(&mut self, vote: VoteEnvelope) {
+    /// Inserts a vote given the canonical head and whether its target block is imported.
+    ///
+    /// Returns `false` if the vote is already known, out of range, its validator already voted
+    /// for the block, or the block is full.
+    fn insert(&mut self, vote: VoteEnvelope, head: BlockNumber, target_known: bool) -> bool {
+        let target = vote.data.target_number;
+        if target.saturating_add(LOWER_LIMIT_OF_VOTE_BLOCK_NUMBER - 1) < head ||
+            target > head.saturating_add(UPPER_LIMIT_OF_VOTE_BLOCK_NUMBER)
+        {
+            return false;
+        }
+
         let vote_hash = vote.hash();
-        if self.received_votes.insert(vote_hash) {
-            // Use target_hash as the key for organizing votes
-            let block_hash = vote.data.target_hash;
-            
-            // Add to priority queue if this is a new block
-            if !self.cur_votes.contains_key(&block_hash) {
-                self.cur_votes_pq.push(vote.data);
+        if self.received_votes.contains(&vote_hash) {
+            return false;
+        }
+        let (queue, limit) = if target_known {
+            (&mut self.cur_votes, MAX_CUR_VOTE_AMOUNT_PER_BLOCK)
+        } else {
+            (&mut self.future_votes, MAX_FUTURE_VOTE_AMOUNT_PER_BLOCK)
+        };
+        if queue.has_voted(&vote.data.target_hash, &vote.vote_address) ||
+            queue.votes_for(&vote.data.target_hash) >= limit
+        {
+            return false;
+        }
+
+        self.received_votes.insert(vote_hash);
+        let data = vote.data;
+        queue.push(vec![vote], data);
+        true
+    }
+
+    /// Moves the future votes for the imported blocks up to `latest_block_number` to the current
+    /// votes and returns them. Future votes for blocks at or below it that are still unknown are
+    /// dropped, as they target a block that was never imported.
+    fn promote(
+        &mut self,
+        latest_block_number: BlockNumber,
+        is_known: impl Fn(&B256) -> bool,
+    ) -> Vec<VoteEnvelope> {
+        let mut promoted = Vec::new();
+        while let Some(vote_data) = self.future_votes.pq.peek() {
+            if vote_data.target_number > latest_block_number.saturating_add(1) {
+                break;
+            }
+            let known = is_known(&vote_data.target_hash);
+            if !known && vote_data.target_number > latest_block_number {
+                break;
+            }
+
+            let (vote_data, votes) = self.future_votes.pop().expect("peeked");
+            let mut dropped = votes;
+            if known {
+                // the current votes of the block may exceed their limit here, which is harmless
+                let (fresh, duplicates): (Vec<_>, Vec<_>) =
+                    dropped.into_iter().partition(|vote| {
+                        !self.cur_votes.has_voted(&vote_data.target_hash, &vote.vote_address)
+                    });
+                promoted.extend_from_slice(&fresh);
+                self.cur_votes.push(fresh, vote_data);
+                dropped = duplicates;
+            }
+            for vote in dropped {
+                self.received_votes.remove(&vote.hash());
             }
-            
-            self.cur_votes.entry(block_hash).or_default().vote_messages.push(vote);
         }
+        promoted
     }
 
     fn drain(&mut self) -> Vec<VoteEnvelope> {
         self.received_votes.clear();
-        self.cur_votes_pq = VotesPriorityQueue::new();
-        let mut all_votes = Vec::new();
-        for (_, vote_messages) in self.cur_votes.drain() {
-            all_votes.extend(vote_messages.vote_messages);
-        }
-        all_votes
+        self.future_votes = VoteQueue::default();
+        std::mem::take(&mut self.cur_votes)
+            .votes
+            .into_values()
+            .flat_map(|vm| vm.vote_messages)
+            .collect()
+    }
+
+    fn contains(&self, vote_hash: &B256) -> bool {
+        self.received_votes.contains(vote_hash)
     }
 
-    fn len(&self) -> usize { 
-        self.cur_votes.values().map(|vm| vm.vote_messages.len()).sum() 
+    fn len(&self) -> usize {
+        self.cur_votes.len() + self.future_votes.len()
     }
 
     fn fetch_vote_by_block_hash(&self, block_hash: B256) -> Vec<VoteEnvelope> {
-        if let Some(vote_messages) = self.cur_votes.get(&block_hash) {
+        if let Some(vote_messages) = self.cur_votes.votes.get(&block_hash) {
             vote_messages.vote_messages.clone()
         } else {
             Vec::new()
@@ -113,21 +219,17 @@ impl VotePool {
     /// Removes votes where targetNumber + LOWER_LIMIT_OF_VOTE_BLOCK_NUMBER - 1 < latestBlockNumber
     fn prune(&mut self, latest_block_number: BlockNumber) {
         // Remove votes in the range [, latestBlockNumber - LOWER_LIMIT_OF_VOTE_BLOCK_NUMBER]
-        while let Some(vote_data) = self.cur_votes_pq.peek() {
-            if vote_data.target_number + LOWER_LIMIT_OF_VOTE_BLOCK_NUMBER - 1 < latest_block_number {
-                // Remove from priority queue
-                let vote_data = self.cur_votes_pq.pop().unwrap();
-                let block_hash = vote_data.target_hash;
-                
-                // Remove from votes map and received_votes set
-                if let Some(vote_box) = self.cur_votes.remove(&block_hash) {
-                    for vote in vote_box.vote_messages {
-                        let vote_hash = vote.hash();
-                        self.received_votes.remove(&vote_hash);
-                    }
+        for queue in [&mut self.cur_votes, &mut self.future_votes] {
+            while let Some(vote_data) = queue.pq.peek() {
+                if vote_data.target_number.saturating_add(LOWER_LIMIT_OF_VOTE_BLOCK_NUMBER - 1) >=
+                    latest_block_number
+                {
+                    break;
+                }
+                let (_, votes) = queue.pop().expect("peeked");
+                for vote in votes {
+                    self.received_votes.remove(&vote.hash());
                 }
-            } else {
-                break;
             }
         }
     }
@@ -136,9 +238,39 @@ impl VotePool {
 /// Global singleton pool.
 static VOTE_POOL: Lazy<RwLock<VotePool>> = Lazy::new(|| RwLock::new(VotePool::new()));
 
+/// Votes entering the current votes of the pool.
+static NEW_VOTES: Lazy<broadcast::Sender<VoteEnvelope>> =
+    Lazy::new(|| broadcast::channel(NEW_VOTES_BUFFER).0);
+
+/// Returns whether the block with the given hash was imported.
+fn is_imported(block_hash: &B256) -> bool {
+    crate::node::evm::util::HEADER_CACHE_READER
+        .lock()
+        .unwrap()
+        .get_header_by_hash(block_hash)
+        .is_some()
+}
+
 /// Insert a single vote into the pool (deduplicated by hash).
-pub fn put_vote(vote: VoteEnvelope) {
-    VOTE_POOL.write().expect("vote pool poisoned").insert(vote);
+///
+/// Returns `false` if the vote was not added: already known, too far from the canonical head, or
+/// its block already has the maximum number of votes.
+pub fn put_vote(vote: VoteEnvelope) -> bool {
+    let Some(head) = crate::shared::get_best_block_number() else { return false };
+    let target_known = is_imported(&vote.data.target_hash);
+    let accepted =
+        VOTE_POOL.write().expect("vote pool poisoned").insert(vote.clone(), head, target_known);
+    if accepted && target_known {
+        // sending only fails when nobody listens
+        let _ = NEW_VOTES.send(vote);
+    }
+    accepted
+}
+
+/// Subscribes to the votes for imported blocks accepted by the pool, including the future votes
+/// once their block is imported.
+pub fn subscribe_new_votes() -> broadcast::Receiver<VoteEnvelope> {
+    NEW_VOTES.subscribe()
 }
 
 /// Drain all pending votes.
@@ -146,9 +278,14 @@ pub fn drain() -> Vec<VoteEnvelope> {
     VOTE_POOL.write().expect("vote pool poisoned").drain()
 }
 
+/// Returns `true` if the vote with the given hash is already in the pool.
+pub fn contains(vote_hash: &B256) -> bool {
+    VOTE_POOL.read().expect("vote pool poisoned").contains(vote_hash)
+}
+
 /// Current number of queued votes.
-pub fn len() -> usize { 
-    VOTE_POOL.read().expect("vote pool poisoned").len() 
+pub fn len() -> usize {
+    VOTE_POOL.read().expect("vote pool poisoned").len()
 }
 
 /// Fetch votes by block hash.
@@ -156,9 +293,116 @@ pub fn fetch_vote_by_block_hash(block_hash: B256) -> Vec<VoteEnvelope> {
     VOTE_POOL.read().expect("vote pool poisoned").fetch_vote_by_block_hash(block_hash)
 }
 
+/// Promotes the future votes for the imported block `block_hash` and the blocks below it, then
+/// prunes old votes based on the number of the block.
+pub fn on_block_imported(block_number: BlockNumber, block_hash: B256) {
+    let promoted = {
+        let mut pool = VOTE_POOL.write().expect("vote pool poisoned");
+        let promoted = pool.promote(block_number, |hash| *hash == block_hash || is_imported(hash));
+        pool.prune(block_number);
+        promoted
+    };
+    for vote in promoted {
+        let _ = NEW_VOTES.send(vote);
+    }
+}
+
 /// Prune old votes based on the latest block number.
 pub fn prune(latest_block_number: BlockNumber) {
     VOTE_POOL.write().expect("vote pool poisoned").prune(latest_block_number)
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
 
+    fn vote(target_number: u64, target_hash: B256, voter: u8) -> VoteEnvelope {
+        VoteEnvelope {
+            vote_address: [voter; 48].into(),
+            signature: Default::default(),
+            data: VoteData { target_number, target_hash, ..Default::default() },
+        }
+    }
+
+    #[test]
+    fn test_rejects_votes_out_of_range() {
+        let mut pool = VotePool::new();
+        let head = 1000;
+        assert!(!pool.insert(vote(head + 12, B256::ZERO, 1), head, false));
+        assert!(!pool.insert(vote(head - 256, B256::ZERO, 1), head, true));
+        assert!(pool.insert(vote(head + 11, B256::ZERO, 1), head, false));
+        assert!(pool.insert(vote(head - 255, B256::repeat_byte(1), 1), head, true));
+        assert!(!pool.insert(vote(head - 255, B256::repeat_byte(1), 1), head, true));
+        assert_eq!(pool.len(), 2);
+    }
+
+    #[test]
+    fn test_limits_votes_per_block() {
+        let mut pool = VotePool::new();
+        let (head, hash) = (1000, B256::repeat_byte(1));
+        for voter in 0..MAX_CUR_VOTE_AMOUNT_PER_BLOCK as u8 {
+            assert!(pool.insert(vote(head, hash, voter), head, true));
+        }
+        assert!(!pool.insert(vote(head, hash, 100), head, true));
+
+        let future = B256::repeat_byte(2);
+        for voter in 0..MAX_FUTURE_VOTE_AMOUNT_PER_BLOCK as u8 {
+            assert!(pool.insert(vote(head + 1, future, voter), head, false));
+        }
+        assert!(!pool.insert(vote(head + 1, future, 100), head, false));
+    }
+
+    #[test]
+    fn test_limits_votes_per_validator() {
+        let mut pool = VotePool::new();
+        let (head, hash) = (1000, B256::repeat_byte(1));
+
+        // a validator floods the block with votes from different sources
+        let flood = |source_number| {
+            let mut vote = vote(head, hash, 1);
+            vote.data.source_number = source_number;
+            vote
+        };
+        assert!(pool.insert(flood(0), head, true));
+        for source_number in 1..=MAX_CUR_VOTE_AMOUNT_PER_BLOCK as u64 {
+            assert!(!pool.insert(flood(source_number), head, true));
+        }
+        assert_eq!(pool.fetch_vote_by_block_hash(hash).len(), 1);
+
+        // the other validators still get their votes in
+        for voter in 2..=MAX_CUR_VOTE_AMOUNT_PER_BLOCK as u8 {
+            assert!(pool.insert(vote(head, hash, voter), head, true));
+        }
+        assert_eq!(pool.fetch_vote_by_block_hash(hash).len(), MAX_CUR_VOTE_AMOUNT_PER_BLOCK);
+
+        // targets chosen by peers do not overflow the range checks
+        assert!(!pool.insert(vote(u64::MAX, B256::repeat_byte(2), 1), head, false));
+        pool.prune(u64::MAX);
+        assert_eq!(pool.len(), 0);
+    }
+
+    #[test]
+    fn test_promotes_future_votes() {
+        let mut pool = VotePool::new();
+        let head = 1000;
+        let (imported, orphan, ahead) =
+            (B256::repeat_byte(1), B256::repeat_byte(2), B256::repeat_byte(3));
+        assert!(pool.insert(vote(head + 1, imported, 1), head, false));
+        assert!(pool.insert(vote(head + 1, orphan, 1), head, false));
+        assert!(pool.insert(vote(head + 5, ahead, 1), head, false));
+        assert!(pool.fetch_vote_by_block_hash(imported).is_empty());
+
+        // the block at head + 1 is imported, its sibling never was
+        let promoted = pool.promote(head + 1, |hash| *hash == imported);
+        assert_eq!(promoted, vec![vote(head + 1, imported, 1)]);
+        assert_eq!(pool.fetch_vote_by_block_hash(imported).len(), 1);
+        assert_eq!(pool.len(), 2);
+        assert!(!pool.contains(&vote(head + 1, orphan, 1).hash()));
+
+        // the far future vote stays until its block comes
+        assert!(pool.promote(head + 2, |_| false).is_empty());
+        assert_eq!(pool.future_votes.len(), 1);
+        pool.prune(head + 5 + LOWER_LIMIT_OF_VOTE_BLOCK_NUMBER);
+        assert_eq!(pool.len(), 0);
+    }
+}
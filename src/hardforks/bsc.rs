@@ -61,8 +61,10 @@ hardfork!(
         /// BSC `Lorentz` hardfork
         Lorentz,
         /// BSC `Maxwell` hardfork
-        #[default]
         Maxwell,
+        /// BSC `Fermi` hardfork - 450 ms blocks
+        #[default]
+        Fermi,
     }
 );
 
@@ -110,7 +112,7 @@ impl BscHardfork {
             (Self::Pascal.boxed(), ForkCondition::Timestamp(1742436600)), /* 2025-03-20 02:10:00 AM UTC - deployed with Prague */
             (Self::Lorentz.boxed(), ForkCondition::Timestamp(1745903100)), /* 2025-04-29 05:05:00 AM UTC */
             (Self::Maxwell.boxed(), ForkCondition::Timestamp(1751250600)), /* 2025-06-30 02:30:00 AM UTC */
-            // Note: FermiTime is nil in official BSC config, so we don't include it yet
+            (Self::Fermi.boxed(), ForkCondition::Timestamp(1768357800)), /* 2026-01-14 02:30:00 AM UTC */
         ])
     }
 
@@ -155,6 +157,7 @@ impl BscHardfork {
             (Self::Pascal.boxed(), ForkCondition::Timestamp(1740452880)),
             (Self::Lorentz.boxed(), ForkCondition::Timestamp(1744097580)),
             (Self::Maxwell.boxed(), ForkCondition::Timestamp(1748243100)),
+            (Self::Fermi.boxed(), ForkCondition::Timestamp(1762741500)),
         ])
     }
 
@@ -198,6 +201,8 @@ impl BscHardfork {
             (Self::Pascal.boxed(), ForkCondition::Timestamp(1754967081)),
             (Self::Lorentz.boxed(), ForkCondition::Timestamp(1754967081)),
             (Self::Maxwell.boxed(), ForkCondition::Timestamp(1754967101)),
+            // Not scheduled on qanet yet
+            (Self::Fermi.boxed(), ForkCondition::Never),
         ])
     }
 }
@@ -240,7 +245,10 @@ impl From<BscHardfork> for SpecId {
             BscHardfork::HaberFix |
             BscHardfork::Bohr |
             BscHardfork::Tycho => SpecId::CANCUN,
-            BscHardfork::Pascal | BscHardfork::Lorentz | BscHardfork::Maxwell => SpecId::PRAGUE,
+            BscHardfork::Pascal |
+            BscHardfork::Lorentz |
+            BscHardfork::Maxwell |
+            BscHardfork::Fermi => SpecId::PRAGUE,
         }
     }
 }
@@ -249,6 +257,64 @@ impl From<BscHardfork> for SpecId {
 mod tests {
     use super::*;
     use crate::chainspec::{bsc::bsc_mainnet, bsc_chapel::bsc_testnet};
+    use alloy_primitives::hex;
+    use reth_chainspec::{ForkHash, ForkId, Head};
+
+    fn fork_hash(hash: &str) -> ForkHash {
+        ForkHash(hex::decode(hash).unwrap().try_into().unwrap())
+    }
+
+    #[test]
+    fn test_fermi_fork_id_mainnet() {
+        let spec = bsc_mainnet();
+        let head = |timestamp| Head { number: 60_000_000, timestamp, ..Default::default() };
+
+        assert_eq!(
+            spec.fork_id(&head(1768357799)),
+            ForkId { hash: fork_hash("098d24ac"), next: 1768357800 }
+        );
+        assert_eq!(
+            spec.fork_id(&head(1768357800)),
+            ForkId { hash: fork_hash("4d518ce1"), next: 0 }
+        );
+    }
+
+    #[test]
+    fn test_fermi_fork_id_testnet() {
+        let spec = bsc_testnet();
+        let head = |timestamp| Head { number: 70_000_000, timestamp, ..Default::default() };
+
+        assert_eq!(
+            spec.fork_id(&head(1762741499)),
+            ForkId { hash: fork_hash("38469028"), next: 1762741500 }
+        );
+        assert_eq!(
+            spec.fork_id(&head(1762741500)),
+            ForkId { hash: fork_hash("5b240570"), next: 0 }
+        );
+    }
+
+    #[test]
+    fn test_fermi_spec() {
+        let spec = crate::chainspec::BscChainSpec::from(bsc_testnet());
+        assert_eq!(
+            crate::node::evm::config::revm_spec_by_timestamp_and_block_number(
+                spec.clone(),
+                1762741499,
+                70_000_000
+            ),
+            BscHardfork::Maxwell
+        );
+        assert_eq!(
+            crate::node::evm::config::revm_spec_by_timestamp_and_block_number(
+                spec,
+                1762741500,
+                70_000_000
+            ),
+            BscHardfork::Fermi
+        );
+        assert_eq!(SpecId::from(BscHardfork::Fermi), SpecId::PRAGUE);
+    }
 
     #[test]
     fn test_hardfork_activation_order_differences() {
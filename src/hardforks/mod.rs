@@ -1,5 +1,6 @@
 //! Hard forks of bsc protocol.
 #![allow(unused)]
+use crate::consensus::parlia::timing::{ParliaTiming, ParliaTimingSchedule};
 use bsc::BscHardfork;
 use reth_chainspec::{EthereumHardforks, ForkCondition};
 
@@ -11,6 +12,16 @@ pub trait BscHardforks: EthereumHardforks {
     /// [`ForkCondition::Never`].
     fn bsc_fork_activation(&self, fork: BscHardfork) -> ForkCondition;
 
+    /// Returns the Parlia timing schedule of the chain, the one of the public networks by default.
+    fn parlia_timing(&self) -> &ParliaTimingSchedule {
+        ParliaTimingSchedule::bsc()
+    }
+
+    /// Returns the Parlia timing at the given block.
+    fn parlia_timing_at(&self, block_number: u64, timestamp: u64) -> ParliaTiming {
+        self.parlia_timing().timing_at(self, block_number, timestamp)
+    }
+
     /// Convenience method to check if [`BscHardfork::Ramanujan`] is firstly active at a given
     /// block.
     fn is_ramanujan_transition_at_block(&self, block_number: u64) -> bool {
@@ -233,4 +244,10 @@ pub trait BscHardforks: EthereumHardforks {
         self.is_london_active_at_block(block_number) &&
         self.bsc_fork_activation(BscHardfork::Maxwell).active_at_timestamp(timestamp)
     }
+
+    /// Convenience method to check if [`BscHardfork::Fermi`] is active at a given timestamp.
+    fn is_fermi_active_at_timestamp(&self, block_number: u64, timestamp: u64) -> bool {
+        self.is_london_active_at_block(block_number) &&
+        self.bsc_fork_activation(BscHardfork::Fermi).active_at_timestamp(timestamp)
+    }
 }
@@ -1,9 +1,18 @@
-use clap::{Args, Parser};
-use reth::{builder::NodeHandle, cli::Cli};
+use clap::Parser;
+use reth::{
+    builder::NodeHandle,
+    cli::{Cli, Commands},
+};
 use reth_bsc::node::consensus::BscConsensus;
 use reth_bsc::{
     chainspec::parser::BscChainSpecParser,
-    node::{evm::config::BscEvmConfig, BscNode},
+    node::{
+        args::BscNodeArgs,
+        evm::config::BscEvmConfig,
+        miner::{BscMiner, DoubleSignReporter, VoteManager},
+        storage::{set_sidecar_retention_blocks, BscTables},
+        BscNode,
+    },
 };
 use std::sync::Arc;
 
@@ -12,11 +21,6 @@ use std::sync::Arc;
 #[global_allocator]
 static ALLOC: tikv_jemallocator::Jemalloc = tikv_jemallocator::Jemalloc;
 
-/// No Additional arguments
-#[derive(Debug, Clone, Copy, Default, Args)]
-#[non_exhaustive]
-struct NoArgs;
-
 fn main() -> eyre::Result<()> {
     reth_cli_util::sigsegv_handler::install();
 
@@ -25,14 +29,33 @@ fn main() -> eyre::Result<()> {
         std::env::set_var("RUST_BACKTRACE", "1");
     }
 
-    Cli::<BscChainSpecParser, NoArgs>::parse().run_with_components::<BscNode>(
+    let mut cli = Cli::<BscChainSpecParser, BscNodeArgs>::parse();
+    // Patch the chain spec before anything, the fork id and filter are derived from it
+    if let Commands::Node(command) = &mut cli.command {
+        if !command.ext.overrides.is_empty() {
+            command.chain = Arc::new(command.ext.overrides.apply(&command.chain)?);
+        }
+    }
+
+    cli.run_with_components::<BscNode>(
         |spec| (BscEvmConfig::new(spec.clone()), BscConsensus::new(spec)),
-        async move |builder, _| {
+        async move |builder, args| {
+            builder.db().create_tables_for::<BscTables>()?;
+            set_sidecar_retention_blocks(args.storage.sidecar_retention_blocks);
+
+            let mining_config = args.mining.mining_config()?;
             let (node, engine_handle_tx) = BscNode::new();
+            let mut node =
+                node.with_disable_peer_tx_broadcast(args.network.disable_peer_tx_broadcast);
+            if let Some(config) = &mining_config {
+                node = node.with_mining(config.mining_ctx());
+            }
+            let finalized_headers = reth_bsc::rpc::parlia::finalized_headers_channel();
+            let rpc_finalized_headers = finalized_headers.clone();
             let NodeHandle { node, node_exit_future: exit_future } =
                 builder.node(node)
                     .extend_rpc_modules(move |ctx| {
-                        tracing::info!("Start to register Parlia RPC API: parlia_getSnapshot");
+                        tracing::info!("Start to register Parlia RPC API");
                         use reth_bsc::rpc::parlia::{ParliaApiImpl, ParliaApiServer, DynSnapshotProvider};
                         
                         let snapshot_provider = if let Some(provider) = reth_bsc::shared::get_snapshot_provider() {
@@ -43,10 +66,16 @@ fn main() -> eyre::Result<()> {
                         };
                         
                         let wrapped_provider = Arc::new(DynSnapshotProvider::new(snapshot_provider));
-                        let parlia_api = ParliaApiImpl::new(wrapped_provider);
+                        let parlia_api = ParliaApiImpl::new(wrapped_provider, ctx.config().chain.clone())
+                            .with_finalized_headers(rpc_finalized_headers);
                         ctx.modules.merge_configured(parlia_api.into_rpc())?;
 
                         tracing::info!("Succeed to register Parlia RPC API");
+
+                        use reth_bsc::rpc::{BlobSidecarApiImpl, BlobSidecarApiServer};
+                        let sidecar_api = BlobSidecarApiImpl::new(ctx.provider().clone());
+                        ctx.modules.merge_configured(sidecar_api.into_rpc())?;
+                        tracing::info!("Succeed to register blob sidecar RPC API");
                         Ok(())
                     })
                     .launch().await?;
@@ -54,6 +83,39 @@ fn main() -> eyre::Result<()> {
             // Send the engine handle to the network
             engine_handle_tx.send(node.beacon_engine_handle.clone()).unwrap();
 
+            if let Some(snapshot_provider) = reth_bsc::shared::get_snapshot_provider() {
+                node.task_executor.spawn(reth_bsc::rpc::parlia::track_finalized_headers(
+                    node.provider.clone(),
+                    snapshot_provider.clone(),
+                    finalized_headers,
+                ));
+            }
+
+            if let Some(config) = mining_config {
+                if let Some(vote_config) = config.vote.clone() {
+                    let vote_manager = VoteManager::new(node.provider.clone(), vote_config)?;
+                    node.task_executor.spawn_critical("bsc vote manager", vote_manager.run());
+                }
+                if config.submit_double_sign_evidence {
+                    let reporter = DoubleSignReporter::new(
+                        node.provider.clone(),
+                        node.pool.clone(),
+                        node.chain_spec(),
+                        &config,
+                    );
+                    node.task_executor.spawn(reporter.run());
+                }
+                let miner = BscMiner::new(
+                    node.provider.clone(),
+                    node.chain_spec(),
+                    config,
+                    node.payload_builder_handle.clone(),
+                    node.beacon_engine_handle.clone(),
+                    node.network.clone(),
+                )?;
+                node.task_executor.spawn_critical("bsc miner", miner.run());
+            }
+
             exit_future.await
         },
     )?;
@@ -0,0 +1,26 @@
+//! Command line arguments of the BSC node.
+use crate::{
+    chainspec::overrides::BscHardforkOverrides,
+    node::{miner::BscMiningArgs, network::BscNetworkArgs, storage::BscStorageArgs},
+};
+use clap::Args;
+
+/// Arguments added by the BSC node to the reth `node` command.
+#[derive(Debug, Clone, Default, Args)]
+pub struct BscNodeArgs {
+    /// Block production and voting.
+    #[command(flatten)]
+    pub mining: BscMiningArgs,
+
+    /// Peer-to-peer networking.
+    #[command(flatten)]
+    pub network: BscNetworkArgs,
+
+    /// Storage of the BSC specific data.
+    #[command(flatten)]
+    pub storage: BscStorageArgs,
+
+    /// Overrides of the hardfork activations.
+    #[command(flatten)]
+    pub overrides: BscHardforkOverrides,
+}
@@ -2,7 +2,10 @@ use crate::{
     hardforks::BscHardforks, 
     node::BscNode, 
     BscBlock, BscBlockBody, BscPrimitives,
-    consensus::parlia::{provider::EnhancedDbSnapshotProvider, Parlia, util::calculate_millisecond_timestamp},
+    consensus::parlia::{
+        header_verifier::ParliaHeaderVerifier, provider::EnhancedDbSnapshotProvider, Parlia,
+        util::calculate_millisecond_timestamp,
+    },
 };
 use alloy_consensus::{Header, TxReceipt};
 use alloy_primitives::{B256, Bytes};
@@ -46,6 +49,12 @@ where
         crate::shared::set_header_provider(Arc::new(ctx.provider().clone()))
             .unwrap_or_else(|e| panic!("Failed to set global header provider: {e}"));
 
+        crate::shared::set_best_block_number_provider(Arc::new(ctx.provider().clone()))
+            .unwrap_or_else(|e| panic!("Failed to set global best block number provider: {e}"));
+
+        crate::shared::set_block_provider(Arc::new(ctx.provider().clone()))
+            .unwrap_or_else(|e| panic!("Failed to set global block provider: {e}"));
+
         Ok(Arc::new(BscConsensus::new(ctx.chain_spec())))
     }
 }
@@ -57,12 +66,17 @@ where
 pub struct BscConsensus<ChainSpec> {
     base: EthBeaconConsensus<ChainSpec>,
     parlia: Arc<Parlia<ChainSpec>>,
+    header_verifier: Arc<ParliaHeaderVerifier<ChainSpec>>,
     chain_spec: Arc<ChainSpec>,
 }
 
 impl<ChainSpec: EthChainSpec + BscHardforks + 'static> BscConsensus<ChainSpec> {
     pub fn new(chain_spec: Arc<ChainSpec>) -> Self {
-        Self { base: EthBeaconConsensus::new(chain_spec.clone()), parlia: Arc::new(Parlia::new(chain_spec.clone(), 200)), chain_spec }
+        let epoch = chain_spec.parlia_timing().base.epoch_length;
+        let parlia = Arc::new(Parlia::new(chain_spec.clone(), epoch));
+        let header_verifier = Arc::new(ParliaHeaderVerifier::new(parlia.clone()));
+        let base = EthBeaconConsensus::new(chain_spec.clone());
+        Self { base, parlia, header_verifier, chain_spec }
     }
 }
 
@@ -109,6 +123,15 @@ impl<ChainSpec: EthChainSpec + BscHardforks + 'static> HeaderValidator<Header>
             }
         }
 
+        // the seal is checked against the snapshots rebuilt from the synced headers
+        if let Some(snapshot_provider) = crate::shared::get_snapshot_provider() {
+            let verified = self.header_verifier.verify(snapshot_provider.as_ref(), header, parent);
+            if let Err(err) = verified {
+                tracing::warn!("Failed to verify header seal, block_number: {}, err: {}", err.number, err);
+                return Err(ConsensusError::Other(err.to_string()))
+            }
+        }
+
         Ok(())
     }
 }
@@ -1,7 +1,12 @@
 use std::sync::Arc;
 
 use crate::{
-    node::{engine_api::payload::BscPayloadTypes, BscNode},
+    node::{
+        engine_api::payload::BscPayloadTypes,
+        evm::config::{BscEvmConfig, BscMiningCtx},
+        miner::BscPayloadBuilder,
+        BscNode,
+    },
     BscBlock, BscPrimitives,
 };
 use alloy_eips::eip7685::Requests;
@@ -9,14 +14,14 @@ use alloy_primitives::U256;
 use reth::{
     api::FullNodeTypes,
     builder::{components::PayloadServiceBuilder, BuilderContext},
-    payload::{PayloadBuilderHandle, PayloadServiceCommand},
-    transaction_pool::TransactionPool,
+    payload::{PayloadBuilderHandle, PayloadBuilderService},
+    transaction_pool::{PoolTransaction, TransactionPool},
 };
-use reth_evm::ConfigureEvm;
+use reth_basic_payload_builder::{BasicPayloadJobGenerator, BasicPayloadJobGeneratorConfig};
+use reth_node_core::cli::config::PayloadBuilderConfig;
 use reth_payload_primitives::BuiltPayload;
-use reth_primitives::SealedBlock;
-use tokio::sync::{broadcast, mpsc};
-use tracing::warn;
+use reth_primitives::{SealedBlock, TransactionSigned};
+use reth_provider::CanonStateSubscriptions;
 
 /// Built payload for BSC. This is similar to [`EthBuiltPayload`] but without sidecars as those
 /// included into [`BscBlock`].
@@ -46,72 +51,61 @@ impl BuiltPayload for BscBuiltPayload {
     }
 }
 
-#[derive(Debug, Clone, Copy, Default)]
+/// Spawns the payload builder service producing Parlia blocks.
+///
+/// Without a mining context the service still runs, but every build attempt fails as the system
+/// transactions can't be signed.
+#[derive(Debug, Clone, Default)]
 #[non_exhaustive]
-pub struct BscPayloadServiceBuilder;
+pub struct BscPayloadServiceBuilder {
+    /// Mining context handed to the payload builder.
+    mining: Option<BscMiningCtx>,
+}
+
+impl BscPayloadServiceBuilder {
+    /// Sets the mining context used to build and sign blocks.
+    pub fn with_mining(mut self, mining: BscMiningCtx) -> Self {
+        self.mining = Some(mining);
+        self
+    }
+}
 
-impl<Node, Pool, Evm> PayloadServiceBuilder<Node, Pool, Evm> for BscPayloadServiceBuilder
+impl<Node, Pool> PayloadServiceBuilder<Node, Pool, BscEvmConfig> for BscPayloadServiceBuilder
 where
     Node: FullNodeTypes<Types = BscNode>,
-    Pool: TransactionPool,
-    Evm: ConfigureEvm,
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>
+        + Unpin
+        + 'static,
 {
     async fn spawn_payload_builder_service(
         self,
         ctx: &BuilderContext<Node>,
-        _pool: Pool,
-        _evm_config: Evm,
+        pool: Pool,
+        evm_config: BscEvmConfig,
     ) -> eyre::Result<PayloadBuilderHandle<BscPayloadTypes>> {
-        let (tx, mut rx) = mpsc::unbounded_channel();
+        let conf = ctx.payload_builder_config();
+        let mut payload_builder =
+            BscPayloadBuilder::new(ctx.provider().clone(), pool, evm_config, self.mining)
+                .with_extra_vanity(conf.extra_data_bytes());
+        if let Some(gas_limit) = conf.gas_limit() {
+            payload_builder = payload_builder.with_desired_gas_limit(gas_limit);
+        }
 
-        ctx.task_executor().spawn_critical("payload builder", async move {
-            let mut subscriptions = Vec::new();
+        let payload_job_config = BasicPayloadJobGeneratorConfig::default()
+            .interval(conf.interval())
+            .deadline(conf.deadline())
+            .max_payload_tasks(conf.max_payload_tasks());
+        let payload_generator = BasicPayloadJobGenerator::with_builder(
+            ctx.provider().clone(),
+            ctx.task_executor().clone(),
+            payload_job_config,
+            payload_builder,
+        );
+        let (payload_service, payload_service_handle) =
+            PayloadBuilderService::new(payload_generator, ctx.provider().canonical_state_stream());
 
-            while let Some(message) = rx.recv().await {
-                match message {
-                    PayloadServiceCommand::Subscribe(tx) => {
-                        let (events_tx, events_rx) = broadcast::channel(100);
-                        // Retain senders to make sure that channels are not getting closed
-                        subscriptions.push(events_tx);
-                        let _ = tx.send(events_rx);
-                    }
-                    message => warn!(?message, "Noop payload service received a message"),
-                }
-            }
-        });
+        ctx.task_executor().spawn_critical("payload builder service", Box::pin(payload_service));
 
-        Ok(PayloadBuilderHandle::new(tx))
+        Ok(payload_service_handle)
     }
 }
-
-// impl From<EthBuiltPayload> for BscBuiltPayload {
-//     fn from(value: EthBuiltPayload) -> Self {
-//         let EthBuiltPayload { id, block, fees, sidecars, requests } = value;
-//         BscBuiltPayload {
-//             id,
-//             block: block.into(),
-//             fees,
-//             requests,
-//         }
-//     }
-// }
-
-// pub struct BscPayloadBuilder<Inner> {
-//     inner: Inner,
-// }
-
-// impl<Inner> PayloadBuilder for BscPayloadBuilder<Inner>
-// where
-//     Inner: PayloadBuilder<BuiltPayload = EthBuiltPayload>,
-// {
-//     type Attributes = Inner::Attributes;
-//     type BuiltPayload = BscBuiltPayload;
-//     type Error = Inner::Error;
-
-//     fn try_build(
-//         &self,
-//         args: BuildArguments<Self::Attributes, Self::BuiltPayload>,
-//     ) -> Result<BuildOutcome<Self::BuiltPayload>, PayloadBuilderError> {
-//         let outcome = self.inner.try_build(args)?;
-//     }
-// }
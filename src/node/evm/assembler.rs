@@ -92,7 +92,7 @@ where
             };
         }
 
-        let header = Header {
+        let mut header = Header {
             parent_hash: eth_ctx.parent_hash,
             ommers_hash: EMPTY_OMMER_ROOT_HASH,
             beneficiary: evm_env.block_env.beneficiary,
@@ -116,6 +116,16 @@ where
             requests_hash,
         };
 
+        // When building a block the Parlia specific fields come from the prepared header
+        // template, they are not derivable from the evm env.
+        if let Some(template) = &ctx.header {
+            header.beneficiary = template.beneficiary;
+            header.difficulty = template.difficulty;
+            header.mix_hash = template.mix_hash;
+            header.nonce = template.nonce;
+            header.extra_data = template.extra_data.clone();
+        }
+
         Ok(Block {
             header,
             body: BlockBody { transactions, ommers: Default::default(), withdrawals },
@@ -138,7 +148,7 @@ impl BlockAssembler<BscBlockExecutorFactory> for BscEvmConfig {
                 // HACK: we're setting sidecars to `None` here but ideally we should somehow get
                 // them from the payload builder.
                 //
-                // Payload building is out of scope of reth-bsc for now, so this is not critical
+                // The payload builder skips blob transactions for now, so this is not critical
                 sidecars: None,
             },
         })
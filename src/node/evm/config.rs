@@ -1,6 +1,7 @@
 use super::{assembler::BscBlockAssembler, executor::BscBlockExecutor, factory::BscEvmFactory};
 use crate::{
     chainspec::BscChainSpec,
+    consensus::parlia::signer::Signer,
     evm::transaction::BscTxEnv,
     hardforks::{bsc::BscHardfork, BscHardforks},
     node::engine_api::validator::BscExecutionData,
@@ -9,7 +10,7 @@ use crate::{
 };
 use alloy_consensus::{transaction::SignerRecoverable, BlockHeader, Header, TxReceipt};
 use alloy_eips::eip7840::BlobParams;
-use alloy_primitives::{Log, U256};
+use alloy_primitives::{Address, Log, U256};
 use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
 use reth_ethereum_forks::EthereumHardfork;
 use reth_evm::{
@@ -29,6 +30,18 @@ use revm::{
 };
 use std::{borrow::Cow, convert::Infallible, sync::Arc};
 
+/// Extra context for executing a block that is being built locally.
+///
+/// When present, the executor creates and signs the system transactions itself instead of
+/// matching them against the ones found in the block.
+#[derive(Debug, Clone)]
+pub struct BscMiningCtx {
+    /// The validator producing the block, used as coinbase and system tx sender.
+    pub validator: Address,
+    /// Signs the system transactions on behalf of the validator.
+    pub signer: Arc<dyn Signer>,
+}
+
 /// Context for BSC block execution.
 /// Contains all the fields from EthBlockExecutionCtx plus additional header field.
 #[derive(Debug, Clone)]
@@ -36,7 +49,11 @@ pub struct BscBlockExecutionCtx<'a> {
     /// Base Ethereum execution context.
     pub base: EthBlockExecutionCtx<'a>,
     /// Block header (optional for BSC-specific logic).
+    ///
+    /// When building a block this is the prepared header template.
     pub header: Option<Header>,
+    /// Mining context, only set when building a new block.
+    pub mining: Option<BscMiningCtx>,
 }
 
 impl<'a> BscBlockExecutionCtx<'a> {
@@ -46,7 +63,6 @@ impl<'a> BscBlockExecutionCtx<'a> {
     }
 }
 
-
 /// Ethereum-related EVM configuration.
 #[derive(Debug, Clone)]
 pub struct BscEvmConfig {
@@ -310,6 +326,7 @@ where
                 withdrawals: block.body().withdrawals.as_ref().map(Cow::Borrowed),
             },
             header: Some(block.header().clone()),
+            mining: None,
         }
     }
 
@@ -326,6 +343,7 @@ where
                 withdrawals: attributes.withdrawals.map(Cow::Owned),
             },
             header: None, // No header available for next block context
+            mining: None,
         }
     }
 }
@@ -348,6 +366,7 @@ where
                 withdrawals: block.body.inner.withdrawals.as_ref().map(Cow::Borrowed),
             },
             header: Some(block.header.clone()),
+            mining: None,
         }
     }
 
@@ -365,7 +384,9 @@ pub fn revm_spec_by_timestamp_and_block_number(
     timestamp: u64,
     block_number: u64,
 ) -> BscHardfork {
-    if chain_spec.is_maxwell_active_at_timestamp(block_number, timestamp) {
+    if chain_spec.is_fermi_active_at_timestamp(block_number, timestamp) {
+        BscHardfork::Fermi
+    } else if chain_spec.is_maxwell_active_at_timestamp(block_number, timestamp) {
         BscHardfork::Maxwell
     } else if chain_spec.is_lorentz_active_at_timestamp(block_number, timestamp) {
         BscHardfork::Lorentz
@@ -64,6 +64,8 @@ where
     pub(super) receipts: Vec<R::Receipt>,
     /// System txs
     pub(super) system_txs: Vec<R::Transaction>,
+    /// System txs created and signed by the executor when building a block.
+    pub(super) assembled_system_txs: Vec<TransactionSigned>,
     /// Receipt builder.
     pub(super) receipt_builder: R,
     /// System contracts used to trigger fork specific logic.
@@ -110,13 +112,17 @@ where
         let hertz_patch_manager = HertzPatchManager::new(is_mainnet);
         
         tracing::trace!("Succeed to new block executor, header: {:?}", ctx.header);
-        if let Some(ref header) = ctx.header {
+        if ctx.mining.is_some() {
+            // the header template is not sealed yet, so its hash must not be cached.
+            tracing::trace!("New block executor in mining mode, block_number: {:?}", evm.block().number.to::<u64>());
+        } else if let Some(ref header) = ctx.header {
             crate::node::evm::util::HEADER_CACHE_READER.lock().unwrap().insert_header_to_cache(header.clone());
         } else {
             tracing::warn!("No header found in the context, block_number: {:?}", evm.block().number.to::<u64>());
         }
 
-        let parlia = Arc::new(Parlia::new(Arc::new(spec.clone()), 200));
+        let epoch = spec.parlia_timing().base.epoch_length;
+        let parlia = Arc::new(Parlia::new(Arc::new(spec.clone()), epoch));
         let spec_clone = spec.clone();
         Self {
             spec,
@@ -124,6 +130,7 @@ where
             gas_used: 0,
             receipts: vec![],
             system_txs: vec![],
+            assembled_system_txs: vec![],
             receipt_builder,
             system_contracts,
             hertz_patch_manager,
@@ -142,6 +149,61 @@ where
         }
     }
 
+    /// Returns true if the executor is building a new block instead of replaying one.
+    pub(crate) fn is_mining(&self) -> bool {
+        self.ctx.mining.is_some()
+    }
+
+    /// Finishes a block that is being built and returns the signed system txs together with the
+    /// prepared header, so that they can be assembled into the block.
+    pub(crate) fn finish_mining(
+        mut self,
+    ) -> Result<(EVM, BlockExecutionResult<R::Receipt>, Vec<TransactionSigned>, Header), BlockExecutionError>
+    {
+        if !self.is_mining() {
+            return Err(BlockExecutionError::msg("Block executor is not in mining mode"));
+        }
+        self.apply_post_execution_system_txs()?;
+
+        let header = self
+            .inner_ctx
+            .header
+            .take()
+            .ok_or(BlockExecutionError::msg("Failed to get prepared header in mining mode"))?;
+        Ok((
+            self.evm,
+            BlockExecutionResult {
+                receipts: self.receipts,
+                requests: Requests::default(),
+                gas_used: self.gas_used,
+            },
+            self.assembled_system_txs,
+            header,
+        ))
+    }
+
+    /// Applies the contract deployments, upgrades and system txs that close the block.
+    fn apply_post_execution_system_txs(&mut self) -> Result<(), BlockExecutionError> {
+        // If first block deploy genesis contracts
+        if self.evm.block().number == uint!(1U256) {
+            self.deploy_genesis_contracts(self.evm.block().beneficiary)?;
+        }
+
+        if self.spec.is_feynman_active_at_timestamp(self.evm.block().number.to::<u64>(), self.evm.block().timestamp.to::<u64>() - 3) {
+            self.upgrade_contracts()?;
+        }
+
+        if self.spec.is_feynman_active_at_timestamp(self.evm.block().number.to::<u64>(), self.evm.block().timestamp.to()) &&
+            !self
+                .spec
+                .is_feynman_active_at_timestamp(self.evm.block().number.to::<u64>() - 1, self.evm.block().timestamp.to::<u64>() - 3)
+        {
+            self.initialize_feynman_contracts(self.evm.block().beneficiary)?;
+        }
+
+        self.finalize_new_block(&self.evm.block().clone())
+    }
+
     /// Applies system contract upgrades if the Feynman fork is not yet active.
     fn upgrade_contracts(&mut self) -> Result<(), BlockExecutionError> {
         let contracts = get_upgrade_system_contracts(
@@ -339,24 +401,7 @@ where
     fn finish(
         mut self,
     ) -> Result<(Self::Evm, BlockExecutionResult<R::Receipt>), BlockExecutionError> {
-        // If first block deploy genesis contracts
-        if self.evm.block().number == uint!(1U256) {
-            self.deploy_genesis_contracts(self.evm.block().beneficiary)?;
-        }
-
-        if self.spec.is_feynman_active_at_timestamp(self.evm.block().number.to::<u64>(), self.evm.block().timestamp.to::<u64>() - 3) {
-            self.upgrade_contracts()?;
-        }
-
-        if self.spec.is_feynman_active_at_timestamp(self.evm.block().number.to::<u64>(), self.evm.block().timestamp.to()) &&
-            !self
-                .spec
-                .is_feynman_active_at_timestamp(self.evm.block().number.to::<u64>() - 1, self.evm.block().timestamp.to::<u64>() - 3)
-        {
-            self.initialize_feynman_contracts(self.evm.block().beneficiary)?;
-        }
-
-        self.finalize_new_block(&self.evm.block().clone())?;
+        self.apply_post_execution_system_txs()?;
 
         Ok((
             self.evm,
@@ -380,4 +425,63 @@ where
         &self.evm
     }
 
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        chainspec::BscChainSpec,
+        node::evm::{config::BscEvmConfig, factory::BscEvmFactory},
+    };
+    use alloy_primitives::B256;
+    use reth_evm::{eth::EthBlockExecutionCtx, ConfigureEvm, EvmFactory};
+    use reth_evm_ethereum::RethReceiptBuilder;
+    use revm::database::EmptyDB;
+
+    #[test]
+    fn test_fork_transition_on_custom_chain() {
+        let genesis = serde_json::from_str(
+            r#"{
+                "config": { "chainId": 714714, "brunoBlock": 2, "eulerBlock": 2 },
+                "nonce": "0x0",
+                "timestamp": "0x0",
+                "extraData": "0x",
+                "gasLimit": "0x2625a00",
+                "difficulty": "0x1",
+                "mixHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+                "coinbase": "0x0000000000000000000000000000000000000000",
+                "alloc": {}
+            }"#,
+        )
+        .unwrap();
+        let spec = Arc::new(BscChainSpec::from_genesis(genesis).unwrap());
+        let header =
+            Header { number: 2, timestamp: 1000, gas_limit: 40_000_000, ..Default::default() };
+
+        let evm_env = BscEvmConfig::new(spec.clone()).evm_env(&header);
+        let mut state =
+            State::builder().with_database(EmptyDB::default()).with_bundle_update().build();
+        let evm = BscEvmFactory::default().create_evm(&mut state, evm_env);
+        let ctx = BscBlockExecutionCtx {
+            base: EthBlockExecutionCtx {
+                parent_hash: B256::ZERO,
+                parent_beacon_block_root: None,
+                ommers: &[],
+                withdrawals: None,
+            },
+            header: Some(header),
+            mining: None,
+        };
+        let mut executor = BscBlockExecutor::new(
+            evm,
+            ctx,
+            spec.clone(),
+            RethReceiptBuilder::default(),
+            SystemContract::new(spec),
+        );
+
+        // the block activates Bruno and Euler, the custom network has no contracts to upgrade
+        executor.upgrade_contracts().unwrap();
+        assert!(executor.evm.db_mut().cache.accounts.is_empty());
+    }
+}
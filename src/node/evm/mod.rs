@@ -26,7 +26,7 @@ use revm::{
 mod assembler;
 pub mod config;
 pub use config::BscEvmConfig;
-mod executor;
+pub(crate) mod executor;
 mod pre_execution;
 mod post_execution;
 mod factory;
@@ -1,7 +1,7 @@
 use super::executor::BscBlockExecutor;
 use super::error::BscBlockExecutionError;
 use super::util::set_nonce;
-use crate::consensus::parlia::{DIFF_INTURN, VoteAddress, VoteAttestation, snapshot::DEFAULT_TURN_LENGTH, constants::COLLECT_ADDITIONAL_VOTES_REWARD_RATIO, util::is_breathe_block};
+use crate::consensus::parlia::{DIFF_INTURN, VoteAddress, VoteAttestation, constants::COLLECT_ADDITIONAL_VOTES_REWARD_RATIO, util::is_breathe_block};
 use crate::consensus::{SYSTEM_ADDRESS, MAX_SYSTEM_REWARD, SYSTEM_REWARD_PERCENT};
 use crate::evm::transaction::BscTxEnv;
 use crate::system_contracts::{SLASH_CONTRACT, SYSTEM_REWARD_CONTRACT, feynman_fork::{ValidatorElectionInfo, get_top_validators_by_voting_power, ElectedValidators}};
@@ -118,12 +118,33 @@ where
             return Ok(());
         }
 
+        let validator_bytes = self.encode_validator_bytes(header_ref.number, current_validators)?;
+
+        let expected = self.parlia.get_validator_bytes_from_header(header_ref, epoch_length).unwrap();
+        if !validator_bytes.as_slice().eq(expected.as_slice()) {
+            // TODO: recheck it, maybe still has bugs.
+            warn!("validator bytes: {:?}", hex::encode(validator_bytes));
+            warn!("expected: {:?}", hex::encode(expected));
+            return Err(BlockExecutionError::msg("Invalid validators"));
+        }
+        tracing::debug!("Succeed to verify validators, block_number: {}, epoch_length: {}", header_ref.number, epoch_length);
+
+        Ok(())
+    }
+
+    /// Encodes the sorted validator set as it is stored in the extra data of an epoch block,
+    /// without the leading validator count.
+    pub(super) fn encode_validator_bytes(
+        &self,
+        block_number: u64,
+        current_validators: Option<(Vec<Address>, HashMap<Address, VoteAddress>)>,
+    ) -> Result<Vec<u8>, BlockExecutionError> {
         let (mut validators, mut vote_addrs_map) =
             current_validators.ok_or(BlockExecutionError::msg("Invalid current validators data"))?;
         validators.sort();
 
         let validator_num = validators.len();
-        if self.spec.is_luban_transition_at_block(header_ref.number) {
+        if self.spec.is_luban_transition_at_block(block_number) {
             vote_addrs_map = validators
                 .iter()
                 .copied()
@@ -131,27 +152,18 @@ where
                 .collect::<HashMap<_, _>>();
         }
 
-        let validator_bytes: Vec<u8> = validators
+        Ok(validators
             .into_iter()
             .flat_map(|v| {
                 let mut bytes = v.to_vec();
-                if self.spec.is_luban_active_at_block(header_ref.number) {
-                    bytes.extend_from_slice(vote_addrs_map[&v].as_ref());
+                if self.spec.is_luban_active_at_block(block_number) {
+                    bytes.extend_from_slice(
+                        vote_addrs_map.get(&v).copied().unwrap_or_default().as_ref(),
+                    );
                 }
                 bytes
             })
-            .collect();
-
-        let expected = self.parlia.get_validator_bytes_from_header(header_ref, epoch_length).unwrap();
-        if !validator_bytes.as_slice().eq(expected.as_slice()) {
-            // TODO: recheck it, maybe still has bugs.
-            warn!("validator bytes: {:?}", hex::encode(validator_bytes));
-            warn!("expected: {:?}", hex::encode(expected));
-            return Err(BlockExecutionError::msg("Invalid validators"));
-        }
-        tracing::debug!("Succeed to verify validators, block_number: {}, epoch_length: {}", header_ref.number, epoch_length);
-
-        Ok(())
+            .collect())
     }
 
     fn verify_turn_length(
@@ -182,7 +194,7 @@ where
         Err(BscBlockExecutionError::MismatchingEpochTurnLengthError.into())
     }
 
-    fn get_turn_length(
+    pub(super) fn get_turn_length(
         &mut self,
         header: &Header,
     ) -> Result<Option<u8>, BlockExecutionError> {
@@ -194,7 +206,7 @@ where
             return Ok(Some(turn_length))
         }
 
-        Ok(Some(DEFAULT_TURN_LENGTH))
+        Ok(Some(self.spec.parlia_timing_at(header.number, header.timestamp).turn_length))
     }
 
     fn slash_spoiled_validator(
@@ -222,26 +234,18 @@ where
             .unwrap_or_default();
 
         let transaction = set_nonce(transaction, account.nonce);
-        let hash = transaction.signature_hash();
-        if self.system_txs.is_empty() || hash != self.system_txs[0].signature_hash() {
-            // slash tx could fail and not in the block
-            if let Some(to) = transaction.to() {
-                if to == SLASH_CONTRACT &&
-                    (self.system_txs.is_empty() ||
-                        self.system_txs[0].to().unwrap_or_default() !=
-                            SLASH_CONTRACT)
-                {
-                    warn!("slash validator failed");
-                    return Ok(());
-                }
-            }
-            warn!("unexpected transaction: {:?}", transaction);
-            for tx in self.system_txs.iter() {
-                warn!("left system tx: {:?}", tx);
-            }
-            return Err(BscBlockExecutionError::UnexpectedSystemTx.into());
-        }
-        let signed_tx = self.system_txs.remove(0);
+        let signed_tx = if let Some(mining) = self.ctx.mining.as_ref() {
+            // building a block, so the system tx is created here instead of taken from the block.
+            let signed_tx =
+                mining.signer.sign_transaction(transaction.clone()).map_err(BlockExecutionError::other)?;
+            self.assembled_system_txs.push(signed_tx.clone());
+            Some(signed_tx)
+        } else {
+            self.take_expected_system_tx(&transaction)?
+        };
+        let Some(signed_tx) = signed_tx else {
+            return Ok(());
+        };
 
         // Create TxEnv first (before moving transaction)
         let tx_env = BscTxEnv {
@@ -284,6 +288,36 @@ where
         Ok(())
     }
 
+    /// Takes the next system tx of the block, which must match the expected one.
+    ///
+    /// Returns `None` if the expected tx is a slash tx that is not in the block, since slashing
+    /// could fail when the block was built.
+    fn take_expected_system_tx(
+        &mut self,
+        transaction: &Transaction,
+    ) -> Result<Option<TransactionSigned>, BlockExecutionError> {
+        let hash = transaction.signature_hash();
+        if self.system_txs.is_empty() || hash != self.system_txs[0].signature_hash() {
+            // slash tx could fail and not in the block
+            if let Some(to) = transaction.to() {
+                if to == SLASH_CONTRACT &&
+                    (self.system_txs.is_empty() ||
+                        self.system_txs[0].to().unwrap_or_default() !=
+                            SLASH_CONTRACT)
+                {
+                    warn!("slash validator failed");
+                    return Ok(None);
+                }
+            }
+            warn!("unexpected transaction: {:?}", transaction);
+            for tx in self.system_txs.iter() {
+                warn!("left system tx: {:?}", tx);
+            }
+            return Err(BscBlockExecutionError::UnexpectedSystemTx.into());
+        }
+        Ok(Some(self.system_txs.remove(0)))
+    }
+
     fn distribute_incoming(
         &mut self,
         validator: Address,
@@ -11,7 +11,7 @@ use revm::{
 };
 use alloy_consensus::{TxReceipt, Header, BlockHeader};
 use alloy_primitives::B256;
-use crate::consensus::parlia::{VoteAddress, Snapshot, Parlia, DIFF_INTURN, DIFF_NOTURN};
+use crate::consensus::parlia::{VoteAddress, Snapshot, Parlia, DEFAULT_TURN_LENGTH, EXTRA_SEAL_LEN, EXTRA_VANITY_LEN, VALIDATOR_BYTES_LEN_AFTER_LUBAN};
 use crate::consensus::parlia::util::{is_breathe_block, calculate_millisecond_timestamp};
 use crate::consensus::parlia::vote::MAX_ATTESTATION_EXTRA_LENGTH;
 use crate::node::evm::error::BscBlockExecutionError;
@@ -20,13 +20,6 @@ use crate::system_contracts::feynman_fork::ValidatorElectionInfo;
 use std::{collections::HashMap, sync::{Arc, LazyLock, Mutex}};
 use schnellru::{ByLength, LruMap};
 use reth_primitives::GotExpected;
-use blst::{
-    min_pk::{PublicKey, Signature},
-    BLST_ERROR,
-};
-use bit_set::BitSet;
-
-const BLST_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
 
 type ValidatorCache = LruMap<u64, (Vec<Address>, Vec<VoteAddress>), ByLength>;
 
@@ -60,17 +53,26 @@ where
         let block_number = block.number.to::<u64>();
         tracing::trace!("Check new block, block_number: {}", block_number);
 
-        let header = crate::node::evm::util::HEADER_CACHE_READER
-            .lock()
-            .unwrap()
-            .get_header_by_number(block_number)
-            .ok_or(BlockExecutionError::msg("Failed to get header from global header reader"))?;
+        let is_mining = self.is_mining();
+        let header = if is_mining {
+            self.ctx
+                .header
+                .clone()
+                .ok_or(BlockExecutionError::msg("Failed to get header template in mining mode"))?
+        } else {
+            crate::node::evm::util::HEADER_CACHE_READER
+                .lock()
+                .unwrap()
+                .get_header_by_number(block_number)
+                .ok_or(BlockExecutionError::msg("Failed to get header from global header reader"))?
+        };
         self.inner_ctx.header = Some(header.clone());
 
+        // the parent is looked up by hash as the block may not extend the canonical chain.
         let parent_header = crate::node::evm::util::HEADER_CACHE_READER
             .lock()
             .unwrap()
-            .get_header_by_number(block_number - 1)
+            .get_header_by_hash(&header.parent_hash)
             .ok_or(BlockExecutionError::msg("Failed to get parent header from global header reader"))?;
         self.inner_ctx.parent_header = Some(parent_header.clone());
 
@@ -78,11 +80,14 @@ where
             .snapshot_provider
             .as_ref()
             .unwrap()
-            .snapshot(block_number-1)
+            .snapshot_by_hash(block_number - 1, header.parent_hash)
             .ok_or(BlockExecutionError::msg("Failed to get snapshot from snapshot provider"))?;
         self.inner_ctx.snap = Some(snap.clone());
 
-        self.verify_cascading_fields(&header, &parent_header, &snap)?;
+        // the seal and attestation of a block being built are only added after execution.
+        if !is_mining {
+            self.verify_cascading_fields(&header, &parent_header, &snap)?;
+        }
 
         let epoch_length = self.parlia.get_epoch_length(&header);
         if header.number % epoch_length == 0 {
@@ -100,6 +105,10 @@ where
             };
             tracing::debug!("vote_addrs_map: {:?}", vote_addrs_map);
             self.inner_ctx.current_validators = Some((validator_set, vote_addrs_map));
+
+            if is_mining {
+                self.prepare_epoch_extra(header.clone())?;
+            }
         }
     
         if self.spec.is_feynman_active_at_timestamp(header.number, header.timestamp) &&
@@ -143,6 +152,35 @@ where
         Ok(())
     }
 
+    /// Fills the validator set and turn length into the extra data of an epoch block that is
+    /// being built, between the vanity and the seal.
+    fn prepare_epoch_extra(&mut self, mut header: Header) -> Result<(), BlockExecutionError> {
+        let extra = header.extra_data.to_vec();
+        if extra.len() < EXTRA_VANITY_LEN + EXTRA_SEAL_LEN {
+            return Err(BlockExecutionError::msg("Invalid extra data of header template"));
+        }
+
+        let mut new_extra = Vec::with_capacity(extra.len() + 1024);
+        new_extra.extend_from_slice(&extra[..extra.len() - EXTRA_SEAL_LEN]);
+        let validator_bytes =
+            self.encode_validator_bytes(header.number, self.inner_ctx.current_validators.clone())?;
+        if self.spec.is_luban_active_at_block(header.number) {
+            let validator_num = validator_bytes.len() / VALIDATOR_BYTES_LEN_AFTER_LUBAN;
+            new_extra.push(validator_num as u8);
+        }
+        new_extra.extend_from_slice(&validator_bytes);
+        if self.spec.is_bohr_active_at_timestamp(header.number, header.timestamp) {
+            let turn_length = self.get_turn_length(&header)?.unwrap_or(DEFAULT_TURN_LENGTH);
+            new_extra.push(turn_length);
+        }
+        new_extra.extend_from_slice(&extra[extra.len() - EXTRA_SEAL_LEN..]);
+
+        header.extra_data = Bytes::from(new_extra);
+        tracing::debug!("Succeed to prepare epoch extra, block_number: {}, extra_len: {}", header.number, header.extra_data.len());
+        self.inner_ctx.header = Some(header);
+        Ok(())
+    }
+
     pub(crate) fn get_current_validators(
         &mut self, 
         block_number: u64
@@ -219,7 +257,7 @@ where
     ) -> Result<(), BlockExecutionError> {
         self.verify_block_time_for_ramanujan(snap, header, parent)?;
         self.verify_vote_attestation(snap, header, parent)?;
-        self.verify_seal(snap, header)?;
+        self.parlia.verify_seal(snap, header)?;
 
         Ok(())
     }
@@ -259,7 +297,8 @@ where
             return Ok(());
         }
 
-        let parlia = Parlia::new(Arc::new(self.spec.clone()), 200);
+        let epoch = self.spec.parlia_timing().base.epoch_length;
+        let parlia = Parlia::new(Arc::new(self.spec.clone()), epoch);
         let attestation =
             parlia.get_vote_attestation_from_header(header, snap.epoch_num).map_err(|err| {
                 tracing::error!("Failed to get vote attestation from header, block_number: {}, error: {:?}", header.number(), err);
@@ -303,114 +342,16 @@ where
                 .snapshot_provider
                 .as_ref()
                 .unwrap()
-                .snapshot(parent.number() - 1)
+                .snapshot_by_hash(parent.number() - 1, parent.parent_hash())
                 .ok_or(BlockExecutionError::msg("Failed to get pre snapshot from snapshot provider"))?;
 
-            // query bls keys from snapshot.
-            let validators_count = pre_snap.validators.len();
-            let vote_bit_set: BitSet<usize> = BitSet::from_iter(
-                (0..64).filter(|&i| (attestation.vote_address_set >> i) & 1 != 0)
-            );
-            let bit_set_count = vote_bit_set.len();
-            if bit_set_count > validators_count {
-                return Err(BscBlockExecutionError::InvalidAttestationVoteCount(GotExpected {
-                    got: bit_set_count as u64,
-                    expected: validators_count as u64,
-                })
-                .into());
-            }
-             
-            let mut vote_addrs: Vec<VoteAddress> = Vec::with_capacity(bit_set_count);
-            for (i, val) in pre_snap.validators.iter().enumerate() {
-                if !vote_bit_set.contains(i) {
-                    continue;
-                }
-
-                let val_info = pre_snap
-                    .validators_map
-                    .get(val)
-                    .ok_or(BscBlockExecutionError::VoteAddrNotFoundInSnap { address: *val })?;
-                vote_addrs.push(val_info.vote_addr);
-            }
-
-            // check if voted validator count satisfied 2/3 + 1
-            let at_least_votes = (validators_count * 2).div_ceil(3); // ceil division
-            if vote_addrs.len() < at_least_votes {
-                return Err(BscBlockExecutionError::InvalidAttestationVoteCount(GotExpected {
-                    got: vote_addrs.len() as u64,
-                    expected: at_least_votes as u64,
-                })
-                .into());
-            }
- 
-            // check bls aggregate sig
-            let vote_addrs: Vec<PublicKey> = vote_addrs
-                .iter()
-                .map(|addr| PublicKey::from_bytes(addr.as_slice()).unwrap())
-                .collect();
-            let vote_addrs_ref: Vec<&PublicKey> = vote_addrs.iter().collect();
- 
-            let sig = Signature::from_bytes(&attestation.agg_signature[..])
-                .map_err(|_| BscBlockExecutionError::BLSTInnerError)?;
-            let err = sig.fast_aggregate_verify(
-                true,
-                attestation.data.hash().as_slice(),
-                BLST_DST,
-                &vote_addrs_ref,
-            );
- 
-            return match err {
-                BLST_ERROR::BLST_SUCCESS => Ok(()),
-                _ => Err(BscBlockExecutionError::BLSTInnerError.into()),
-            };
+            return self.parlia.verify_attestation_votes(&attestation, &pre_snap).map_err(Into::into);
         }
     
         Ok(())
     }
 
     
-    fn verify_seal(
-        &self,
-        snap: &Snapshot,
-        header: &Header,
-    ) -> Result<(), BlockExecutionError> {
-        let parlia = Parlia::new(Arc::new(self.spec.clone()), 200);
-        let proposer = parlia.recover_proposer(header).map_err(|err| {
-            tracing::error!("Failed to recover proposer from header, block_number: {}, error: {:?}", header.number(), err);
-            BscBlockExecutionError::ParliaConsensusInnerError { error: err.into() }
-        })?;
-
-        if proposer != header.beneficiary {
-            return Err(BscBlockExecutionError::WrongHeaderSigner {
-                block_number: header.number(),
-                signer: GotExpected { got: proposer, expected: header.beneficiary }.into(),
-            }
-            .into());
-        }
-
-        if !snap.validators.contains(&proposer) {
-            return Err(BscBlockExecutionError::SignerUnauthorized { 
-                block_number: header.number(), 
-                proposer 
-            }.into());
-        }
-
-        if snap.sign_recently(proposer) {
-            return Err(BscBlockExecutionError::SignerOverLimit { proposer }.into());
-        }
-
-        let is_inturn = snap.is_inturn(proposer);
-        if (is_inturn && header.difficulty != DIFF_INTURN) ||
-            (!is_inturn && header.difficulty != DIFF_NOTURN)
-        {
-            return Err(
-                BscBlockExecutionError::InvalidDifficulty { difficulty: header.difficulty }.into()
-            );
-        }
-
-        Ok(())
-    }
-
     pub(crate) fn get_justified_header(
         &self,
         snap: &Snapshot,
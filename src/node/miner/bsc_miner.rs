@@ -0,0 +1,257 @@
+//! Validator loop proposing blocks on top of the canonical head.
+use super::{
+    config::MiningConfig,
+    payload::{next_block_time_ms, now_millis},
+};
+use crate::{
+    chainspec::BscChainSpec,
+    consensus::{
+        parlia::{seal::SealBlock, SnapshotProvider},
+        ParliaConsensus,
+    },
+    node::{
+        engine_api::payload::BscPayloadTypes,
+        network::{BscNetworkPrimitives, BscNewBlock},
+    },
+    BscBlock, BscPrimitives,
+};
+use alloy_consensus::Header;
+use alloy_primitives::{B256, U128};
+use alloy_rpc_types::engine::PayloadAttributes;
+use reth::payload::{EthPayloadBuilderAttributes, PayloadBuilderHandle};
+use reth_chainspec::EthereumHardforks;
+use reth_engine_primitives::BeaconConsensusEngineHandle;
+use reth_eth_wire::NewBlock;
+use reth_network::NetworkHandle;
+use reth_payload_primitives::{BuiltPayload, EngineApiMessageVersion, PayloadKind, PayloadTypes};
+use reth_primitives::SealedHeader;
+use reth_provider::{
+    BlockNumReader, CanonStateNotification, CanonStateSubscriptions, HeaderProvider,
+};
+use std::{sync::Arc, time::Duration};
+use tokio::sync::broadcast::{error::RecvError, Receiver};
+use tracing::{debug, info, trace, warn};
+
+/// Time waited before retrying a failed proposal on the same head.
+const RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Drives block production of the local validator.
+///
+/// Every time the canonical head changes the miner checks whether the validator may propose the
+/// next block. If so it waits for the block time, including the back off time of out-of-turn
+/// validators, resolves the payload, seals it and imports it through the engine before
+/// announcing it to the peers. A new head arriving while the block is built aborts the proposal,
+/// once the block is sealed it is imported and announced regardless. A failed proposal is
+/// retried on the same head after [`RETRY_BACKOFF`].
+pub struct BscMiner<Provider> {
+    provider: Provider,
+    snapshot_provider: Arc<dyn SnapshotProvider + Send + Sync>,
+    consensus: ParliaConsensus<Provider>,
+    sealer: Arc<SealBlock<BscChainSpec>>,
+    config: MiningConfig,
+    payload_builder: PayloadBuilderHandle<BscPayloadTypes>,
+    engine: BeaconConsensusEngineHandle<BscPayloadTypes>,
+    network: NetworkHandle<BscNetworkPrimitives>,
+}
+
+impl<Provider> std::fmt::Debug for BscMiner<Provider> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BscMiner").field("config", &self.config).finish_non_exhaustive()
+    }
+}
+
+impl<Provider> BscMiner<Provider>
+where
+    Provider: CanonStateSubscriptions<Primitives = BscPrimitives>
+        + HeaderProvider<Header = Header>
+        + BlockNumReader
+        + Clone
+        + 'static,
+{
+    /// Creates a new [`BscMiner`].
+    ///
+    /// Must be called after the node is launched, as it relies on the global snapshot provider.
+    pub fn new(
+        provider: Provider,
+        chain_spec: Arc<BscChainSpec>,
+        config: MiningConfig,
+        payload_builder: PayloadBuilderHandle<BscPayloadTypes>,
+        engine: BeaconConsensusEngineHandle<BscPayloadTypes>,
+        network: NetworkHandle<BscNetworkPrimitives>,
+    ) -> eyre::Result<Self> {
+        let snapshot_provider = crate::shared::get_snapshot_provider()
+            .cloned()
+            .ok_or_else(|| eyre::eyre!("snapshot provider is not initialized"))?;
+        let sealer = Arc::new(SealBlock::new(
+            snapshot_provider.clone(),
+            chain_spec.clone(),
+            config.signer.clone(),
+        ));
+        Ok(Self {
+            consensus: ParliaConsensus::new(provider.clone(), chain_spec),
+            provider,
+            snapshot_provider,
+            sealer,
+            config,
+            payload_builder,
+            engine,
+            network,
+        })
+    }
+
+    /// Runs the validator loop until the canonical state stream is closed.
+    pub async fn run(self) {
+        info!(target: "bsc::miner", validator = %self.config.validator, "Starting validator");
+
+        let mut canon_events = self.provider.subscribe_to_canonical_state();
+        let mut head = match self.current_head() {
+            Ok(head) => head,
+            Err(err) => {
+                warn!(target: "bsc::miner", %err, "Failed to read the canonical head, validator stopped");
+                return
+            }
+        };
+
+        loop {
+            let built = tokio::select! {
+                new_head = next_head(&mut canon_events) => {
+                    let Some(new_head) = new_head else { return };
+                    head = new_head;
+                    continue
+                }
+                built = self.build(&head) => built,
+            };
+
+            // the notification of the sealed block arrives before the engine answers, so the
+            // block is imported and announced without racing the next head
+            let result = match built {
+                Ok(Some(block)) => self.import_and_announce(&head, block).await.map(Some),
+                Ok(None) => Ok(None),
+                Err(err) => Err(err),
+            };
+            match result {
+                Ok(Some(hash)) => {
+                    info!(target: "bsc::miner", number = head.number + 1, ?hash, "Proposed block");
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    warn!(target: "bsc::miner", number = head.number + 1, %err, "Failed to propose block");
+                    // no other validator may move the head, the proposal is retried on the same
+                    // head unless a new one arrives in the meantime
+                    tokio::select! {
+                        new_head = next_head(&mut canon_events) => {
+                            let Some(new_head) = new_head else { return };
+                            head = new_head;
+                        }
+                        _ = tokio::time::sleep(RETRY_BACKOFF) => {}
+                    }
+                    continue
+                }
+            }
+            // the block is proposed or the validator may not propose on this head
+            let Some(new_head) = next_head(&mut canon_events).await else { return };
+            head = new_head;
+        }
+    }
+
+    fn current_head(&self) -> eyre::Result<SealedHeader> {
+        let number = self.provider.best_block_number()?;
+        self.provider
+            .sealed_header(number)?
+            .ok_or_else(|| eyre::eyre!("canonical header {number} not found"))
+    }
+
+    /// Builds the block on top of `parent`, waiting for its block time.
+    ///
+    /// Returns `None` if the validator is not allowed to propose this block.
+    async fn build(&self, parent: &SealedHeader) -> eyre::Result<Option<BscBlock>> {
+        let validator = self.config.validator;
+        let snap = self
+            .snapshot_provider
+            .snapshot_by_hash(parent.number, parent.hash())
+            .ok_or_else(|| eyre::eyre!("snapshot of block {} not found", parent.number))?;
+        if !snap.validators.contains(&validator) {
+            trace!(target: "bsc::miner", %validator, "Not in the validator set, skip proposing");
+            return Ok(None)
+        }
+        if snap.sign_recently(validator) {
+            debug!(target: "bsc::miner", %validator, "Signed recently, must wait for others");
+            return Ok(None)
+        }
+
+        let block_time =
+            next_block_time_ms(&self.consensus.parlia, parent, &snap, validator).max(now_millis());
+        let timestamp = block_time / 1000;
+        let attributes = EthPayloadBuilderAttributes::new(
+            parent.hash(),
+            PayloadAttributes {
+                timestamp,
+                prev_randao: B256::ZERO,
+                suggested_fee_recipient: validator,
+                withdrawals: self
+                    .consensus
+                    .parlia
+                    .spec
+                    .is_shanghai_active_at_timestamp(timestamp)
+                    .then(Vec::new),
+                parent_beacon_block_root: self
+                    .consensus
+                    .parlia
+                    .spec
+                    .is_cancun_active_at_timestamp(timestamp)
+                    .then_some(B256::ZERO),
+            },
+        );
+        let payload_id = self.payload_builder.send_new_payload(attributes).await??;
+
+        // fill the block with transactions until it is time to propose it
+        tokio::time::sleep(Duration::from_millis(block_time.saturating_sub(now_millis()))).await;
+        let payload = self
+            .payload_builder
+            .resolve_kind(payload_id, PayloadKind::WaitForPending)
+            .await
+            .ok_or_else(|| eyre::eyre!("payload {payload_id} not found"))??;
+        Ok(Some(payload.block().clone().into_block()))
+    }
+
+    /// Seals `block`, imports it through the engine and announces it to the peers.
+    async fn import_and_announce(
+        &self,
+        parent: &SealedHeader,
+        block: BscBlock,
+    ) -> eyre::Result<B256> {
+        let sealer = self.sealer.clone();
+        let sealed = tokio::task::spawn_blocking(move || sealer.seal(block)).await??;
+        let hash = sealed.hash();
+        // nothing fails once the block is made canonical, a retry would propose a sibling
+        let parent_td = self.provider.header_td(&parent.hash())?.unwrap_or_default();
+        let td = U128::from((parent_td + sealed.header().difficulty).saturating_to::<u128>());
+
+        let status =
+            self.engine.new_payload(BscPayloadTypes::block_to_payload(sealed.clone())).await?;
+        if !status.is_valid() {
+            eyre::bail!("sealed block {hash} rejected by the engine: {:?}", status.status);
+        }
+        let state = self.consensus.forkchoice_state(sealed.sealed_header(), hash);
+        self.engine.fork_choice_updated(state, None, EngineApiMessageVersion::default()).await?;
+
+        self.network.announce_block(BscNewBlock(NewBlock { block: sealed.into_block(), td }), hash);
+
+        Ok(hash)
+    }
+}
+
+/// Waits for the next canonical head, returns `None` once the stream is closed.
+pub(super) async fn next_head(
+    canon_events: &mut Receiver<CanonStateNotification<BscPrimitives>>,
+) -> Option<SealedHeader> {
+    loop {
+        match canon_events.recv().await {
+            Ok(notification) => return Some(notification.tip().clone_sealed_header()),
+            Err(RecvError::Lagged(skipped)) => {
+                trace!(target: "bsc::miner", skipped, "Canonical state stream lagged");
+            }
+            Err(RecvError::Closed) => return None,
+        }
+    }
+}
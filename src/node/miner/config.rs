@@ -0,0 +1,176 @@
+//! Validator configuration of the node.
+use super::{
+    signer::{KeystoreSigner, LocalSigner, RemoteSigner},
+    vote_signer::VoteSigner,
+};
+use crate::{consensus::parlia::signer::Signer, node::evm::config::BscMiningCtx};
+use alloy_primitives::{Address, B256};
+use clap::Args;
+use std::{path::PathBuf, sync::Arc};
+
+/// Parameters to run the node as a validator.
+///
+/// Exactly one signer backend must be configured when mining is enabled.
+#[derive(Debug, Clone, Default, Args)]
+#[command(next_help_heading = "Mining")]
+pub struct BscMiningArgs {
+    /// Produce and seal blocks when the validator is allowed to.
+    #[arg(long = "mining.enabled", default_value_t = false)]
+    pub enabled: bool,
+
+    /// Address of the validator producing the blocks, it must match the signing key.
+    #[arg(long = "mining.validator", value_name = "ADDRESS")]
+    pub validator: Option<Address>,
+
+    /// Hex encoded private key of the validator, only meant for devnets.
+    #[arg(long = "mining.private-key", value_name = "HEX")]
+    pub private_key: Option<B256>,
+
+    /// Path of the encrypted JSON keystore holding the validator key.
+    #[arg(long = "mining.keystore", value_name = "PATH", requires = "password_file")]
+    pub keystore: Option<PathBuf>,
+
+    /// Path of the file containing the keystore password.
+    #[arg(long = "mining.password-file", value_name = "PATH")]
+    pub password_file: Option<PathBuf>,
+
+    /// URL of a clef compatible remote signer holding the validator key.
+    #[arg(long = "mining.remote-signer", value_name = "URL")]
+    pub remote_signer: Option<String>,
+
+    /// Path of the EIP-2335 keystore holding the BLS key used to cast fast finality votes.
+    ///
+    /// The validator does not vote when no BLS key is configured.
+    #[arg(long = "mining.bls-keystore", value_name = "PATH", requires = "bls_password_file")]
+    pub bls_keystore: Option<PathBuf>,
+
+    /// Path of the file containing the BLS keystore password.
+    #[arg(long = "mining.bls-password-file", value_name = "PATH")]
+    pub bls_password_file: Option<PathBuf>,
+
+    /// Path of the journal of the votes cast by the validator.
+    ///
+    /// Defaults to `vote-journal.jsonl` next to the BLS keystore.
+    #[arg(long = "mining.vote-journal", value_name = "PATH")]
+    pub vote_journal: Option<PathBuf>,
+
+    /// Submit the evidence of the double signs found in the network to the SlashIndicator
+    /// contract, from the validator account.
+    #[arg(long = "mining.submit-double-sign-evidence", default_value_t = false)]
+    pub submit_double_sign_evidence: bool,
+}
+
+impl BscMiningArgs {
+    /// Returns the validator configuration, or `None` when block production is disabled.
+    pub fn mining_config(&self) -> eyre::Result<Option<MiningConfig>> {
+        if !self.enabled {
+            return Ok(None)
+        }
+
+        let validator = self
+            .validator
+            .ok_or_else(|| eyre::eyre!("--mining.validator is required when mining is enabled"))?;
+
+        let signer: Arc<dyn Signer> =
+            match (self.private_key, &self.keystore, &self.remote_signer) {
+                (Some(private_key), None, None) => {
+                    Arc::new(LocalSigner::from_private_key(private_key)?)
+                }
+                (None, Some(keystore), None) => {
+                    let password_file = self.password_file.as_ref().ok_or_else(|| {
+                        eyre::eyre!("--mining.password-file is required by --mining.keystore")
+                    })?;
+                    let password = std::fs::read_to_string(password_file)?;
+                    Arc::new(KeystoreSigner::decrypt(keystore, password.trim_end())?)
+                }
+                (None, None, Some(url)) => Arc::new(RemoteSigner::new(url, validator)?),
+                _ => eyre::bail!(
+                    "exactly one of --mining.private-key, --mining.keystore or \
+                     --mining.remote-signer is required when mining is enabled"
+                ),
+            };
+
+        let signer_address = signer.address();
+        if signer_address != validator {
+            eyre::bail!("signing key belongs to {signer_address}, not to validator {validator}");
+        }
+
+        let vote = match (&self.bls_keystore, &self.bls_password_file) {
+            (Some(keystore), Some(password_file)) => {
+                let password = std::fs::read_to_string(password_file)?;
+                let signer = VoteSigner::from_keystore(keystore, password.trim_end())?;
+                let journal = self
+                    .vote_journal
+                    .clone()
+                    .unwrap_or_else(|| keystore.with_file_name("vote-journal.jsonl"));
+                Some(VoteConfig { signer: Arc::new(signer), journal })
+            }
+            (None, _) => None,
+            (Some(_), None) => eyre::bail!(
+                "--mining.bls-password-file is required by --mining.bls-keystore"
+            ),
+        };
+
+        Ok(Some(MiningConfig {
+            validator,
+            signer,
+            vote,
+            submit_double_sign_evidence: self.submit_double_sign_evidence,
+        }))
+    }
+}
+
+/// Identity of the local validator, used to sign block headers and system transactions.
+#[derive(Debug, Clone)]
+pub struct MiningConfig {
+    /// Address of the validator.
+    pub validator: Address,
+    /// Backend holding the validator key.
+    pub signer: Arc<dyn Signer>,
+    /// Fast finality voting, `None` if no BLS key is configured.
+    pub vote: Option<VoteConfig>,
+    /// Whether the double signs found in the network are reported to the slash contract.
+    pub submit_double_sign_evidence: bool,
+}
+
+impl MiningConfig {
+    /// Returns the context used by the executor to sign the system transactions.
+    pub fn mining_ctx(&self) -> BscMiningCtx {
+        BscMiningCtx { validator: self.validator, signer: self.signer.clone() }
+    }
+}
+
+/// Configuration of the fast finality votes of the validator.
+#[derive(Debug, Clone)]
+pub struct VoteConfig {
+    /// BLS key signing the votes.
+    pub signer: Arc<VoteSigner>,
+    /// Path of the journal of the votes cast.
+    pub journal: PathBuf,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256};
+
+    #[test]
+    fn test_mining_config_checks_validator() {
+        let mut args = BscMiningArgs {
+            enabled: true,
+            validator: Some(address!("f39Fd6e51aad88F6F4ce6aB8827279cffFb92266")),
+            // well known dev key
+            private_key: Some(b256!(
+                "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"
+            )),
+            ..Default::default()
+        };
+        assert!(args.mining_config().unwrap().is_some());
+
+        args.validator = Some(Address::ZERO);
+        assert!(args.mining_config().is_err());
+
+        args.remote_signer = Some("http://127.0.0.1:8550".to_string());
+        assert!(args.mining_config().is_err());
+    }
+}
@@ -0,0 +1,136 @@
+//! Submission of the double signs found in the network to the SlashIndicator contract.
+use super::config::MiningConfig;
+use crate::{
+    chainspec::BscChainSpec,
+    consensus::parlia::{
+        double_sign::{self, DoubleSign},
+        signer::Signer,
+    },
+    system_contracts::SystemContract,
+};
+use alloy_consensus::{transaction::Recovered, TxLegacy};
+use alloy_primitives::{Address, TxKind, U256};
+use reth::transaction_pool::{PoolTransaction, TransactionOrigin, TransactionPool};
+use reth_chainspec::EthChainSpec;
+use reth_primitives::{Transaction, TransactionSigned};
+use reth_provider::StateProviderFactory;
+use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{info, warn};
+
+/// Gas limit of the evidence transactions, the two headers and the slash fit in it.
+const EVIDENCE_GAS_LIMIT: u64 = 1_000_000;
+
+/// Gas price of the evidence transactions, above the minimum accepted by the validators.
+const EVIDENCE_GAS_PRICE: u128 = 1_000_000_000;
+
+/// Sends a `submitDoubleSignEvidence` transaction from the validator account for every double
+/// sign found by the detector.
+pub struct DoubleSignReporter<Provider, Pool> {
+    provider: Provider,
+    pool: Pool,
+    chain_spec: Arc<BscChainSpec>,
+    validator: Address,
+    signer: Arc<dyn Signer>,
+    system_contract: SystemContract<Arc<BscChainSpec>>,
+}
+
+impl<Provider, Pool> std::fmt::Debug for DoubleSignReporter<Provider, Pool> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DoubleSignReporter")
+            .field("validator", &self.validator)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Provider, Pool> DoubleSignReporter<Provider, Pool>
+where
+    Provider: StateProviderFactory + 'static,
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>> + 'static,
+{
+    /// Creates a reporter sending the evidences from the account of the validator.
+    pub fn new(
+        provider: Provider,
+        pool: Pool,
+        chain_spec: Arc<BscChainSpec>,
+        config: &MiningConfig,
+    ) -> Self {
+        Self {
+            provider,
+            pool,
+            system_contract: SystemContract::new(chain_spec.clone()),
+            chain_spec,
+            validator: config.validator,
+            signer: config.signer.clone(),
+        }
+    }
+
+    /// Reports the double signs until the detector is gone.
+    pub async fn run(self) {
+        info!(target: "bsc::slash", validator = %self.validator, "Starting double sign reporter");
+
+        let mut double_signs = double_sign::subscribe_double_signs();
+        loop {
+            match double_signs.recv().await {
+                Ok(double_sign) => {
+                    if double_sign.signer == self.validator {
+                        warn!(target: "bsc::slash", "Not reporting a double sign of the validator");
+                        continue
+                    }
+                    if let Err(err) = self.report(&double_sign).await {
+                        warn!(
+                            target: "bsc::slash",
+                            signer = %double_sign.signer,
+                            number = double_sign.number(),
+                            %err,
+                            "Failed to report double sign"
+                        );
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!(target: "bsc::slash", skipped, "Double sign reporter lagged");
+                }
+                Err(RecvError::Closed) => return,
+            }
+        }
+    }
+
+    /// Signs the evidence transaction and adds it to the pool.
+    async fn report(&self, double_sign: &DoubleSign) -> eyre::Result<()> {
+        let (header1, header2) = double_sign.header_bytes();
+        let (to, input) = self.system_contract.submit_double_sign_evidence(header1, header2);
+
+        // the transactions already pending in the pool come before
+        let state_nonce =
+            self.provider.latest()?.account_nonce(&self.validator)?.unwrap_or_default();
+        let pool_nonce = self
+            .pool
+            .get_highest_transaction_by_sender(self.validator)
+            .map(|tx| tx.nonce() + 1)
+            .unwrap_or_default();
+        let tx = Transaction::Legacy(TxLegacy {
+            chain_id: Some(self.chain_spec.chain().id()),
+            nonce: state_nonce.max(pool_nonce),
+            gas_price: EVIDENCE_GAS_PRICE,
+            gas_limit: EVIDENCE_GAS_LIMIT,
+            to: TxKind::Call(to),
+            value: U256::ZERO,
+            input,
+        });
+
+        let signer = self.signer.clone();
+        let signed = tokio::task::spawn_blocking(move || signer.sign_transaction(tx)).await??;
+        let recovered = Recovered::new_unchecked(signed, self.validator);
+        let pooled = Pool::Transaction::try_from_consensus(recovered)
+            .map_err(|_| eyre::eyre!("evidence transaction can't enter the pool"))?;
+        let hash = self.pool.add_transaction(TransactionOrigin::Local, pooled).await?;
+        info!(
+            target: "bsc::slash",
+            signer = %double_sign.signer,
+            number = double_sign.number(),
+            tx = ?hash,
+            "Submitted double sign evidence"
+        );
+        Ok(())
+    }
+}
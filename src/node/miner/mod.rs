@@ -0,0 +1,15 @@
+//! Block production for BSC validators.
+pub mod bsc_miner;
+pub mod config;
+pub mod double_sign_reporter;
+pub mod payload;
+pub mod signer;
+pub mod vote_journal;
+pub mod vote_manager;
+pub mod vote_signer;
+
+pub use bsc_miner::BscMiner;
+pub use config::{BscMiningArgs, MiningConfig, VoteConfig};
+pub use double_sign_reporter::DoubleSignReporter;
+pub use payload::BscPayloadBuilder;
+pub use vote_manager::VoteManager;
@@ -0,0 +1,635 @@
+//! Payload builder producing Parlia blocks out of the transaction pool.
+use crate::{
+    chainspec::BscChainSpec,
+    consensus::parlia::{
+        util::calculate_millisecond_timestamp, Parlia, Snapshot, DIFF_INTURN, DIFF_NOTURN,
+        EXTRA_SEAL_LEN, EXTRA_VANITY_LEN,
+    },
+    hardforks::BscHardforks,
+    node::{
+        engine::BscBuiltPayload,
+        evm::{
+            config::{BscBlockExecutionCtx, BscEvmConfig, BscMiningCtx},
+            executor::BscBlockExecutor,
+        },
+    },
+    system_contracts::{is_system_transaction, SystemContract},
+};
+use alloy_consensus::{BlockHeader, Header, Transaction};
+use alloy_primitives::{Address, Bytes, B256, U256};
+use reth_basic_payload_builder::{
+    is_better_payload, BuildArguments, BuildOutcome, PayloadBuilder, PayloadConfig,
+};
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_evm::{
+    block::{BlockExecutionError, BlockValidationError},
+    eth::EthBlockExecutionCtx,
+    execute::{BlockAssembler, BlockAssemblerInput, BlockExecutor},
+    ConfigureEvm,
+};
+use reth::{
+    payload::EthPayloadBuilderAttributes,
+    transaction_pool::{
+        error::InvalidPoolTransactionError, BestTransactions, BestTransactionsAttributes,
+        PoolTransaction, TransactionPool,
+    },
+};
+use reth_evm_ethereum::RethReceiptBuilder;
+use reth_payload_primitives::PayloadBuilderError;
+use reth_primitives::{InvalidTransactionError, SealedHeader, TransactionSigned};
+use reth_primitives_traits::Block as _;
+use reth_provider::{HashedPostStateProvider, StateProviderFactory, StateRootProvider};
+use reth_revm::{database::StateProviderDatabase, db::states::bundle_state::BundleRetention, State};
+use std::{
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tracing::{debug, trace, warn};
+
+/// Gas reserved at the end of every block for the system transactions.
+pub const SYSTEM_TXS_GAS: u64 = 20_000_000;
+
+/// Bound divisor of the gas limit change between two consecutive blocks.
+const GAS_LIMIT_BOUND_DIVISOR: u64 = 256;
+
+/// Minimum gas limit of a block.
+const MIN_GAS_LIMIT: u64 = 5000;
+
+/// Default gas limit targeted when none is configured, same as the one used by bsc-geth.
+const DEFAULT_DESIRED_GAS_LIMIT: u64 = 140_000_000;
+
+/// Errors returned while building a BSC payload.
+#[derive(Debug, thiserror::Error)]
+pub enum BscPayloadBuilderError {
+    /// No validator is configured, so the system txs can't be signed.
+    #[error("no validator configured for payload building")]
+    MissingValidator,
+    /// The snapshot of the parent block is not available.
+    #[error("snapshot not found, block_number: {block_number}")]
+    SnapshotNotFound { block_number: u64 },
+    /// The configured validator is not part of the current validator set.
+    #[error("unauthorized validator: {validator}")]
+    UnauthorizedValidator { validator: Address },
+}
+
+/// Builds BSC blocks on top of the given parent.
+///
+/// The executor runs in mining mode: it prepares the epoch extra data and creates, signs and
+/// appends the system transactions itself. The built block is returned with an empty seal, the
+/// header still needs to be signed by the validator before it can be imported.
+#[derive(Debug, Clone)]
+pub struct BscPayloadBuilder<Pool, Client> {
+    /// Client providing access to the parent state.
+    client: Client,
+    /// Transaction pool.
+    pool: Pool,
+    /// The evm configuration.
+    evm_config: BscEvmConfig,
+    /// Parlia consensus, used to compute the back off time of out-of-turn blocks.
+    parlia: Arc<Parlia<BscChainSpec>>,
+    /// Mining context, blocks can't be built without it.
+    mining: Option<BscMiningCtx>,
+    /// Gas limit the built blocks move towards.
+    desired_gas_limit: u64,
+    /// Vanity put in the extra data of the built blocks.
+    extra_vanity: Bytes,
+}
+
+impl<Pool, Client> BscPayloadBuilder<Pool, Client> {
+    /// Creates a new [`BscPayloadBuilder`].
+    pub fn new(
+        client: Client,
+        pool: Pool,
+        evm_config: BscEvmConfig,
+        mining: Option<BscMiningCtx>,
+    ) -> Self {
+        let chain_spec = evm_config.chain_spec().clone();
+        let parlia = Arc::new(Parlia::new(chain_spec.clone(), chain_spec.parlia.base.epoch_length));
+        Self {
+            client,
+            pool,
+            evm_config,
+            parlia,
+            mining,
+            desired_gas_limit: DEFAULT_DESIRED_GAS_LIMIT,
+            extra_vanity: Bytes::new(),
+        }
+    }
+
+    /// Sets the gas limit the built blocks move towards.
+    pub fn with_desired_gas_limit(mut self, desired_gas_limit: u64) -> Self {
+        self.desired_gas_limit = desired_gas_limit;
+        self
+    }
+
+    /// Sets the vanity of the built blocks, truncated to [`EXTRA_VANITY_LEN`] bytes.
+    pub fn with_extra_vanity(mut self, extra_vanity: Bytes) -> Self {
+        self.extra_vanity = extra_vanity;
+        self
+    }
+
+    /// Prepares the header template of the block built on top of `parent`.
+    ///
+    /// State related fields are filled in by the block assembler once the block is executed.
+    fn prepare_header(&self, parent: &SealedHeader, snap: &Snapshot, validator: Address) -> Header {
+        let chain_spec = self.evm_config.chain_spec().as_ref();
+        let number = parent.number + 1;
+
+        let mut extra_data = self.extra_vanity.to_vec();
+        extra_data.resize(EXTRA_VANITY_LEN, 0);
+        extra_data.extend_from_slice(&[0u8; EXTRA_SEAL_LEN]);
+
+        let mut header = Header {
+            parent_hash: parent.hash(),
+            number,
+            beneficiary: validator,
+            gas_limit: calculate_gas_limit(parent.gas_limit, self.desired_gas_limit),
+            difficulty: if snap.is_inturn(validator) { DIFF_INTURN } else { DIFF_NOTURN },
+            extra_data: extra_data.into(),
+            ..Default::default()
+        };
+
+        let block_time =
+            next_block_time_ms(&self.parlia, parent, snap, validator).max(now_millis());
+        header.timestamp = block_time / 1000;
+        if chain_spec.is_lorentz_active_at_timestamp(number, header.timestamp) {
+            header.mix_hash = B256::from(U256::from(block_time % 1000));
+        }
+
+        header.base_fee_per_gas = Some(
+            parent
+                .next_block_base_fee(chain_spec.base_fee_params_at_timestamp(header.timestamp))
+                .unwrap_or_default(),
+        );
+
+        if BscHardforks::is_cancun_active_at_timestamp(chain_spec, number, header.timestamp) {
+            header.blob_gas_used = Some(0);
+            header.excess_blob_gas = parent
+                .maybe_next_block_excess_blob_gas(
+                    chain_spec.blob_params_at_timestamp(header.timestamp),
+                )
+                .or(Some(0));
+        }
+
+        if chain_spec.is_bohr_active_at_timestamp(number, header.timestamp) {
+            header.parent_beacon_block_root = Some(B256::ZERO);
+        }
+
+        header
+    }
+}
+
+impl<Pool, Client> BscPayloadBuilder<Pool, Client>
+where
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>,
+    Client: StateProviderFactory,
+{
+    /// Builds a payload on top of the parent in `args`.
+    ///
+    /// When `empty` is set the pool is skipped and the block only contains system txs.
+    fn build_payload(
+        &self,
+        args: BuildArguments<EthPayloadBuilderAttributes, BscBuiltPayload>,
+        empty: bool,
+    ) -> Result<BuildOutcome<BscBuiltPayload>, PayloadBuilderError> {
+        let BuildArguments { mut cached_reads, config, cancel, best_payload } = args;
+        let PayloadConfig { parent_header, attributes } = config;
+        let chain_spec = self.evm_config.chain_spec().clone();
+
+        let mining = self
+            .mining
+            .as_ref()
+            .ok_or_else(|| PayloadBuilderError::other(BscPayloadBuilderError::MissingValidator))?;
+        let snap = crate::shared::get_snapshot_provider()
+            .and_then(|provider| {
+                provider.snapshot_by_hash(parent_header.number, parent_header.hash())
+            })
+            .ok_or_else(|| {
+                PayloadBuilderError::other(BscPayloadBuilderError::SnapshotNotFound {
+                    block_number: parent_header.number,
+                })
+            })?;
+        if !snap.validators.contains(&mining.validator) {
+            return Err(PayloadBuilderError::other(BscPayloadBuilderError::UnauthorizedValidator {
+                validator: mining.validator,
+            }))
+        }
+
+        let header = self.prepare_header(&parent_header, &snap, mining.validator);
+        debug!(
+            target: "payload_builder",
+            id = %attributes.id,
+            parent_hash = ?parent_header.hash(),
+            parent_number = parent_header.number,
+            timestamp = header.timestamp,
+            "building new payload"
+        );
+
+        let state_provider = self.client.state_by_block_hash(parent_header.hash())?;
+        let state = StateProviderDatabase::new(&state_provider);
+        let mut db = State::builder()
+            .with_database(cached_reads.as_db_mut(state))
+            .with_bundle_update()
+            .build();
+
+        let evm_env = self.evm_config.evm_env(&header);
+        let base_fee = evm_env.block_env.basefee;
+        let block_gas_limit = evm_env.block_env.gas_limit.saturating_sub(SYSTEM_TXS_GAS);
+        let ctx = BscBlockExecutionCtx {
+            base: EthBlockExecutionCtx {
+                parent_hash: parent_header.hash(),
+                parent_beacon_block_root: header.parent_beacon_block_root,
+                ommers: &[],
+                withdrawals: None,
+            },
+            header: Some(header),
+            mining: Some(mining.clone()),
+        };
+
+        let evm = self.evm_config.evm_with_env(&mut db, evm_env.clone());
+        let mut executor = BscBlockExecutor::new(
+            evm,
+            ctx.clone(),
+            chain_spec.clone(),
+            RethReceiptBuilder::default(),
+            SystemContract::new(chain_spec.clone()),
+        );
+        executor.apply_pre_execution_changes().map_err(|err| {
+            warn!(target: "payload_builder", %err, "failed to apply pre-execution changes");
+            PayloadBuilderError::evm(err)
+        })?;
+
+        let mut cumulative_gas_used = 0;
+        let mut total_fees = U256::ZERO;
+        let mut executed_txs: Vec<TransactionSigned> = Vec::new();
+
+        if !empty {
+            let mut best_txs = self
+                .pool
+                .best_transactions_with_attributes(BestTransactionsAttributes::new(base_fee, None));
+            // Blob sidecars are not propagated by the builder yet, keep blob txs in the pool.
+            best_txs.skip_blobs();
+
+            while let Some(pool_tx) = best_txs.next() {
+                if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
+                    best_txs.mark_invalid(
+                        &pool_tx,
+                        InvalidPoolTransactionError::ExceedsGasLimit(
+                            pool_tx.gas_limit(),
+                            block_gas_limit,
+                        ),
+                    );
+                    continue
+                }
+
+                if cancel.is_cancelled() {
+                    return Ok(BuildOutcome::Cancelled)
+                }
+
+                let tx = pool_tx.to_consensus();
+                // Zero priced txs sent by the validator to a system contract would be mistaken
+                // for system txs by the importing nodes.
+                if is_system_transaction(tx.inner(), tx.signer(), mining.validator) {
+                    best_txs.mark_invalid(&pool_tx, InvalidPoolTransactionError::Underpriced);
+                    continue
+                }
+
+                let gas_used = match executor.execute_transaction(tx.as_recovered_ref()) {
+                    Ok(gas_used) => gas_used,
+                    Err(BlockExecutionError::Validation(BlockValidationError::InvalidTx {
+                        error,
+                        ..
+                    })) => {
+                        if error.is_nonce_too_low() {
+                            trace!(target: "payload_builder", %error, ?tx, "skipping nonce too low transaction");
+                        } else {
+                            trace!(target: "payload_builder", %error, ?tx, "skipping invalid transaction and its descendants");
+                            best_txs.mark_invalid(
+                                &pool_tx,
+                                InvalidPoolTransactionError::Consensus(
+                                    InvalidTransactionError::TxTypeNotSupported,
+                                ),
+                            );
+                        }
+                        continue
+                    }
+                    Err(err) => return Err(PayloadBuilderError::evm(err)),
+                };
+
+                let miner_fee = tx
+                    .effective_tip_per_gas(base_fee)
+                    .expect("fee is always valid; execution succeeded");
+                total_fees += U256::from(miner_fee) * U256::from(gas_used);
+                cumulative_gas_used += gas_used;
+                executed_txs.push(tx.into_inner());
+            }
+
+            if !is_better_payload(best_payload.as_ref(), total_fees) {
+                drop(executor);
+                return Ok(BuildOutcome::Aborted { fees: total_fees, cached_reads })
+            }
+        }
+
+        let (_, execution_result, system_txs, header) =
+            executor.finish_mining().map_err(PayloadBuilderError::evm)?;
+        db.merge_transitions(BundleRetention::Reverts);
+
+        let hashed_state = state_provider.hashed_post_state(&db.bundle_state);
+        let state_root = state_provider.state_root(hashed_state).map_err(|err| {
+            warn!(target: "payload_builder", parent_hash = %parent_header.hash(), %err, "failed to calculate state root for payload");
+            err
+        })?;
+
+        let timestamp = header.timestamp;
+        let mut transactions = executed_txs;
+        transactions.extend(system_txs);
+        let block = self
+            .evm_config
+            .assemble_block(BlockAssemblerInput {
+                evm_env,
+                execution_ctx: BscBlockExecutionCtx { header: Some(header), mining: None, ..ctx },
+                parent: &*parent_header,
+                transactions,
+                output: &execution_result,
+                bundle_state: &db.bundle_state,
+                state_provider: &state_provider,
+                state_root,
+            })
+            .map_err(PayloadBuilderError::evm)?;
+
+        let sealed_block = Arc::new(block.seal_slow());
+        debug!(target: "payload_builder", id = %attributes.id, sealed_block_header = ?sealed_block.sealed_header(), "built unsigned block");
+
+        let requests = EthereumHardforks::is_prague_active_at_timestamp(chain_spec.as_ref(), timestamp)
+            .then_some(execution_result.requests);
+        Ok(BuildOutcome::Better {
+            payload: BscBuiltPayload { block: sealed_block, fees: total_fees, requests },
+            cached_reads,
+        })
+    }
+}
+
+impl<Pool, Client> PayloadBuilder for BscPayloadBuilder<Pool, Client>
+where
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>> + Clone,
+    Client: StateProviderFactory + Clone,
+{
+    type Attributes = EthPayloadBuilderAttributes;
+    type BuiltPayload = BscBuiltPayload;
+
+    fn try_build(
+        &self,
+        args: BuildArguments<Self::Attributes, Self::BuiltPayload>,
+    ) -> Result<BuildOutcome<Self::BuiltPayload>, PayloadBuilderError> {
+        self.build_payload(args, false)
+    }
+
+    fn build_empty_payload(
+        &self,
+        config: PayloadConfig<Self::Attributes, Header>,
+    ) -> Result<Self::BuiltPayload, PayloadBuilderError> {
+        let args = BuildArguments::new(Default::default(), config, Default::default(), None);
+        self.build_payload(args, true)?.into_payload().ok_or(PayloadBuilderError::MissingPayload)
+    }
+}
+
+/// Calculates the gas limit of the next block, moving from the parent gas limit towards the
+/// desired one by at most `parent_gas_limit / GAS_LIMIT_BOUND_DIVISOR - 1`.
+pub fn calculate_gas_limit(parent_gas_limit: u64, desired_gas_limit: u64) -> u64 {
+    let delta = (parent_gas_limit / GAS_LIMIT_BOUND_DIVISOR).saturating_sub(1);
+    let desired_gas_limit = desired_gas_limit.max(MIN_GAS_LIMIT);
+    if parent_gas_limit < desired_gas_limit {
+        (parent_gas_limit + delta).min(desired_gas_limit)
+    } else {
+        parent_gas_limit.saturating_sub(delta).max(desired_gas_limit)
+    }
+}
+
+/// Returns the earliest time, in milliseconds, at which `validator` may produce the block on top
+/// of `parent`, back off time of out-of-turn validators included.
+pub fn next_block_time_ms(
+    parlia: &Parlia<BscChainSpec>,
+    parent: &Header,
+    snap: &Snapshot,
+    validator: Address,
+) -> u64 {
+    let number = parent.number + 1;
+    let mut block_time = calculate_millisecond_timestamp(parent) + snap.block_interval;
+    if parlia.spec.is_ramanujan_active_at_block(number) {
+        let header = Header {
+            number,
+            beneficiary: validator,
+            timestamp: block_time / 1000,
+            ..Default::default()
+        };
+        block_time += parlia.back_off_time(snap, parent, &header);
+    }
+    block_time
+}
+
+/// Returns the current unix time in milliseconds.
+pub(crate) fn now_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        consensus::parlia::{signer::Signer, SnapshotProvider},
+        node::{evm::util::HEADER_CACHE_READER, miner::signer::LocalSigner},
+    };
+    use alloy_consensus::{
+        transaction::{Recovered, SignerRecoverable},
+        TxEip1559, TxEip4844,
+    };
+    use alloy_eips::Encodable2718;
+    use alloy_primitives::TxKind;
+    use reth::transaction_pool::{
+        blobstore::InMemoryBlobStore, noop::NoopTransactionValidator, CoinbaseTipOrdering,
+        EthPooledTransaction, Pool, PoolConfig, TransactionOrigin,
+    };
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+
+    const CHAIN_ID: u64 = 714714;
+    const GWEI: u128 = 1_000_000_000;
+
+    /// Custom chain running the block activated forks, up to Hertz and London.
+    fn chain_spec() -> Arc<BscChainSpec> {
+        let genesis = serde_json::from_str(
+            r#"{
+                "config": {
+                    "chainId": 714714,
+                    "homesteadBlock": 0,
+                    "eip150Block": 0,
+                    "eip155Block": 0,
+                    "eip158Block": 0,
+                    "byzantiumBlock": 0,
+                    "constantinopleBlock": 0,
+                    "petersburgBlock": 0,
+                    "istanbulBlock": 0,
+                    "muirGlacierBlock": 0,
+                    "ramanujanBlock": 0,
+                    "nielsBlock": 0,
+                    "mirrorSyncBlock": 0,
+                    "brunoBlock": 0,
+                    "eulerBlock": 0,
+                    "nanoBlock": 0,
+                    "moranBlock": 0,
+                    "gibbsBlock": 0,
+                    "planckBlock": 0,
+                    "lubanBlock": 0,
+                    "platoBlock": 0,
+                    "berlinBlock": 0,
+                    "londonBlock": 0,
+                    "hertzBlock": 0,
+                    "hertzfixBlock": 0
+                },
+                "nonce": "0x0",
+                "timestamp": "0x0",
+                "extraData": "0x",
+                "gasLimit": "0x2625a00",
+                "difficulty": "0x1",
+                "mixHash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+                "coinbase": "0x0000000000000000000000000000000000000000",
+                "alloc": {}
+            }"#,
+        )
+        .unwrap();
+        Arc::new(BscChainSpec::from_genesis(genesis).unwrap())
+    }
+
+    /// Snapshot provider only knowing the snapshot of the parent block.
+    struct ParentSnapshot(Snapshot);
+
+    impl SnapshotProvider for ParentSnapshot {
+        fn snapshot(&self, block_number: u64) -> Option<Snapshot> {
+            (block_number == self.0.block_number).then(|| self.0.clone())
+        }
+
+        fn insert(&self, _snapshot: Snapshot) {}
+
+        fn get_header(&self, _block_number: u64) -> Option<Header> {
+            None
+        }
+    }
+
+    /// Signs the transaction with the key filled with `key`.
+    fn pooled(key: u8, tx: reth_primitives::Transaction) -> EthPooledTransaction {
+        let signer = LocalSigner::from_private_key(B256::repeat_byte(key)).unwrap();
+        let signed = signer.sign_transaction(tx).unwrap();
+        let encoded_length = signed.encode_2718_len();
+        let sender = signer.address();
+        EthPooledTransaction::new(Recovered::new_unchecked(signed, sender), encoded_length)
+    }
+
+    fn transfer(key: u8, tip: u128) -> EthPooledTransaction {
+        pooled(
+            key,
+            reth_primitives::Transaction::Eip1559(TxEip1559 {
+                chain_id: CHAIN_ID,
+                gas_limit: 21_000,
+                max_fee_per_gas: tip,
+                max_priority_fee_per_gas: tip,
+                to: TxKind::Call(Address::repeat_byte(0xaa)),
+                value: U256::from(1),
+                ..Default::default()
+            }),
+        )
+    }
+
+    fn blob_tx(key: u8, tip: u128) -> EthPooledTransaction {
+        pooled(
+            key,
+            reth_primitives::Transaction::Eip4844(TxEip4844 {
+                chain_id: CHAIN_ID,
+                gas_limit: 21_000,
+                max_fee_per_gas: tip,
+                max_priority_fee_per_gas: tip,
+                to: Address::repeat_byte(0xaa),
+                blob_versioned_hashes: vec![B256::with_last_byte(1)],
+                max_fee_per_blob_gas: GWEI,
+                ..Default::default()
+            }),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_build_payload() {
+        let signer = Arc::new(LocalSigner::from_private_key(B256::repeat_byte(0x42)).unwrap());
+        let validator = signer.address();
+
+        // the parent leaves room for two transfers next to the gas reserved for the system txs
+        let gas_limit = SYSTEM_TXS_GAS + 2 * 21_000;
+        let parent = SealedHeader::seal_slow(Header {
+            number: 1,
+            beneficiary: validator,
+            gas_limit,
+            timestamp: now_millis() / 1000 - 1,
+            difficulty: DIFF_INTURN,
+            ..Default::default()
+        });
+        HEADER_CACHE_READER.lock().unwrap().insert_header_to_cache(parent.header().clone());
+        let snap = Snapshot::new(vec![validator], parent.number, parent.hash(), 100, None);
+        let _ = crate::shared::set_snapshot_provider(Arc::new(ParentSnapshot(snap)));
+
+        let client = MockEthProvider::default();
+        let pool = Pool::new(
+            NoopTransactionValidator::<EthPooledTransaction>::default(),
+            CoinbaseTipOrdering::default(),
+            InMemoryBlobStore::default(),
+            PoolConfig::default(),
+        );
+        // the blob tx pays the best tip, it would come first if it was not skipped
+        let txs =
+            [transfer(1, 3 * GWEI), transfer(2, 2 * GWEI), transfer(3, GWEI), blob_tx(4, 4 * GWEI)];
+        for tx in txs {
+            client.add_account(tx.sender(), ExtendedAccount::new(0, U256::from(u128::MAX)));
+            pool.add_transaction(TransactionOrigin::External, tx).await.unwrap();
+        }
+
+        let mining = BscMiningCtx { validator, signer };
+        let builder =
+            BscPayloadBuilder::new(client, pool, BscEvmConfig::new(chain_spec()), Some(mining))
+                .with_desired_gas_limit(gas_limit);
+        let config = PayloadConfig::new(Arc::new(parent), EthPayloadBuilderAttributes::default());
+        let payload = builder
+            .try_build(BuildArguments::new(Default::default(), config, Default::default(), None))
+            .unwrap()
+            .into_payload()
+            .unwrap();
+
+        assert_eq!(payload.block.header().gas_limit, gas_limit);
+        let transactions = &payload.block.body().inner.transactions;
+        assert!(transactions.iter().all(|tx| tx.blob_versioned_hashes().is_none()));
+
+        // the two best paying transfers fill the gas left next to the one of the system txs
+        let (user_txs, system_txs) = transactions.split_at(2);
+        let tips = user_txs.iter().map(|tx| tx.max_priority_fee_per_gas()).collect::<Vec<_>>();
+        assert_eq!(tips, [Some(3 * GWEI), Some(2 * GWEI)]);
+        let user_gas = user_txs.iter().map(|tx| tx.gas_limit()).sum::<u64>();
+        assert!(user_gas <= gas_limit - SYSTEM_TXS_GAS);
+
+        // the validator is rewarded by system txs closing the block, paying out the whole fees
+        assert!(!system_txs.is_empty());
+        for tx in system_txs {
+            assert!(is_system_transaction(tx, tx.recover_signer().unwrap(), validator));
+        }
+        assert_eq!(payload.fees, U256::from(5 * GWEI * 21_000));
+        let rewards = system_txs.iter().fold(U256::ZERO, |rewards, tx| rewards + tx.value());
+        assert_eq!(rewards, payload.fees);
+    }
+
+    #[test]
+    fn test_calculate_gas_limit() {
+        // moving up is bounded by parent / 256 - 1
+        assert_eq!(calculate_gas_limit(70_000_000, 140_000_000), 70_000_000 + 273_436);
+        // moving down is bounded as well
+        assert_eq!(calculate_gas_limit(140_000_000, 70_000_000), 140_000_000 - 546_874);
+        // the desired limit is never overshot
+        assert_eq!(calculate_gas_limit(139_900_000, 140_000_000), 140_000_000);
+        assert_eq!(calculate_gas_limit(140_100_000, 140_000_000), 140_000_000);
+        // the desired limit is clamped to the minimum
+        assert_eq!(calculate_gas_limit(MIN_GAS_LIMIT, 0), MIN_GAS_LIMIT);
+    }
+}
@@ -0,0 +1,307 @@
+//! Signer backends holding the validator key.
+use crate::consensus::parlia::signer::{Signer, SignerError};
+use alloy_consensus::{transaction::SignerRecoverable, SignableTransaction};
+use alloy_eips::{Decodable2718, Typed2718};
+use alloy_primitives::{keccak256, Address, Bytes, Signature, B256, U256, U64};
+use alloy_signer::SignerSync;
+use alloy_signer_local::PrivateKeySigner;
+use jsonrpsee::{
+    core::{client::ClientT, params::ArrayParams, ClientError},
+    http_client::{HttpClient, HttpClientBuilder},
+    rpc_params,
+};
+use reth_primitives::{Transaction, TransactionSigned};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// Timeout of the requests sent to a remote signer.
+const REMOTE_SIGNER_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Signer holding a plaintext key in memory, only meant for devnets.
+#[derive(Debug, Clone)]
+pub struct LocalSigner {
+    inner: PrivateKeySigner,
+}
+
+impl LocalSigner {
+    /// Creates a signer from the raw private key.
+    pub fn from_private_key(private_key: B256) -> Result<Self, SignerError> {
+        Ok(Self { inner: PrivateKeySigner::from_bytes(&private_key)? })
+    }
+
+    fn sign_hash(&self, hash: B256) -> Result<Signature, SignerError> {
+        Ok(self.inner.sign_hash_sync(&hash)?)
+    }
+}
+
+impl Signer for LocalSigner {
+    fn address(&self) -> Address {
+        self.inner.address()
+    }
+
+    fn sign_data(&self, _mime_type: &str, data: &[u8]) -> Result<[u8; 65], SignerError> {
+        Ok(signature_to_bytes(&self.sign_hash(keccak256(data))?))
+    }
+
+    fn sign_transaction(&self, tx: Transaction) -> Result<TransactionSigned, SignerError> {
+        let signature = self.sign_hash(tx.signature_hash())?;
+        Ok(TransactionSigned::new_unhashed(tx, signature))
+    }
+}
+
+/// Signer backed by an encrypted JSON keystore file, as written by geth.
+#[derive(Debug, Clone)]
+pub struct KeystoreSigner {
+    path: PathBuf,
+    inner: LocalSigner,
+}
+
+impl KeystoreSigner {
+    /// Decrypts the keystore at `path` with the given password.
+    pub fn decrypt(
+        path: impl AsRef<Path>,
+        password: impl AsRef<[u8]>,
+    ) -> Result<Self, SignerError> {
+        let path = path.as_ref().to_path_buf();
+        let inner = LocalSigner { inner: PrivateKeySigner::decrypt_keystore(&path, password)? };
+        Ok(Self { path, inner })
+    }
+
+    /// Returns the path of the keystore file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Signer for KeystoreSigner {
+    fn address(&self) -> Address {
+        self.inner.address()
+    }
+
+    fn sign_data(&self, mime_type: &str, data: &[u8]) -> Result<[u8; 65], SignerError> {
+        self.inner.sign_data(mime_type, data)
+    }
+
+    fn sign_transaction(&self, tx: Transaction) -> Result<TransactionSigned, SignerError> {
+        self.inner.sign_transaction(tx)
+    }
+}
+
+/// Arguments of `account_signTransaction`, following clef's `SendTxArgs`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RemoteTxArgs {
+    from: Address,
+    to: Option<Address>,
+    gas: U64,
+    gas_price: U256,
+    value: U256,
+    nonce: U64,
+    input: Bytes,
+    chain_id: Option<U64>,
+}
+
+/// Result of `account_signTransaction`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RemoteSignedTx {
+    /// The RLP encoded signed transaction.
+    raw: Bytes,
+}
+
+/// Signer delegating to an external signer over HTTP JSON-RPC, so that the key never enters the
+/// node process.
+///
+/// It speaks clef's `account_signData` and `account_signTransaction` methods.
+#[derive(Debug, Clone)]
+pub struct RemoteSigner {
+    client: HttpClient,
+    address: Address,
+}
+
+impl RemoteSigner {
+    /// Creates a signer for `address` talking to the remote signer at `url`.
+    pub fn new(url: &str, address: Address) -> Result<Self, SignerError> {
+        let client =
+            HttpClientBuilder::default().request_timeout(REMOTE_SIGNER_TIMEOUT).build(url)?;
+        Ok(Self { client, address })
+    }
+
+    /// Sends the request, blocking the current thread until the response arrives.
+    fn request<R: DeserializeOwned>(
+        &self,
+        method: &'static str,
+        params: ArrayParams,
+    ) -> Result<R, SignerError> {
+        let handle = tokio::runtime::Handle::try_current().map_err(|_| {
+            ClientError::Custom("remote signer requires a tokio runtime".into())
+        })?;
+        let request = self.client.request(method, params);
+        Ok(tokio::task::block_in_place(|| handle.block_on(request))?)
+    }
+}
+
+impl Signer for RemoteSigner {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn sign_data(&self, mime_type: &str, data: &[u8]) -> Result<[u8; 65], SignerError> {
+        let signature: Bytes = self.request(
+            "account_signData",
+            rpc_params![mime_type, self.address, Bytes::copy_from_slice(data)],
+        )?;
+        let mut signature: [u8; 65] = signature.as_ref().try_into().map_err(|_| {
+            SignerError::InvalidResponse(format!("signature of {} bytes", signature.len()))
+        })?;
+        // clef may return the legacy `V` of 27/28
+        if signature[64] >= 27 {
+            signature[64] -= 27;
+        }
+        Ok(signature)
+    }
+
+    fn sign_transaction(&self, tx: Transaction) -> Result<TransactionSigned, SignerError> {
+        let Transaction::Legacy(legacy) = &tx else {
+            return Err(SignerError::UnsupportedTransaction(tx.ty()))
+        };
+        let args = RemoteTxArgs {
+            from: self.address,
+            to: legacy.to.to().copied(),
+            gas: U64::from(legacy.gas_limit),
+            gas_price: U256::from(legacy.gas_price),
+            value: legacy.value,
+            nonce: U64::from(legacy.nonce),
+            input: legacy.input.clone(),
+            chain_id: legacy.chain_id.map(U64::from),
+        };
+        let signed: RemoteSignedTx = self.request("account_signTransaction", rpc_params![args])?;
+
+        let signed_tx = TransactionSigned::decode_2718(&mut signed.raw.as_ref())
+            .map_err(|err| SignerError::InvalidResponse(err.to_string()))?;
+        if signed_tx.signature_hash() != tx.signature_hash() {
+            return Err(SignerError::InvalidResponse("signed a different transaction".into()))
+        }
+        let signer = signed_tx
+            .recover_signer()
+            .map_err(|err| SignerError::InvalidResponse(err.to_string()))?;
+        if signer != self.address {
+            return Err(SignerError::InvalidResponse(format!("transaction signed by {signer}")))
+        }
+        Ok(signed_tx)
+    }
+}
+
+/// Encodes the signature as `[R || S || V]` with `V` being the recovery id.
+fn signature_to_bytes(signature: &Signature) -> [u8; 65] {
+    let mut out = [0u8; 65];
+    out[..32].copy_from_slice(&signature.r().to_be_bytes::<32>());
+    out[32..64].copy_from_slice(&signature.s().to_be_bytes::<32>());
+    out[64] = signature.v() as u8;
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus::parlia::signer::MIMETYPE_PARLIA;
+    use alloy_consensus::TxLegacy;
+    use alloy_primitives::{address, b256, TxKind};
+    use jsonrpsee::{server::Server, types::ErrorObjectOwned, RpcModule};
+
+    // well known dev key
+    const DEV_KEY: B256 =
+        b256!("ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80");
+    const DEV_ADDRESS: Address = address!("f39Fd6e51aad88F6F4ce6aB8827279cffFb92266");
+
+    fn system_tx() -> Transaction {
+        Transaction::Legacy(TxLegacy {
+            chain_id: Some(56),
+            nonce: 7,
+            gas_price: 0,
+            gas_limit: u64::MAX / 2,
+            to: TxKind::Call(address!("0000000000000000000000000000000000001000")),
+            value: U256::from(1),
+            input: Bytes::from_static(&[0xf3, 0x40, 0xfa, 0x01]),
+        })
+    }
+
+    /// Spawns a mock remote signer answering like clef, backed by the dev key.
+    async fn spawn_mock_signer() -> String {
+        let mut module = RpcModule::new(LocalSigner::from_private_key(DEV_KEY).unwrap());
+        module
+            .register_method("account_signData", |params, signer, _| {
+                let (mime_type, _address, data): (String, Address, Bytes) = params.parse()?;
+                let mut signature = signer.sign_data(&mime_type, &data).unwrap();
+                signature[64] += 27;
+                Ok::<_, ErrorObjectOwned>(Bytes::copy_from_slice(&signature))
+            })
+            .unwrap();
+        module
+            .register_method("account_signTransaction", |params, signer, _| {
+                let (args,): (RemoteTxArgs,) = params.parse()?;
+                let tx = Transaction::Legacy(TxLegacy {
+                    chain_id: args.chain_id.map(|id| id.to()),
+                    nonce: args.nonce.to(),
+                    gas_price: args.gas_price.to(),
+                    gas_limit: args.gas.to(),
+                    to: args.to.into(),
+                    value: args.value,
+                    input: args.input,
+                });
+                let signed = signer.sign_transaction(tx).unwrap();
+                let mut raw = Vec::new();
+                alloy_eips::Encodable2718::encode_2718(&signed, &mut raw);
+                Ok::<_, ErrorObjectOwned>(RemoteSignedTx { raw: raw.into() })
+            })
+            .unwrap();
+
+        let server = Server::builder().build("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", server.local_addr().unwrap());
+        let handle = server.start(module);
+        tokio::spawn(handle.stopped());
+        url
+    }
+
+    #[test]
+    fn test_local_signer() {
+        let signer = LocalSigner::from_private_key(DEV_KEY).unwrap();
+        assert_eq!(signer.address(), DEV_ADDRESS);
+
+        let data = b"parlia header";
+        let signature = Signature::from_raw_array(&signer.sign_data(MIMETYPE_PARLIA, data).unwrap())
+            .unwrap();
+        assert_eq!(
+            signature.recover_address_from_prehash(&keccak256(data)).unwrap(),
+            DEV_ADDRESS
+        );
+
+        let signed = signer.sign_transaction(system_tx()).unwrap();
+        assert_eq!(signed.recover_signer().unwrap(), DEV_ADDRESS);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_remote_signer() {
+        let url = spawn_mock_signer().await;
+        let local = LocalSigner::from_private_key(DEV_KEY).unwrap();
+        let remote = RemoteSigner::new(&url, DEV_ADDRESS).unwrap();
+
+        let data = b"parlia header";
+        assert_eq!(
+            remote.sign_data(MIMETYPE_PARLIA, data).unwrap(),
+            local.sign_data(MIMETYPE_PARLIA, data).unwrap()
+        );
+
+        let signed = remote.sign_transaction(system_tx()).unwrap();
+        assert_eq!(signed, local.sign_transaction(system_tx()).unwrap());
+
+        // a signer answering for another account is rejected
+        let other = RemoteSigner::new(&url, Address::ZERO).unwrap();
+        assert!(matches!(
+            other.sign_transaction(system_tx()),
+            Err(SignerError::InvalidResponse(_))
+        ));
+    }
+}
@@ -0,0 +1,143 @@
+//! Persistent journal of the votes cast by the validator.
+use crate::consensus::parlia::vote::VoteData;
+use alloy_primitives::BlockNumber;
+use std::{
+    collections::BTreeMap,
+    fs::{File, OpenOptions},
+    io::{self, BufRead, BufReader, Write},
+    ops::RangeBounds,
+    path::PathBuf,
+};
+use tracing::warn;
+
+/// Range of blocks in which a vote can still be used as evidence of a malicious vote.
+pub const MALICIOUS_VOTE_SLASH_SCOPE: u64 = 256;
+
+/// Append-only journal of the [`VoteData`] signed by the validator, one JSON document per line.
+///
+/// A vote is journaled before it is broadcast so the voting rules still hold after a restart.
+/// Votes older than [`MALICIOUS_VOTE_SLASH_SCOPE`] blocks are forgotten and the file is compacted
+/// once it holds twice as many lines as there are votes worth keeping.
+#[derive(Debug)]
+pub struct VoteJournal {
+    path: PathBuf,
+    file: File,
+    votes: BTreeMap<BlockNumber, VoteData>,
+    lines: usize,
+}
+
+impl VoteJournal {
+    /// Opens the journal at `path`, creating it if it does not exist.
+    pub fn open(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut votes = BTreeMap::new();
+        let mut lines = 0;
+        if path.exists() {
+            for line in BufReader::new(File::open(&path)?).lines() {
+                let line = line?;
+                lines += 1;
+                match serde_json::from_str::<VoteData>(&line) {
+                    Ok(vote) => {
+                        votes.insert(vote.target_number, vote);
+                    }
+                    // only the last line can be torn by a crash while writing it
+                    Err(err) => {
+                        warn!(target: "bsc::vote", %err, "Skipping corrupted vote journal entry")
+                    }
+                }
+            }
+        }
+
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let mut journal = Self { path, file, votes, lines };
+        journal.prune();
+        Ok(journal)
+    }
+
+    /// Returns the vote cast for the block at `target_number`.
+    pub fn get(&self, target_number: BlockNumber) -> Option<&VoteData> {
+        self.votes.get(&target_number)
+    }
+
+    /// Returns the votes cast for targets within `range`.
+    pub fn range(&self, range: impl RangeBounds<BlockNumber>) -> impl Iterator<Item = &VoteData> {
+        self.votes.range(range).map(|(_, vote)| vote)
+    }
+
+    /// Returns the highest target voted for.
+    pub fn latest(&self) -> Option<&VoteData> {
+        self.votes.last_key_value().map(|(_, vote)| vote)
+    }
+
+    /// Durably records `vote`.
+    pub fn record(&mut self, vote: VoteData) -> io::Result<()> {
+        writeln!(self.file, "{}", serde_json::to_string(&vote)?)?;
+        self.file.sync_data()?;
+        self.lines += 1;
+        self.votes.insert(vote.target_number, vote);
+        self.prune();
+
+        if self.lines > 2 * MALICIOUS_VOTE_SLASH_SCOPE as usize {
+            self.compact()?;
+        }
+        Ok(())
+    }
+
+    fn prune(&mut self) {
+        let Some(latest) = self.votes.last_key_value().map(|(number, _)| *number) else { return };
+        self.votes = self.votes.split_off(&latest.saturating_sub(MALICIOUS_VOTE_SLASH_SCOPE));
+    }
+
+    /// Rewrites the journal with the votes still in memory.
+    fn compact(&mut self) -> io::Result<()> {
+        let tmp = self.path.with_extension("tmp");
+        let mut file = File::create(&tmp)?;
+        for vote in self.votes.values() {
+            writeln!(file, "{}", serde_json::to_string(vote)?)?;
+        }
+        file.sync_all()?;
+        std::fs::rename(&tmp, &self.path)?;
+
+        self.file = OpenOptions::new().append(true).open(&self.path)?;
+        self.lines = self.votes.len();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+
+    fn vote(source_number: u64, target_number: u64) -> VoteData {
+        VoteData {
+            source_number,
+            source_hash: B256::with_last_byte(source_number as u8),
+            target_number,
+            target_hash: B256::with_last_byte(target_number as u8),
+        }
+    }
+
+    #[test]
+    fn test_journal_survives_restart() {
+        let path = std::env::temp_dir().join(format!("vote-journal-{}", uuid::Uuid::new_v4()));
+        {
+            let mut journal = VoteJournal::open(&path).unwrap();
+            for target in 1..=1000 {
+                journal.record(vote(target - 1, target)).unwrap();
+            }
+            assert!(journal.lines <= 2 * MALICIOUS_VOTE_SLASH_SCOPE as usize);
+        }
+
+        let journal = VoteJournal::open(&path).unwrap();
+        assert_eq!(journal.latest(), Some(&vote(999, 1000)));
+        assert_eq!(journal.get(900), Some(&vote(899, 900)));
+        assert_eq!(journal.get(700), None);
+        assert_eq!(journal.range(995..=1005).count(), 6);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
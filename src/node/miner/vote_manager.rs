@@ -0,0 +1,162 @@
+//! Fast finality votes of the local validator, see BEP-126.
+use super::{
+    bsc_miner::next_head,
+    config::VoteConfig,
+    payload::now_millis,
+    vote_journal::{VoteJournal, MALICIOUS_VOTE_SLASH_SCOPE},
+    vote_signer::VoteSigner,
+};
+use crate::{
+    consensus::parlia::{
+        util::calculate_millisecond_timestamp,
+        votes::{self, UPPER_LIMIT_OF_VOTE_BLOCK_NUMBER},
+        SnapshotProvider, VoteData,
+    },
+    node::network::bsc_protocol::registry,
+    BscPrimitives,
+};
+use reth_primitives::SealedHeader;
+use reth_provider::CanonStateSubscriptions;
+use std::sync::Arc;
+use tracing::{debug, info, trace, warn};
+
+/// Heads older than this many block intervals are imported while syncing and not voted for.
+const MAX_VOTE_DELAY_BLOCKS: u64 = 3;
+
+/// Signs and broadcasts a vote for every new canonical head.
+///
+/// The source of a vote is the latest justified block and its target the new head. Votes
+/// breaking the BEP-126 rules are never cast: the validator does not vote twice for the same
+/// height and never casts a vote surrounding, or surrounded by, one of its previous votes.
+pub struct VoteManager<Provider> {
+    provider: Provider,
+    snapshot_provider: Arc<dyn SnapshotProvider + Send + Sync>,
+    signer: Arc<VoteSigner>,
+    journal: VoteJournal,
+}
+
+impl<Provider> std::fmt::Debug for VoteManager<Provider> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VoteManager")
+            .field("signer", &self.signer)
+            .field("journal", &self.journal)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Provider> VoteManager<Provider>
+where
+    Provider: CanonStateSubscriptions<Primitives = BscPrimitives> + 'static,
+{
+    /// Creates a new [`VoteManager`], loading the votes already cast from the journal.
+    ///
+    /// Must be called after the node is launched, as it relies on the global snapshot provider.
+    pub fn new(provider: Provider, config: VoteConfig) -> eyre::Result<Self> {
+        let snapshot_provider = crate::shared::get_snapshot_provider()
+            .cloned()
+            .ok_or_else(|| eyre::eyre!("snapshot provider is not initialized"))?;
+        let journal = VoteJournal::open(&config.journal)?;
+        Ok(Self { provider, snapshot_provider, signer: config.signer, journal })
+    }
+
+    /// Votes for the canonical heads until the canonical state stream is closed.
+    pub async fn run(mut self) {
+        info!(target: "bsc::vote", vote_address = %self.signer.vote_address(), "Starting vote manager");
+
+        let mut canon_events = self.provider.subscribe_to_canonical_state();
+        while let Some(head) = next_head(&mut canon_events).await {
+            if let Err(err) = self.vote(&head) {
+                warn!(target: "bsc::vote", number = head.number, %err, "Failed to vote");
+            }
+        }
+    }
+
+    fn vote(&mut self, head: &SealedHeader) -> eyre::Result<()> {
+        let snap = self
+            .snapshot_provider
+            .snapshot_by_hash(head.number, head.hash())
+            .ok_or_else(|| eyre::eyre!("snapshot of block {} not found", head.number))?;
+        let vote_address = self.signer.vote_address();
+        if !snap.validators_map.values().any(|info| info.vote_addr == vote_address) {
+            trace!(target: "bsc::vote", %vote_address, "Not in the validator set, skip voting");
+            return Ok(())
+        }
+        let head_time = calculate_millisecond_timestamp(head.header());
+        if head_time + MAX_VOTE_DELAY_BLOCKS * snap.block_interval < now_millis() {
+            trace!(target: "bsc::vote", number = head.number, "Head is too old, skip voting");
+            return Ok(())
+        }
+
+        let data = VoteData {
+            source_number: snap.vote_data.target_number,
+            source_hash: snap.vote_data.target_hash,
+            target_number: head.number,
+            target_hash: head.hash(),
+        };
+        if !under_rules(&self.journal, &data) {
+            debug!(target: "bsc::vote", ?data, "Vote would break the voting rules, skip voting");
+            return Ok(())
+        }
+
+        // the vote must be on disk before it leaves the node
+        self.journal.record(data)?;
+        let vote = self.signer.sign(data);
+        let peers = registry::relay_vote(&vote, None);
+        votes::put_vote(vote);
+        debug!(target: "bsc::vote", number = head.number, hash = ?head.hash(), peers, "Voted");
+        Ok(())
+    }
+}
+
+/// Checks `vote` against the votes already cast, following the rules of BEP-126.
+fn under_rules(journal: &VoteJournal, vote: &VoteData) -> bool {
+    let VoteData { source_number, target_number, .. } = *vote;
+    if target_number <= source_number {
+        return false
+    }
+
+    // rule 1: a validator must not publish two distinct votes for the same height
+    if journal.get(target_number).is_some() {
+        return false
+    }
+
+    // rule 2: a validator must not vote within the span of its other votes
+    let lowest = (source_number + 1).max(target_number.saturating_sub(MALICIOUS_VOTE_SLASH_SCOPE));
+    if journal.range(lowest..target_number).any(|prev| prev.source_number > source_number) {
+        return false
+    }
+    journal
+        .range(target_number + 1..=target_number + UPPER_LIMIT_OF_VOTE_BLOCK_NUMBER)
+        .all(|next| next.source_number >= source_number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vote(source_number: u64, target_number: u64) -> VoteData {
+        VoteData { source_number, target_number, ..Default::default() }
+    }
+
+    #[test]
+    fn test_voting_rules() {
+        let path = std::env::temp_dir().join(format!("vote-journal-{}", uuid::Uuid::new_v4()));
+        let mut journal = VoteJournal::open(&path).unwrap();
+        journal.record(vote(10, 12)).unwrap();
+        journal.record(vote(12, 15)).unwrap();
+
+        // double vote
+        assert!(!under_rules(&journal, &vote(11, 15)));
+        // surrounds 12 -> 15
+        assert!(!under_rules(&journal, &vote(11, 16)));
+        // surrounded by 12 -> 15
+        assert!(!under_rules(&journal, &vote(13, 14)));
+        assert!(!under_rules(&journal, &vote(13, 13)));
+
+        assert!(under_rules(&journal, &vote(11, 13)));
+        assert!(under_rules(&journal, &vote(12, 16)));
+        assert!(under_rules(&journal, &vote(15, 16)));
+        assert!(under_rules(&journal, &vote(10, 11)));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,239 @@
+//! BLS key signing the fast finality votes of the validator.
+use crate::consensus::parlia::vote::{VoteAddress, VoteData, VoteEnvelope, VoteSignature, BLST_DST};
+use aes::cipher::{KeyIvInit, StreamCipher};
+use alloy_primitives::hex;
+use blst::{min_pk::SecretKey, BLST_ERROR};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::{fmt, path::Path};
+use unicode_normalization::UnicodeNormalization;
+
+type Aes128Ctr = ctr::Ctr128BE<aes::Aes128>;
+
+/// Errors returned when loading the BLS key.
+#[derive(Debug, thiserror::Error)]
+pub enum VoteSignerError {
+    /// The secret is not a valid BLS12-381 secret key.
+    #[error("invalid BLS secret key: {0:?}")]
+    InvalidKey(BLST_ERROR),
+    /// The keystore file could not be read.
+    #[error("failed to read BLS keystore: {0}")]
+    Io(#[from] std::io::Error),
+    /// The keystore is not a valid EIP-2335 JSON document.
+    #[error("failed to parse BLS keystore: {0}")]
+    Json(#[from] serde_json::Error),
+    /// The keystore uses parameters we do not support.
+    #[error("invalid BLS keystore: {0}")]
+    Keystore(String),
+    /// The checksum of the keystore does not match, the password is wrong.
+    #[error("invalid BLS keystore password")]
+    InvalidPassword,
+}
+
+/// Signs the votes of the validator with its BLS key.
+pub struct VoteSigner {
+    secret_key: SecretKey,
+    vote_address: VoteAddress,
+}
+
+impl fmt::Debug for VoteSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VoteSigner")
+            .field("vote_address", &self.vote_address)
+            .finish_non_exhaustive()
+    }
+}
+
+impl VoteSigner {
+    /// Creates a signer from the raw bytes of the BLS secret key.
+    pub fn from_secret_key(secret: &[u8]) -> Result<Self, VoteSignerError> {
+        let secret_key = SecretKey::from_bytes(secret).map_err(VoteSignerError::InvalidKey)?;
+        let vote_address = VoteAddress::from(secret_key.sk_to_pk().to_bytes());
+        Ok(Self { secret_key, vote_address })
+    }
+
+    /// Decrypts the EIP-2335 keystore at `path`, the format bsc-geth imports BLS keys from.
+    pub fn from_keystore(path: &Path, password: &str) -> Result<Self, VoteSignerError> {
+        let Keystore { crypto, pubkey } = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        let signer = Self::from_secret_key(&crypto.decrypt(password)?)?;
+        if let Some(pubkey) = pubkey {
+            if decode_hex(&pubkey)? != signer.vote_address.as_slice() {
+                return Err(VoteSignerError::Keystore("public key does not match the secret".into()))
+            }
+        }
+        Ok(signer)
+    }
+
+    /// Returns the BLS public key identifying the validator in the votes.
+    pub fn vote_address(&self) -> VoteAddress {
+        self.vote_address
+    }
+
+    /// Signs the hash of `data`.
+    pub fn sign(&self, data: VoteData) -> VoteEnvelope {
+        let signature = self.secret_key.sign(data.hash().as_slice(), BLST_DST, &[]);
+        VoteEnvelope {
+            vote_address: self.vote_address,
+            signature: VoteSignature::from(signature.to_bytes()),
+            data,
+        }
+    }
+}
+
+/// EIP-2335 keystore, only the fields needed to decrypt it.
+#[derive(Deserialize)]
+struct Keystore {
+    crypto: KeystoreCrypto,
+    pubkey: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct KeystoreCrypto {
+    kdf: KeystoreModule<KdfParams>,
+    checksum: KeystoreModule<serde_json::Value>,
+    cipher: KeystoreModule<CipherParams>,
+}
+
+#[derive(Deserialize)]
+struct KeystoreModule<P> {
+    function: String,
+    params: P,
+    message: String,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum KdfParams {
+    Scrypt { dklen: usize, n: u32, r: u32, p: u32, salt: String },
+    Pbkdf2 { dklen: usize, c: u32, prf: String, salt: String },
+}
+
+#[derive(Deserialize)]
+struct CipherParams {
+    iv: String,
+}
+
+impl KeystoreCrypto {
+    /// Returns the secret held by the keystore.
+    fn decrypt(self, password: &str) -> Result<Vec<u8>, VoteSignerError> {
+        let Self { kdf, checksum, cipher } = self;
+
+        // the password is NFKD normalized and stripped of its control codes
+        let password: String = password.nfkd().filter(|c| !c.is_control()).collect();
+        let key = match (kdf.function.as_str(), kdf.params) {
+            ("scrypt", KdfParams::Scrypt { dklen, n, r, p, salt }) => {
+                if !n.is_power_of_two() {
+                    return Err(VoteSignerError::Keystore(format!("invalid scrypt n {n}")))
+                }
+                let params = scrypt::Params::new(n.ilog2() as u8, r, p, dklen)
+                    .map_err(|err| VoteSignerError::Keystore(err.to_string()))?;
+                let mut key = vec![0u8; dklen];
+                scrypt::scrypt(password.as_bytes(), &decode_hex(&salt)?, &params, &mut key)
+                    .map_err(|err| VoteSignerError::Keystore(err.to_string()))?;
+                key
+            }
+            ("pbkdf2", KdfParams::Pbkdf2 { dklen, c, prf, salt }) if prf == "hmac-sha256" => {
+                let mut key = vec![0u8; dklen];
+                let salt = decode_hex(&salt)?;
+                pbkdf2::pbkdf2_hmac::<Sha256>(password.as_bytes(), &salt, c, &mut key);
+                key
+            }
+            (function, _) => {
+                return Err(VoteSignerError::Keystore(format!("unsupported kdf {function}")))
+            }
+        };
+        if key.len() < 32 {
+            return Err(VoteSignerError::Keystore(format!("derived key too short: {}", key.len())))
+        }
+
+        let mut secret = decode_hex(&cipher.message)?;
+        if checksum.function != "sha256" {
+            return Err(VoteSignerError::Keystore(format!(
+                "unsupported checksum {}",
+                checksum.function
+            )))
+        }
+        let expected = Sha256::new().chain_update(&key[16..32]).chain_update(&secret).finalize();
+        if expected.as_slice() != decode_hex(&checksum.message)? {
+            return Err(VoteSignerError::InvalidPassword)
+        }
+
+        if cipher.function != "aes-128-ctr" {
+            return Err(VoteSignerError::Keystore(format!("unsupported cipher {}", cipher.function)))
+        }
+        let mut aes = Aes128Ctr::new_from_slices(&key[..16], &decode_hex(&cipher.params.iv)?)
+            .map_err(|err| VoteSignerError::Keystore(err.to_string()))?;
+        aes.apply_keystream(&mut secret);
+        Ok(secret)
+    }
+}
+
+fn decode_hex(value: &str) -> Result<Vec<u8>, VoteSignerError> {
+    hex::decode(value).map_err(|err| VoteSignerError::Keystore(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{b256, B256};
+
+    /// PBKDF2 test vector of EIP-2335.
+    const KEYSTORE: &str = r#"{
+        "crypto": {
+            "kdf": {
+                "function": "pbkdf2",
+                "params": {
+                    "dklen": 32,
+                    "c": 262144,
+                    "prf": "hmac-sha256",
+                    "salt": "d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"
+                },
+                "message": ""
+            },
+            "checksum": {
+                "function": "sha256",
+                "params": {},
+                "message": "8a9f5d9912ed7e75ea794bc5a89bca5f193721d30868ade6f73043c6ea6febf1"
+            },
+            "cipher": {
+                "function": "aes-128-ctr",
+                "params": {
+                    "iv": "264daa3f303d7259501c93d997d84fe6"
+                },
+                "message": "cee03fde2af33149775b7223e7845e4fb2c8ae1792e5f99fe9ecf474cc8c16ad"
+            }
+        },
+        "pubkey": "9612d7a727c9d0a22e185a1c768478dfe919cada9266988cb32359c11f2b7b27f4ae4040902382ae2910c15e2b420d07",
+        "path": "m/12381/60/0/0",
+        "uuid": "64625def-3331-4eea-ab6f-782f3ed16a83",
+        "version": 4
+    }"#;
+
+    const PASSWORD: &str = "𝔱𝔢𝔰𝔱𝔭𝔞𝔰𝔰𝔴𝔬𝔯𝔡🔑";
+
+    #[test]
+    fn test_decrypt_eip2335_keystore() {
+        let keystore: Keystore = serde_json::from_str(KEYSTORE).unwrap();
+        let secret = keystore.crypto.decrypt(PASSWORD).unwrap();
+        assert_eq!(
+            B256::from_slice(&secret),
+            b256!("000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f")
+        );
+
+        let keystore: Keystore = serde_json::from_str(KEYSTORE).unwrap();
+        assert!(matches!(keystore.crypto.decrypt("wrong"), Err(VoteSignerError::InvalidPassword)));
+    }
+
+    #[test]
+    fn test_sign_vote() {
+        let path = std::env::temp_dir().join(format!("bls-keystore-{}.json", uuid::Uuid::new_v4()));
+        std::fs::write(&path, KEYSTORE).unwrap();
+        let signer = VoteSigner::from_keystore(&path, PASSWORD).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let data = VoteData { source_number: 1, target_number: 2, ..Default::default() };
+        let vote = signer.sign(data);
+        assert_eq!(vote.vote_address, signer.vote_address());
+        assert!(vote.verify());
+    }
+}
@@ -13,7 +13,7 @@ use crate::{
 };
 use consensus::BscConsensusBuilder;
 use engine::BscPayloadServiceBuilder;
-use evm::BscExecutorBuilder;
+use evm::{config::BscMiningCtx, BscExecutorBuilder};
 use network::BscNetworkBuilder;
 use reth::{
     api::{FullNodeComponents, FullNodeTypes, NodeTypes},
@@ -28,10 +28,12 @@ use reth_trie_db::MerklePatriciaTrie;
 use std::sync::Arc;
 use tokio::sync::{oneshot, Mutex};
 
+pub mod args;
 pub mod consensus;
 pub mod engine;
 pub mod engine_api;
 pub mod evm;
+pub mod miner;
 pub mod network;
 pub mod primitives;
 pub mod storage;
@@ -50,12 +52,29 @@ pub type BscNodeAddOns<N> = RpcAddOns<
 pub struct BscNode {
     engine_handle_rx:
         Arc<Mutex<Option<oneshot::Receiver<BeaconConsensusEngineHandle<BscPayloadTypes>>>>>,
+    /// Mining context, set when the node runs as a validator.
+    mining: Option<BscMiningCtx>,
+    /// Whether to ask the peers not to broadcast transactions to the node.
+    disable_peer_tx_broadcast: bool,
 }
 
 impl BscNode {
     pub fn new() -> (Self, oneshot::Sender<BeaconConsensusEngineHandle<BscPayloadTypes>>) {
         let (tx, rx) = oneshot::channel();
-        (Self { engine_handle_rx: Arc::new(Mutex::new(Some(rx))) }, tx)
+        let engine_handle_rx = Arc::new(Mutex::new(Some(rx)));
+        (Self { engine_handle_rx, mining: None, disable_peer_tx_broadcast: false }, tx)
+    }
+
+    /// Enables block building with the given mining context.
+    pub fn with_mining(mut self, mining: BscMiningCtx) -> Self {
+        self.mining = Some(mining);
+        self
+    }
+
+    /// Asks the peers not to broadcast transactions to the node if `disable` is set.
+    pub const fn with_disable_peer_tx_broadcast(mut self, disable: bool) -> Self {
+        self.disable_peer_tx_broadcast = disable;
+        self
     }
 }
 
@@ -85,12 +104,25 @@ impl BscNode {
             .node_types::<Node>()
             .pool(EthereumPoolBuilder::default())
             .executor(BscExecutorBuilder::default())
-            .payload(BscPayloadServiceBuilder::default())
-            .network(BscNetworkBuilder::new(self.engine_handle_rx.clone()))
+            .payload(self.payload_service_builder())
+            .network(
+                BscNetworkBuilder::new(self.engine_handle_rx.clone())
+                    .with_disable_peer_tx_broadcast(self.disable_peer_tx_broadcast),
+            )
             .consensus(BscConsensusBuilder::default())  
     }
 }
 
+impl BscNode {
+    fn payload_service_builder(&self) -> BscPayloadServiceBuilder {
+        let builder = BscPayloadServiceBuilder::default();
+        match self.mining.clone() {
+            Some(mining) => builder.with_mining(mining),
+            None => builder,
+        }
+    }
+}
+
 impl NodeTypes for BscNode {
     type Primitives = BscPrimitives;
     type ChainSpec = BscChainSpec;
@@ -0,0 +1,65 @@
+//! Error types of the block import.
+
+use crate::node::evm::error::BscBlockExecutionError;
+use reth_network_api::ReputationChangeKind;
+
+/// Reasons a block propagated by a peer is rejected.
+#[derive(thiserror::Error, Debug)]
+pub enum BscImportError {
+    /// The header failed the Parlia checks against the snapshot of its parent.
+    #[error(transparent)]
+    Consensus(#[from] BscBlockExecutionError),
+
+    /// The engine rejected the payload of the block.
+    #[error("invalid payload: {0}")]
+    InvalidPayload(String),
+}
+
+impl BscImportError {
+    /// Returns the reputation change of the peer that sent the block, `None` if an honest peer
+    /// could have sent it.
+    pub const fn reputation_change(&self) -> Option<ReputationChangeKind> {
+        match self {
+            Self::Consensus(err) => match err {
+                // A wrong seal, an over-proposer or a forged attestation can only come from a
+                // faulty or malicious producer
+                BscBlockExecutionError::WrongHeaderSigner { .. } |
+                BscBlockExecutionError::SignerUnauthorized { .. } |
+                BscBlockExecutionError::SignerOverLimit { .. } |
+                BscBlockExecutionError::InvalidDifficulty { .. } |
+                BscBlockExecutionError::ParliaConsensusInnerError { .. } |
+                BscBlockExecutionError::TooLargeAttestationExtraLen { .. } |
+                BscBlockExecutionError::InvalidAttestationTarget { .. } |
+                BscBlockExecutionError::InvalidAttestationSource { .. } |
+                BscBlockExecutionError::InvalidAttestationVoteCount(_) |
+                BscBlockExecutionError::VoteAddrNotFoundInSnap { .. } |
+                BscBlockExecutionError::BLSTInnerError => Some(ReputationChangeKind::BadBlock),
+                _ => None,
+            },
+            Self::InvalidPayload(_) => Some(ReputationChangeKind::BadBlock),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Address, B256};
+
+    #[test]
+    fn test_reputation_change() {
+        let err = BscImportError::from(BscBlockExecutionError::SignerOverLimit {
+            proposer: Address::ZERO,
+        });
+        assert_eq!(err.reputation_change(), Some(ReputationChangeKind::BadBlock));
+
+        let err = BscImportError::from(BscBlockExecutionError::FutureBlock {
+            block_number: 1,
+            hash: B256::ZERO,
+        });
+        assert_eq!(err.reputation_change(), None);
+
+        let err = BscImportError::InvalidPayload("bad state root".into());
+        assert_eq!(err.reputation_change(), Some(ReputationChangeKind::BadBlock));
+    }
+}
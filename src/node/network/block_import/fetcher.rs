@@ -0,0 +1,278 @@
+//! Queue of the blocks that cannot be imported yet and retrieval of the blocks they miss.
+//!
+//! Like the block fetcher of bsc-geth, a block whose parent is unknown is kept until the parent
+//! is imported, and the missing parent, or a block that was only announced by its hash, is
+//! requested from the peer that sent the child or the announcement. Several missing ancestors are
+//! requested at once with `GetBlocksByRange` from peers speaking `bsc/2`. Only blocks close to the
+//! head are queued, blocks further away are left to the pipeline sync.
+use super::service::BlockMsg;
+use crate::{
+    node::network::{bsc_protocol::registry, BscNetworkPrimitives, BscNewBlock},
+    BscBlock,
+};
+use alloy_primitives::{B256, U128};
+use reth_eth_wire::{GetBlockBodies, GetBlockHeaders, HeadersDirection, NewBlock};
+use reth_network::{message::NewBlockMessage, NetworkHandle};
+use reth_network_api::{PeerId, PeerRequest};
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    time::Duration,
+};
+use tokio::sync::oneshot;
+
+/// Maximum number of blocks a queued block can be ahead of the head, same as bsc-geth.
+pub(crate) const MAX_QUEUE_DIST: u64 = 32;
+
+/// Maximum number of blocks a queued block can be behind the head, same as bsc-geth.
+pub(crate) const MAX_UNCLE_DIST: u64 = 11;
+
+/// Maximum number of blocks queued for a single peer, same as bsc-geth.
+pub(crate) const BLOCK_LIMIT: usize = 64;
+
+/// Time to wait for a peer to answer a block request.
+const FETCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Future that requests blocks from a peer, resolves to the hash of the requested block, the peer
+/// and the blocks the peer returned, lowest first.
+pub(crate) type FetchFut = Pin<Box<dyn Future<Output = (B256, PeerId, Vec<BlockMsg>)> + Send>>;
+
+/// Returns whether a block at `number` is close enough to the `head` to be queued or fetched.
+pub(crate) fn within_queue_distance(number: u64, head: u64) -> bool {
+    number.saturating_add(MAX_UNCLE_DIST) >= head && number <= head.saturating_add(MAX_QUEUE_DIST)
+}
+
+/// Blocks waiting for their parent to be imported.
+#[derive(Debug, Default)]
+pub(crate) struct BlockQueue {
+    /// The queued blocks and the peers that sent them, by the hash of their parent.
+    by_parent: HashMap<B256, Vec<(BlockMsg, PeerId)>>,
+    /// Hashes of the queued blocks.
+    queued: HashSet<B256>,
+    /// Number of blocks queued for each peer.
+    per_peer: HashMap<PeerId, usize>,
+}
+
+impl BlockQueue {
+    /// Returns whether the block with the given hash is queued.
+    pub(crate) fn contains(&self, hash: &B256) -> bool {
+        self.queued.contains(hash)
+    }
+
+    /// Queues `block` until its parent is imported.
+    ///
+    /// Returns `false` if the block was dropped because it is already queued, too far from the
+    /// `head`, or the peer already has [`BLOCK_LIMIT`] blocks queued.
+    pub(crate) fn insert(&mut self, block: BlockMsg, peer_id: PeerId, head: u64) -> bool {
+        let header = &block.block.0.block.header;
+        if self.queued.contains(&block.hash) || !within_queue_distance(header.number, head) {
+            return false
+        }
+        let count = self.per_peer.entry(peer_id).or_default();
+        if *count >= BLOCK_LIMIT {
+            return false
+        }
+        *count += 1;
+        self.queued.insert(block.hash);
+        self.by_parent.entry(header.parent_hash).or_default().push((block, peer_id));
+        true
+    }
+
+    /// Removes and returns the blocks waiting for the block with hash `parent`.
+    pub(crate) fn take_children(&mut self, parent: &B256) -> Vec<(BlockMsg, PeerId)> {
+        let children = self.by_parent.remove(parent).unwrap_or_default();
+        for (block, peer_id) in &children {
+            self.forget(&block.hash, peer_id);
+        }
+        children
+    }
+
+    /// Drops the queued blocks that fell more than [`MAX_UNCLE_DIST`] blocks behind the `head`.
+    pub(crate) fn prune(&mut self, head: u64) {
+        let mut dropped = Vec::new();
+        self.by_parent.retain(|_, blocks| {
+            blocks.retain(|(block, peer_id)| {
+                let number = block.block.0.block.header.number;
+                let keep = number.saturating_add(MAX_UNCLE_DIST) >= head;
+                if !keep {
+                    dropped.push((block.hash, *peer_id));
+                }
+                keep
+            });
+            !blocks.is_empty()
+        });
+        for (hash, peer_id) in dropped {
+            self.forget(&hash, &peer_id);
+        }
+    }
+
+    /// Removes the bookkeeping of a block that left the queue.
+    fn forget(&mut self, hash: &B256, peer_id: &PeerId) {
+        self.queued.remove(hash);
+        if let Some(count) = self.per_peer.get_mut(peer_id) {
+            *count -= 1;
+            if *count == 0 {
+                self.per_peer.remove(peer_id);
+            }
+        }
+    }
+}
+
+/// Requests the block with the given hash from `peer_id`.
+///
+/// The block is returned with the total difficulty `td` if known, or with its own difficulty
+/// otherwise, since the total difficulty of a block fetched by hash is not part of the response.
+pub(crate) fn fetch_block(
+    network: NetworkHandle<BscNetworkPrimitives>,
+    peer_id: PeerId,
+    hash: B256,
+    td: Option<U128>,
+) -> FetchFut {
+    Box::pin(async move {
+        let block = tokio::time::timeout(FETCH_TIMEOUT, request_block(&network, peer_id, hash))
+            .await
+            .ok()
+            .flatten()
+            .map(|block| {
+                let td = td.unwrap_or_else(|| U128::saturating_from(block.header.difficulty));
+                let new_block = BscNewBlock(NewBlock { block, td });
+                NewBlockMessage { hash, block: Arc::new(new_block) }
+            });
+        (hash, peer_id, block.into_iter().collect())
+    })
+}
+
+/// Requests the `count` blocks ending at the block with the given hash and number from the `bsc`
+/// connection of `peer_id`, returns `None` if the peer has none.
+///
+/// `td` is the total difficulty of the highest block, the others are derived from it. The blocks
+/// are kept as long as each one is the parent of the previous one.
+pub(crate) fn fetch_range(
+    peer_id: PeerId,
+    hash: B256,
+    number: u64,
+    count: u64,
+    td: U128,
+) -> Option<FetchFut> {
+    let rx = registry::request_blocks_by_range(peer_id, number, hash, count)?;
+    Some(Box::pin(async move {
+        let blocks = tokio::time::timeout(FETCH_TIMEOUT, rx).await.ok().and_then(Result::ok);
+        (hash, peer_id, chain_blocks(hash, td, blocks.unwrap_or_default()))
+    }))
+}
+
+/// Returns the blocks that link down from `hash` by their parent hashes, lowest first, with their
+/// total difficulties derived from `td`, the one of the highest block.
+fn chain_blocks(hash: B256, mut td: U128, blocks: Vec<BscBlock>) -> Vec<BlockMsg> {
+    let mut expected = hash;
+    let mut chain = Vec::with_capacity(blocks.len());
+    for block in blocks {
+        let block_hash = block.header.hash_slow();
+        if block_hash != expected {
+            break
+        }
+        expected = block.header.parent_hash;
+        let difficulty = U128::saturating_from(block.header.difficulty);
+        let new_block = BscNewBlock(NewBlock { block, td });
+        chain.push(NewBlockMessage { hash: block_hash, block: Arc::new(new_block) });
+        td = td.saturating_sub(difficulty);
+    }
+    chain.reverse();
+    chain
+}
+
+/// Requests the header and then the body of a block, checking the header has the expected hash.
+async fn request_block(
+    network: &NetworkHandle<BscNetworkPrimitives>,
+    peer_id: PeerId,
+    hash: B256,
+) -> Option<BscBlock> {
+    let (response, rx) = oneshot::channel();
+    let request = GetBlockHeaders {
+        start_block: hash.into(),
+        limit: 1,
+        skip: 0,
+        direction: HeadersDirection::Rising,
+    };
+    network.send_request(peer_id, PeerRequest::GetBlockHeaders { request, response });
+    let header = rx.await.ok()?.ok()?.0.into_iter().next()?;
+    if header.hash_slow() != hash {
+        return None
+    }
+
+    let (response, rx) = oneshot::channel();
+    let request = GetBlockBodies(vec![hash]);
+    network.send_request(peer_id, PeerRequest::GetBlockBodies { request, response });
+    let body = rx.await.ok()?.ok()?.0.into_iter().next()?;
+
+    Some(BscBlock { header, body })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BscBlockBody;
+    use alloy_consensus::Header;
+
+    fn block(number: u64, parent_hash: B256) -> BlockMsg {
+        let header = Header { number, parent_hash, ..Default::default() };
+        let hash = header.hash_slow();
+        let block = BscBlock { header, body: BscBlockBody::default() };
+        let new_block = BscNewBlock(NewBlock { block, td: U128::from(1) });
+        NewBlockMessage { hash, block: Arc::new(new_block) }
+    }
+
+    #[test]
+    fn test_queue_bounds_and_children() {
+        let mut queue = BlockQueue::default();
+        let peer = PeerId::random();
+        let parent = B256::repeat_byte(1);
+        let child = block(100, parent);
+
+        let head = 100 + MAX_UNCLE_DIST;
+        assert!(!queue.insert(block(head + MAX_QUEUE_DIST + 1, parent), peer, head));
+        assert!(!queue.insert(block(100, parent), peer, head + 1));
+        assert!(queue.insert(child.clone(), peer, head));
+        assert!(!queue.insert(child.clone(), peer, 100));
+        assert!(queue.contains(&child.hash));
+
+        let children = queue.take_children(&parent);
+        assert_eq!(children.len(), 1);
+        assert_eq!(children[0].0.hash, child.hash);
+        assert!(!queue.contains(&child.hash));
+        assert!(queue.per_peer.is_empty());
+    }
+
+    #[test]
+    fn test_chain_blocks() {
+        let first = block(10, B256::ZERO);
+        let second = block(11, first.hash);
+        let unrelated = block(9, B256::repeat_byte(1));
+        let blocks = [&second, &first, &unrelated]
+            .map(|msg| msg.block.0.block.clone())
+            .to_vec();
+
+        let chain = chain_blocks(second.hash, U128::from(5), blocks);
+        assert_eq!(chain.iter().map(|msg| msg.hash).collect::<Vec<_>>(), [first.hash, second.hash]);
+        let orphan = vec![second.block.0.block.clone()];
+        assert!(chain_blocks(first.hash, U128::from(5), orphan).is_empty());
+    }
+
+    #[test]
+    fn test_queue_prune_and_peer_limit() {
+        let mut queue = BlockQueue::default();
+        let peer = PeerId::random();
+        for i in 0..BLOCK_LIMIT as u64 {
+            assert!(queue.insert(block(100 + i % 2, B256::with_last_byte(i as u8)), peer, 100));
+        }
+        assert!(!queue.insert(block(100, B256::repeat_byte(0xff)), peer, 100));
+        assert!(queue.insert(block(100, B256::repeat_byte(0xff)), PeerId::random(), 100));
+
+        queue.prune(101 + MAX_UNCLE_DIST);
+        assert_eq!(queue.queued.len(), BLOCK_LIMIT / 2);
+        assert_eq!(queue.per_peer[&peer], BLOCK_LIMIT / 2);
+        assert!(queue.insert(block(101, B256::repeat_byte(0xfe)), peer, 101));
+    }
+}
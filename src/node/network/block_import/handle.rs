@@ -1,6 +1,7 @@
 use std::task::{Context, Poll};
 
 use reth_engine_primitives::EngineTypes;
+use reth_eth_wire::NewBlockHashes;
 use reth_network::import::BlockImportError;
 use reth_network_api::PeerId;
 use reth_payload_primitives::PayloadTypes;
@@ -13,10 +14,12 @@ use super::service::{BlockMsg, ImportEvent, IncomingBlock, Outcome};
 /// This handle provides a bidirectional communication channel with the
 /// [`super::service::ImportService`]:
 /// - Blocks can be sent to the service for import via [`send_block`](ImportHandle::send_block)
+/// - Announced block hashes can be sent to the service via
+///   [`send_hashes`](ImportHandle::send_hashes)
 /// - Import outcomes can be received via [`poll_outcome`](ImportHandle::poll_outcome)`
 #[derive(Debug)]
 pub struct ImportHandle {
-    /// Send the new block to the service
+    /// Send the new block or announced hashes to the service
     to_import: UnboundedSender<IncomingBlock>,
     /// Receive the event(Announcement/Outcome) of the import
     import_outcome: UnboundedReceiver<ImportEvent>,
@@ -35,7 +38,19 @@ impl ImportHandle {
     /// Returns a [`BlockImportError`] if the channel to the import service is closed.
     pub fn send_block(&self, block: BlockMsg, peer_id: PeerId) -> Result<(), BlockImportError> {
         self.to_import
-            .send((block, peer_id))
+            .send(IncomingBlock::Block(block, peer_id))
+            .map_err(|_| BlockImportError::Other("block import service channel closed".into()))
+    }
+
+    /// Sends the hashes of blocks announced by a peer to the service, which fetches the unknown
+    /// ones. Returns a [`BlockImportError`] if the channel to the import service is closed.
+    pub fn send_hashes(
+        &self,
+        hashes: NewBlockHashes,
+        peer_id: PeerId,
+    ) -> Result<(), BlockImportError> {
+        self.to_import
+            .send(IncomingBlock::Hashes(hashes, peer_id))
             .map_err(|_| BlockImportError::Other("block import service channel closed".into()))
     }
 
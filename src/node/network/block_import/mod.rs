@@ -13,6 +13,8 @@ use std::{
 
 use crate::node::network::BscNewBlock;
 
+pub mod error;
+mod fetcher;
 pub mod handle;
 pub mod service;
 
@@ -29,9 +31,10 @@ impl BscBlockImport {
 
 impl BlockImport<BscNewBlock> for BscBlockImport {
     fn on_new_block(&mut self, peer_id: PeerId, incoming_block: NewBlockEvent<BscNewBlock>) {
-        if let NewBlockEvent::Block(block) = incoming_block {
-            let _ = self.handle.send_block(block, peer_id);
-        }
+        let _ = match incoming_block {
+            NewBlockEvent::Block(block) => self.handle.send_block(block, peer_id),
+            NewBlockEvent::Hashes(hashes) => self.handle.send_hashes(hashes, peer_id),
+        };
     }
 
     fn poll(&mut self, cx: &mut Context<'_>) -> Poll<ImportEvent> {
@@ -1,15 +1,23 @@
-use super::handle::ImportHandle;
+use super::{
+    error::BscImportError,
+    fetcher::{self, BlockQueue, FetchFut},
+    handle::ImportHandle,
+};
 use crate::{
-    consensus::{ParliaConsensus, ParliaConsensusErr, parlia::vote_pool},
-    node::{engine_api::payload::BscPayloadTypes, network::BscNewBlock},
-    BscBlock, BscBlockBody,
+    consensus::{parlia::double_sign, ParliaConsensus, ParliaConsensusErr},
+    node::{
+        engine_api::payload::BscPayloadTypes,
+        network::{block_range::MAX_REQUEST_RANGE_BLOCKS, bsc_protocol::registry, BscNewBlock},
+    },
+    shared, BscBlock, BscBlockBody,
 };
 use alloy_consensus::{BlockBody, Header};
 use alloy_primitives::{B256, U128};
-use alloy_rpc_types::engine::{ForkchoiceState, PayloadStatusEnum};
+use alloy_rpc_types::engine::PayloadStatusEnum;
 use futures::{future::Either, stream::FuturesUnordered, StreamExt};
 use reth::network::cache::LruCache;
 use reth_engine_primitives::{BeaconConsensusEngineHandle, EngineTypes};
+use reth_eth_wire::NewBlockHashes;
 use reth_network::{
     import::{BlockImportError, BlockImportEvent, BlockImportOutcome, BlockValidation},
     message::NewBlockMessage,
@@ -17,10 +25,12 @@ use reth_network::{
 use reth_network_api::PeerId;
 use reth_node_ethereum::EthEngineTypes;
 use reth_payload_primitives::{BuiltPayload, EngineApiMessageVersion, PayloadTypes};
-use reth_primitives::NodePrimitives;
+use reth_network_api::ReputationChangeKind;
+use reth_primitives::{NodePrimitives, SealedHeader};
 use reth_primitives_traits::{AlloyBlockHeader, Block};
 use reth_provider::{BlockHashReader, BlockNumReader};
 use std::{
+    collections::HashSet,
     future::Future,
     pin::Pin,
     sync::Arc,
@@ -37,15 +47,37 @@ pub(crate) type Outcome = BlockImportOutcome<BscNewBlock>;
 /// Import event for a block
 pub(crate) type ImportEvent = BlockImportEvent<BscNewBlock>;
 
-/// Future that processes a block import and returns its outcome
-type ImportFut = Pin<Box<dyn Future<Output = Option<Outcome>> + Send + Sync>>;
+/// Result of a block import task
+enum ImportResult {
+    /// The header of the block passed the consensus checks
+    Verified(BlockMsg, PeerId),
+    /// The header of the block failed the consensus checks, with the reputation change of the
+    /// peer that sent it
+    Rejected(B256, PeerId, Option<ReputationChangeKind>),
+    /// The import finished with an outcome to report to the network
+    Outcome(Outcome),
+    /// The engine could not import the block because its parent is unknown
+    MissingParent(BlockMsg, PeerId),
+}
+
+/// Future that processes a block import and returns its result
+type ImportFut = Pin<Box<dyn Future<Output = Option<ImportResult>> + Send + Sync>>;
 
-/// Channel message type for incoming blocks
-pub(crate) type IncomingBlock = (BlockMsg, PeerId);
+/// Channel message type for incoming blocks and block announcements
+#[derive(Debug)]
+pub(crate) enum IncomingBlock {
+    /// A block propagated by a peer
+    Block(BlockMsg, PeerId),
+    /// Hashes of new blocks announced by a peer
+    Hashes(NewBlockHashes, PeerId),
+}
 
 /// Size of the LRU cache for processed blocks.
 const LRU_PROCESSED_BLOCKS_SIZE: u32 = 100;
 
+/// Size of the LRU cache for blocks that failed the header checks.
+const LRU_BAD_BLOCKS_SIZE: u32 = 128;
+
 /// A service that handles bidirectional block import communication with the network.
 /// It receives new blocks from the network via `from_network` channel and sends back
 /// import outcomes via `to_network` channel.
@@ -65,6 +97,17 @@ where
     pending_imports: FuturesUnordered<ImportFut>,
     /// Cache of processed block hashes to avoid reprocessing the same block.
     processed_blocks: LruCache<B256>,
+    /// Cache of blocks that failed the header checks, peers sending them again are penalised
+    /// without checking them twice.
+    bad_blocks: LruCache<B256>,
+    /// Blocks waiting for their parent to be imported.
+    queue: BlockQueue,
+    /// Pending requests of missing or announced blocks.
+    pending_fetches: FuturesUnordered<FetchFut>,
+    /// Hashes of the blocks being requested.
+    fetching: HashSet<B256>,
+    /// Hashes of the blocks whose header is being checked.
+    verifying: HashSet<B256>,
 }
 
 impl<Provider> ImportService<Provider>
@@ -85,9 +128,56 @@ where
             to_network,
             pending_imports: FuturesUnordered::new(),
             processed_blocks: LruCache::new(LRU_PROCESSED_BLOCKS_SIZE),
+            bad_blocks: LruCache::new(LRU_BAD_BLOCKS_SIZE),
+            queue: BlockQueue::default(),
+            pending_fetches: FuturesUnordered::new(),
+            fetching: HashSet::new(),
+            verifying: HashSet::new(),
         }
     }
 
+    /// Check the header of a block against the consensus rules
+    ///
+    /// The checks verify BLS signatures and may rebuild snapshots, so they run on a blocking
+    /// thread rather than in the poll loop of the service
+    fn verify_header(&self, block: BlockMsg, peer_id: PeerId) -> ImportFut {
+        let consensus = self.consensus.clone();
+        let header = SealedHeader::new(block.block.0.block.header.clone(), block.hash);
+
+        Box::pin(async move {
+            let number = header.number;
+            let verifying = tokio::task::spawn_blocking(move || {
+                let verified = consensus.verify_header(&header);
+                if verified.is_ok() {
+                    // Both blocks of a double sign pass the header checks, the evidence is logged
+                    // and kept
+                    double_sign::check_header(&consensus.parlia, header.header());
+                }
+                verified
+            });
+            // The check panicked, the block is dropped without blaming the peer
+            let Ok(verified) = verifying.await else {
+                return Some(ImportResult::Rejected(block.hash, peer_id, None))
+            };
+
+            match verified {
+                Ok(()) => Some(ImportResult::Verified(block, peer_id)),
+                Err(err) => {
+                    let err = BscImportError::from(err);
+                    tracing::debug!(
+                        target: "net::block_import",
+                        %peer_id,
+                        number,
+                        hash = %block.hash,
+                        %err,
+                        "Rejecting block"
+                    );
+                    Some(ImportResult::Rejected(block.hash, peer_id, err.reputation_change()))
+                }
+            }
+        })
+    }
+
     /// Process a new payload and return the outcome
     fn new_payload(&self, block: BlockMsg, peer_id: PeerId) -> ImportFut {
         let engine = self.engine.clone();
@@ -98,16 +188,23 @@ where
 
             match engine.new_payload(payload).await {
                 Ok(payload_status) => match payload_status.status {
-                    PayloadStatusEnum::Valid => {
-                        Outcome { peer: peer_id, result: Ok(BlockValidation::ValidBlock { block }) }
-                            .into()
-                    }
-                    PayloadStatusEnum::Invalid { validation_error } => Outcome {
+                    PayloadStatusEnum::Valid => Some(ImportResult::Outcome(Outcome {
                         peer: peer_id,
-                        result: Err(BlockImportError::Other(validation_error.into())),
+                        result: Ok(BlockValidation::ValidBlock { block }),
+                    })),
+                    PayloadStatusEnum::Invalid { validation_error } => {
+                        // The network lowers the reputation of the peer on the outcome
+                        registry::record_offence(peer_id);
+                        let err = BscImportError::InvalidPayload(validation_error);
+                        Some(ImportResult::Outcome(Outcome {
+                            peer: peer_id,
+                            result: Err(BlockImportError::Other(Box::new(err))),
+                        }))
+                    }
+                    // The engine buffers blocks it cannot connect to a known parent
+                    PayloadStatusEnum::Syncing | PayloadStatusEnum::Accepted => {
+                        Some(ImportResult::MissingParent(block, peer_id))
                     }
-                    .into(),
-                    _ => None,
                 },
                 Err(err) => None,
             }
@@ -119,33 +216,28 @@ where
         let engine = self.engine.clone();
         let consensus = self.consensus.clone();
         let sealed_block = block.block.0.block.clone().seal();
-        let hash = sealed_block.hash();
-        let number = sealed_block.number();
 
         Box::pin(async move {
-            let (head_block_hash, current_hash) = match consensus.canonical_head(hash, number) {
-                Ok(hash) => hash,
-                Err(_) => return None,
-            };
-
-            let state = ForkchoiceState {
-                head_block_hash,
-                safe_block_hash: head_block_hash,
-                finalized_block_hash: head_block_hash,
-            };
+            let (head_block_hash, current_hash) =
+                match consensus.canonical_head(sealed_block.sealed_header()) {
+                    Ok(hash) => hash,
+                    Err(_) => return None,
+                };
+            let state = consensus.forkchoice_state(sealed_block.sealed_header(), head_block_hash);
 
             match engine.fork_choice_updated(state, None, EngineApiMessageVersion::default()).await
             {
                 Ok(response) => match response.payload_status.status {
-                    PayloadStatusEnum::Valid => {
-                        Outcome { peer: peer_id, result: Ok(BlockValidation::ValidBlock { block }) }
-                            .into()
-                    }
-                    PayloadStatusEnum::Invalid { validation_error } => Outcome {
+                    PayloadStatusEnum::Valid => Some(ImportResult::Outcome(Outcome {
                         peer: peer_id,
-                        result: Err(BlockImportError::Other(validation_error.into())),
+                        result: Ok(BlockValidation::ValidBlock { block }),
+                    })),
+                    PayloadStatusEnum::Invalid { validation_error } => {
+                        Some(ImportResult::Outcome(Outcome {
+                            peer: peer_id,
+                            result: Err(BlockImportError::Other(validation_error.into())),
+                        }))
                     }
-                    .into(),
                     _ => None,
                 },
                 Err(err) => None,
@@ -161,7 +253,30 @@ where
             return;
         }
         
-        if self.processed_blocks.contains(&block.hash) {
+        if self.processed_blocks.contains(&block.hash) ||
+            self.queue.contains(&block.hash) ||
+            self.verifying.contains(&block.hash)
+        {
+            return;
+        }
+        if self.bad_blocks.contains(&block.hash) {
+            registry::penalize_peer(peer_id, ReputationChangeKind::BadBlock);
+            return;
+        }
+
+        self.verifying.insert(block.hash);
+        let verify_fut = self.verify_header(block, peer_id);
+        self.pending_imports.push(verify_fut);
+    }
+
+    /// Import a block whose header passed the consensus checks
+    fn on_verified_block(&mut self, block: BlockMsg, peer_id: PeerId) {
+        self.verifying.remove(&block.hash);
+
+        // A block whose parent is still queued waits for it without bothering the engine
+        let parent_hash = block.block.0.block.header.parent_hash;
+        if self.queue.contains(&parent_hash) {
+            self.enqueue(block, peer_id);
             return;
         }
 
@@ -171,6 +286,104 @@ where
         let fcu_fut = self.update_fork_choice(block, peer_id);
         self.pending_imports.push(fcu_fut);
     }
+
+    /// Queues a block whose parent is unknown and requests the parent, and the ancestors missing
+    /// below it, from the peer that sent it
+    fn enqueue(&mut self, block: BlockMsg, peer_id: PeerId) {
+        let header = &block.block.0.block.header;
+        let (number, parent_hash) = (header.number, header.parent_hash);
+        // The total difficulty of the parent is the one of the child less its difficulty
+        let parent_td = block.block.0.td.saturating_sub(U128::saturating_from(header.difficulty));
+        let head = shared::get_best_block_number().unwrap_or(number);
+
+        if !self.queue.insert(block, peer_id, head) {
+            tracing::trace!(target: "net::block_import", %peer_id, number, "Dropping block");
+            return;
+        }
+        if !self.queue.contains(&parent_hash) {
+            let parent_number = number.saturating_sub(1);
+            let count = parent_number.saturating_sub(head).clamp(1, MAX_REQUEST_RANGE_BLOCKS);
+            if count > 1 &&
+                self.fetch_range(parent_hash, parent_number, count, peer_id, parent_td)
+            {
+                return;
+            }
+            self.fetch(parent_hash, peer_id, Some(parent_td));
+        }
+    }
+
+    /// Requests the blocks announced by a peer that are close to the head and not known yet
+    fn on_new_hashes(&mut self, hashes: NewBlockHashes, peer_id: PeerId) {
+        let Some(head) = shared::get_best_block_number() else { return };
+        for announced in hashes.0 {
+            if fetcher::within_queue_distance(announced.number, head) &&
+                shared::get_header_by_hash(&announced.hash).is_none()
+            {
+                self.fetch(announced.hash, peer_id, None);
+            }
+        }
+    }
+
+    /// Requests the block with the given hash from `peer_id` unless it is already known,
+    /// queued or requested
+    fn fetch(&mut self, hash: B256, peer_id: PeerId, td: Option<U128>) {
+        if self.processed_blocks.contains(&hash) ||
+            self.queue.contains(&hash) ||
+            self.fetching.contains(&hash)
+        {
+            return;
+        }
+        let Some(network) = registry::network() else { return };
+        self.fetching.insert(hash);
+        self.pending_fetches.push(fetcher::fetch_block(network, peer_id, hash, td));
+    }
+
+    /// Requests the `count` blocks ending at the block with the given hash in a single
+    /// `GetBlocksByRange` request, returns `false` if the peer has no `bsc` connection
+    fn fetch_range(
+        &mut self,
+        hash: B256,
+        number: u64,
+        count: u64,
+        peer_id: PeerId,
+        td: U128,
+    ) -> bool {
+        if self.fetching.contains(&hash) {
+            return true;
+        }
+        let Some(fut) = fetcher::fetch_range(peer_id, hash, number, count, td) else {
+            return false;
+        };
+        self.fetching.insert(hash);
+        self.pending_fetches.push(fut);
+        true
+    }
+
+    /// Records a block whose header failed the consensus checks and penalises the peer that sent
+    /// it unless an honest peer could have sent it
+    fn on_rejected_block(
+        &mut self,
+        hash: B256,
+        peer_id: PeerId,
+        reputation: Option<ReputationChangeKind>,
+    ) {
+        self.verifying.remove(&hash);
+        if let Some(kind) = reputation {
+            self.bad_blocks.insert(hash);
+            registry::penalize_peer(peer_id, kind);
+        }
+    }
+
+    /// Records a block the engine accepted and imports the blocks that were waiting for it
+    fn on_valid_block(&mut self, block: &BlockMsg) {
+        self.processed_blocks.insert(block.hash);
+
+        let block_number = block.block.0.block.header.number();
+        self.queue.prune(shared::get_best_block_number().unwrap_or(block_number));
+        for (child, peer_id) in self.queue.take_children(&block.hash) {
+            self.on_new_block(child, peer_id);
+        }
+    }
 }
 
 impl<Provider> Future for ImportService<Provider>
@@ -182,35 +395,75 @@ where
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let this = self.get_mut();
 
-        // Receive new blocks from network
-        while let Poll::Ready(Some((block, peer_id))) = this.from_network.poll_recv(cx) {
-            this.on_new_block(block, peer_id);
-        }
+        loop {
+            // Receive new blocks and announcements from network
+            while let Poll::Ready(Some(incoming)) = this.from_network.poll_recv(cx) {
+                match incoming {
+                    IncomingBlock::Block(block, peer_id) => this.on_new_block(block, peer_id),
+                    IncomingBlock::Hashes(hashes, peer_id) => this.on_new_hashes(hashes, peer_id),
+                }
+            }
 
-        // Process completed imports and send events to network
-        while let Poll::Ready(Some(outcome)) = this.pending_imports.poll_next_unpin(cx) {
-            if let Some(outcome) = outcome {
-                if let Ok(BlockValidation::ValidBlock { block }) = &outcome.result {
-                    this.processed_blocks.insert(block.hash);
-                    
-                    // Prune old votes from the vote pool based on the new block number
-                    let block_number = block.block.0.block.header.number();
-                    vote_pool::prune(block_number);
+            // Import the blocks returned by peers
+            while let Poll::Ready(Some((hash, peer_id, blocks))) =
+                this.pending_fetches.poll_next_unpin(cx)
+            {
+                this.fetching.remove(&hash);
+                if blocks.is_empty() {
+                    tracing::debug!(
+                        target: "net::block_import",
+                        %peer_id,
+                        %hash,
+                        "Failed to fetch block"
+                    );
+                }
+                // Lowest first, so that each block reaches the engine after its parent
+                for block in blocks {
+                    this.on_new_block(block, peer_id);
                 }
+            }
 
-                if let Err(e) = this.to_network.send(BlockImportEvent::Outcome(outcome)) {
-                    return Poll::Ready(Err(Box::new(e)));
+            // Process completed imports and send events to network
+            let mut progressed = false;
+            while let Poll::Ready(Some(result)) = this.pending_imports.poll_next_unpin(cx) {
+                match result {
+                    Some(ImportResult::Outcome(outcome)) => {
+                        if let Ok(BlockValidation::ValidBlock { block }) = &outcome.result {
+                            // Queued children are pushed as new imports, poll them again
+                            progressed = true;
+                            this.on_valid_block(block);
+                        }
+
+                        if let Err(e) = this.to_network.send(BlockImportEvent::Outcome(outcome)) {
+                            return Poll::Ready(Err(Box::new(e)));
+                        }
+                    }
+                    Some(ImportResult::Verified(block, peer_id)) => {
+                        // The imports of the block are pushed, poll them again
+                        progressed = true;
+                        this.on_verified_block(block, peer_id);
+                    }
+                    Some(ImportResult::Rejected(hash, peer_id, reputation)) => {
+                        this.on_rejected_block(hash, peer_id, reputation);
+                    }
+                    Some(ImportResult::MissingParent(block, peer_id)) => {
+                        progressed = true;
+                        this.enqueue(block, peer_id);
+                    }
+                    None => {}
                 }
             }
-        }
 
-        Poll::Pending
+            if !progressed {
+                return Poll::Pending
+            }
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::chainspec::bsc::bsc_mainnet;
+    use crate::chainspec::{bsc::bsc_mainnet, BscChainSpec};
 
     use super::*;
     use alloy_primitives::{B256, U128};
@@ -319,6 +572,43 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn imports_queued_block_after_parent() {
+        let fixture = TestFixture::new(EngineResponses::syncing_first()).await;
+        let parent = create_test_block();
+        let mut child = BscBlock::clone(&parent.block.0.block);
+        child.header.number = 1;
+        child.header.parent_hash = parent.hash;
+        let child_hash = child.header.hash_slow();
+        let child = NewBlockMessage {
+            hash: child_hash,
+            block: Arc::new(BscNewBlock(NewBlock { block: child, td: U128::from(2) })),
+        };
+
+        // The engine does not know the parent of the first block, so it is queued
+        fixture.handle.send_block(child, PeerId::random()).unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+        fixture.handle.send_block(parent, PeerId::random()).unwrap();
+
+        let mut handle = fixture.handle;
+        let imported = tokio::time::timeout(tokio::time::Duration::from_secs(5), async move {
+            while let Some(event) = futures::future::poll_fn(|cx| handle.poll_outcome(cx)).await {
+                if let BlockImportEvent::Outcome(BlockImportOutcome {
+                    result: Ok(BlockValidation::ValidBlock { block }),
+                    ..
+                }) = event
+                {
+                    if block.hash == child_hash {
+                        return true
+                    }
+                }
+            }
+            false
+        })
+        .await;
+        assert_eq!(imported, Ok(true));
+    }
+
     #[derive(Clone)]
     struct MockProvider;
 
@@ -358,17 +648,24 @@ mod tests {
     struct EngineResponses {
         new_payload: PayloadStatusEnum,
         fcu: PayloadStatusEnum,
+        /// Whether the first new payload and forkchoice update are answered with `Syncing`
+        syncing_first: bool,
     }
 
     impl EngineResponses {
         fn both_valid() -> Self {
-            Self { new_payload: PayloadStatusEnum::Valid, fcu: PayloadStatusEnum::Valid }
+            Self {
+                new_payload: PayloadStatusEnum::Valid,
+                fcu: PayloadStatusEnum::Valid,
+                syncing_first: false,
+            }
         }
 
         fn invalid_new_payload() -> Self {
             Self {
                 new_payload: PayloadStatusEnum::Invalid { validation_error: "test error".into() },
                 fcu: PayloadStatusEnum::Valid,
+                syncing_first: false,
             }
         }
 
@@ -376,8 +673,13 @@ mod tests {
             Self {
                 new_payload: PayloadStatusEnum::Valid,
                 fcu: PayloadStatusEnum::Invalid { validation_error: "fcu error".into() },
+                syncing_first: false,
             }
         }
+
+        fn syncing_first() -> Self {
+            Self { syncing_first: true, ..Self::both_valid() }
+        }
     }
 
     /// Test fixture for block import tests
@@ -388,7 +690,8 @@ mod tests {
     impl TestFixture {
         /// Create a new test fixture with the given engine responses
         async fn new(responses: EngineResponses) -> Self {
-            let consensus = Arc::new(ParliaConsensus { provider: MockProvider });
+            let chain_spec = Arc::new(BscChainSpec::from(bsc_mainnet()));
+            let consensus = Arc::new(ParliaConsensus::new(MockProvider, chain_spec));
             let (to_engine, from_engine) = mpsc::unbounded_channel();
             let engine_handle = BeaconConsensusEngineHandle::new(to_engine);
 
@@ -462,11 +765,17 @@ mod tests {
         responses: EngineResponses,
     ) {
         tokio::spawn(Box::pin(async move {
+            let (mut syncing_payload, mut syncing_fcu) =
+                (responses.syncing_first, responses.syncing_first);
             while let Some(message) = from_engine.recv().await {
                 match message {
                     BeaconEngineMessage::NewPayload { payload: _, tx } => {
-                        tx.send(Ok(PayloadStatus::new(responses.new_payload.clone(), None)))
-                            .unwrap();
+                        let status = if std::mem::take(&mut syncing_payload) {
+                            PayloadStatusEnum::Syncing
+                        } else {
+                            responses.new_payload.clone()
+                        };
+                        tx.send(Ok(PayloadStatus::new(status, None))).unwrap();
                     }
                     BeaconEngineMessage::ForkchoiceUpdated {
                         state: _,
@@ -474,11 +783,13 @@ mod tests {
                         version: _,
                         tx,
                     } => {
-                        tx.send(Ok(OnForkChoiceUpdated::valid(PayloadStatus::new(
-                            responses.fcu.clone(),
-                            None,
-                        ))))
-                        .unwrap();
+                        let status = if std::mem::take(&mut syncing_fcu) {
+                            PayloadStatusEnum::Syncing
+                        } else {
+                            responses.fcu.clone()
+                        };
+                        tx.send(Ok(OnForkChoiceUpdated::valid(PayloadStatus::new(status, None))))
+                            .unwrap();
                     }
                     _ => {}
                 }
@@ -0,0 +1,291 @@
+//! `GetBlocksByRange` and `BlocksByRange` messages of `bsc/2`.
+//!
+//! geth-bsc peers use them to fetch the blocks right below a block near the tip, with their
+//! sidecars, in a single round trip. A range is requested by its highest block and the blocks are
+//! returned from that block down, each one being the parent of the previous one.
+use crate::{node::network::bsc_protocol::registry, BscBlock};
+use alloy_primitives::B256;
+use alloy_rlp::{Encodable, RlpDecodable, RlpEncodable};
+use reth_network_api::{PeerId, ReputationChangeKind};
+
+/// Maximum number of blocks that can be requested at once, same as bsc-geth.
+pub const MAX_REQUEST_RANGE_BLOCKS: u64 = 64;
+
+/// Encoded size of the blocks after which a response is cut short.
+pub const SOFT_RESPONSE_LIMIT: usize = 2 * 1024 * 1024;
+
+/// Requests `count` blocks ending at the start block (message id 0x02).
+///
+/// The start block is looked up by hash, or by number if the hash is zero.
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct GetBlocksByRangePacket {
+    /// Id matching the response to the request.
+    pub request_id: u64,
+    /// Number of the highest block of the range.
+    pub start_block_height: u64,
+    /// Hash of the highest block of the range.
+    pub start_block_hash: B256,
+    /// Number of blocks to return.
+    pub count: u64,
+}
+
+/// The blocks replied to a [`GetBlocksByRangePacket`] (message id 0x03).
+///
+/// A block is encoded like geth's `BlockData`, which is the encoding of [`BscBlock`].
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct BlocksByRangePacket {
+    /// Id of the request.
+    pub request_id: u64,
+    /// The blocks, from the start block down.
+    pub blocks: Vec<BscBlock>,
+}
+
+/// Collects the blocks of the requested range, stopping at the first missing block or once the
+/// blocks exceed [`SOFT_RESPONSE_LIMIT`].
+pub fn collect_blocks_by_range(
+    request: &GetBlocksByRangePacket,
+    block_by_hash: impl Fn(&B256) -> Option<BscBlock>,
+    block_by_number: impl Fn(u64) -> Option<BscBlock>,
+) -> Vec<BscBlock> {
+    let start = if request.start_block_hash.is_zero() {
+        block_by_number(request.start_block_height)
+    } else {
+        block_by_hash(&request.start_block_hash)
+    };
+
+    let mut blocks = Vec::new();
+    let mut size = 0;
+    let mut next = start;
+    while let Some(block) = next {
+        size += block.length();
+        let parent_hash = block.header.parent_hash;
+        blocks.push(block);
+        if blocks.len() as u64 >= request.count || size >= SOFT_RESPONSE_LIMIT {
+            break
+        }
+        next = block_by_hash(&parent_hash);
+    }
+    blocks
+}
+
+/// Handles a `GetBlocksByRange` request of a peer, returns the response to send.
+///
+/// The blocks are read from the database, so it is called from a blocking task. Requests for no
+/// blocks or more than [`MAX_REQUEST_RANGE_BLOCKS`] are penalised.
+pub fn handle_get_blocks_by_range(
+    peer_id: PeerId,
+    request: GetBlocksByRangePacket,
+) -> Option<BlocksByRangePacket> {
+    if request.count == 0 || request.count > MAX_REQUEST_RANGE_BLOCKS {
+        tracing::debug!(
+            target: "bsc_protocol",
+            %peer_id,
+            count = request.count,
+            "Invalid block range request"
+        );
+        registry::penalize_peer(peer_id, ReputationChangeKind::BadMessage);
+        return None
+    }
+
+    let blocks = collect_blocks_by_range(
+        &request,
+        crate::shared::get_block_by_hash,
+        crate::shared::get_block_by_number,
+    );
+    tracing::trace!(target: "bsc_protocol", %peer_id, count = blocks.len(), "Serving block range");
+    Some(BlocksByRangePacket { request_id: request.request_id, blocks })
+}
+
+/// Handles the `BlocksByRange` response of a peer, penalising unsolicited responses.
+pub fn handle_blocks_by_range(peer_id: PeerId, response: BlocksByRangePacket) {
+    if !registry::on_blocks_by_range(peer_id, response) {
+        tracing::debug!(target: "bsc_protocol", %peer_id, "Unsolicited block range response");
+        registry::penalize_peer(peer_id, ReputationChangeKind::BadMessage);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BscBlobTransactionSidecar, BscBlockBody};
+    use alloy_consensus::{BlobTransactionSidecar, BlockBody, Header, TxEip4844, TxLegacy};
+    use alloy_eips::{
+        eip4844::{Blob, Bytes48, DATA_GAS_PER_BLOB},
+        eip4895::Withdrawals,
+    };
+    use alloy_primitives::{hex, Address, Bytes, Signature, TxKind, U256};
+    use alloy_rlp::Decodable;
+    use reth_primitives::{Transaction, TransactionSigned};
+    use std::collections::HashMap;
+
+    /// Response of the test in the wire format of geth-bsc, up to the blob of its sidecar.
+    const BLOCKS_BY_RANGE_HEAD: &[u8] = &hex!(
+        "fa02059409fa02058ffa02036ff90235a0666666666666666666666666666666666666666666666666666666"
+        "6666666666a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347940000000000"
+        "000000000000000000000000000000a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5"
+        "e363b421a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a056e81f171bcc"
+        "55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421b90100000000000000000000000000000000"
+        "0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+        "0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+        "0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+        "0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+        "0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+        "000000000000000000000000000000000000000000806580808080a000000000000000000000000000000000"
+        "0000000000000000000000000000000088000000000000000080a056e81f171bcc55a6ff8345e692c0f86e5b"
+        "48e01b996cadc001622fb5e363b4218302000080a00000000000000000000000000000000000000000000000"
+        "000000000000000000f877e407843b9aca008252089422222222222222222222222222222222222222220180"
+        "81930102b85003f84d3808843b9aca00843b9aca008252089433333333333333333333333333333333333333"
+        "338080c001e1a00101010101010101010101010101010101010101010101010101010101010101800102c0c0"
+        "fa0200b8fa0200b4fa02006cfa020004ba020000"
+    );
+
+    /// Rest of the response, after the blob.
+    const BLOCKS_BY_RANGE_TAIL: &[u8] = &hex!(
+        "f1b0444444444444444444444444444444444444444444444444444444444444444444444444444444444444"
+        "444444444444f1b0555555555555555555555555555555555555555555555555555555555555555555555555"
+        "55555555555555555555555565a0777777777777777777777777777777777777777777777777777777777777"
+        "777701a08888888888888888888888888888888888888888888888888888888888888888f90219f901efa000"
+        "00000000000000000000000000000000000000000000000000000000000000a01dcc4de8dec75d7aab85b567"
+        "b6ccd41ad312451b948a7413f0a142fd40d49347940000000000000000000000000000000000000000a056e8"
+        "1f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a056e81f171bcc55a6ff8345e692"
+        "c0f86e5b48e01b996cadc001622fb5e363b421a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc0"
+        "01622fb5e363b421b90100000000000000000000000000000000000000000000000000000000000000000000"
+        "0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+        "0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+        "0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+        "0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+        "0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+        "0000008064808252088080a00000000000000000000000000000000000000000000000000000000000000000"
+        "880000000000000000e5e407843b9aca00825208942222222222222222222222222222222222222222018081"
+        "930102c0"
+    );
+
+    #[test]
+    fn test_blocks_by_range_rlp_matches_reference() {
+        let request = GetBlocksByRangePacket {
+            request_id: 1,
+            start_block_height: 100,
+            start_block_hash: B256::repeat_byte(0x11),
+            count: 8,
+        };
+        // list of 36 bytes: 0x01, 0x64, a 32 bytes string, 0x08
+        let want = [&hex!("e40164a0")[..], &[0x11; 32], &hex!("08")].concat();
+        assert_eq!(alloy_rlp::encode(&request), want);
+        assert_eq!(GetBlocksByRangePacket::decode(&mut &want[..]).unwrap(), request);
+
+        let response = BlocksByRangePacket { request_id: 1, blocks: Vec::new() };
+        assert_eq!(alloy_rlp::encode(&response), hex!("c201c0"));
+
+        let signature = Signature::new(U256::from(1), U256::from(2), false);
+        let transfer = TransactionSigned::new_unhashed(
+            Transaction::Legacy(TxLegacy {
+                chain_id: Some(56),
+                nonce: 7,
+                gas_price: 1_000_000_000,
+                gas_limit: 21_000,
+                to: TxKind::Call(Address::repeat_byte(0x22)),
+                value: U256::from(1),
+                input: Bytes::new(),
+            }),
+            signature,
+        );
+        let blob_tx = TransactionSigned::new_unhashed(
+            Transaction::Eip4844(TxEip4844 {
+                chain_id: 56,
+                nonce: 8,
+                gas_limit: 21_000,
+                max_fee_per_gas: 1_000_000_000,
+                max_priority_fee_per_gas: 1_000_000_000,
+                to: Address::repeat_byte(0x33),
+                blob_versioned_hashes: vec![B256::repeat_byte(0x01)],
+                max_fee_per_blob_gas: 1,
+                ..Default::default()
+            }),
+            signature,
+        );
+
+        // a block before Cancun with a transaction, and a block carrying a blob, the hashes
+        // linking them are arbitrary
+        let parent = BscBlock {
+            header: Header { number: 100, gas_used: 21_000, ..Default::default() },
+            body: BscBlockBody {
+                inner: BlockBody {
+                    transactions: vec![transfer.clone()],
+                    ommers: vec![],
+                    withdrawals: None,
+                },
+                sidecars: None,
+            },
+        };
+        let header = Header {
+            number: 101,
+            parent_hash: B256::repeat_byte(0x66),
+            base_fee_per_gas: Some(0),
+            withdrawals_root: Some(alloy_consensus::constants::EMPTY_WITHDRAWALS),
+            blob_gas_used: Some(DATA_GAS_PER_BLOB),
+            excess_blob_gas: Some(0),
+            parent_beacon_block_root: Some(B256::ZERO),
+            ..Default::default()
+        };
+        let mut blob = Blob::ZERO;
+        blob[0] = 0x01;
+        let sidecar = BscBlobTransactionSidecar {
+            inner: BlobTransactionSidecar {
+                blobs: vec![blob],
+                commitments: vec![Bytes48::repeat_byte(0x44)],
+                proofs: vec![Bytes48::repeat_byte(0x55)],
+            },
+            block_number: 101,
+            block_hash: B256::repeat_byte(0x77),
+            tx_index: 1,
+            tx_hash: B256::repeat_byte(0x88),
+        };
+        let child = BscBlock {
+            header,
+            body: BscBlockBody {
+                inner: BlockBody {
+                    transactions: vec![transfer, blob_tx],
+                    ommers: vec![],
+                    withdrawals: Some(Withdrawals::default()),
+                },
+                sidecars: Some(vec![sidecar]),
+            },
+        };
+        let response = BlocksByRangePacket { request_id: 9, blocks: vec![child, parent] };
+
+        let want = [BLOCKS_BY_RANGE_HEAD, &blob[..], BLOCKS_BY_RANGE_TAIL].concat();
+        assert_eq!(alloy_rlp::encode(&response), want);
+        assert_eq!(BlocksByRangePacket::decode(&mut &want[..]).unwrap(), response);
+    }
+
+    #[test]
+    fn test_collect_blocks_by_range() {
+        let mut blocks = HashMap::new();
+        let mut parent_hash = B256::ZERO;
+        let mut hashes = Vec::new();
+        for number in 0..5 {
+            let header = Header { number, parent_hash, ..Default::default() };
+            parent_hash = header.hash_slow();
+            hashes.push(parent_hash);
+            blocks.insert(parent_hash, BscBlock { header, body: BscBlockBody::default() });
+        }
+        let by_hash = |hash: &B256| blocks.get(hash).cloned();
+        let by_number =
+            |number: u64| blocks.values().find(|block| block.header.number == number).cloned();
+
+        let request = GetBlocksByRangePacket {
+            request_id: 7,
+            start_block_height: 4,
+            start_block_hash: hashes[4],
+            count: 3,
+        };
+        let range = collect_blocks_by_range(&request, by_hash, by_number);
+        let numbers = range.iter().map(|block| block.header.number).collect::<Vec<_>>();
+        assert_eq!(numbers, [4, 3, 2]);
+
+        // the range stops at the genesis block and the start can be looked up by number
+        let request =
+            GetBlocksByRangePacket { start_block_hash: B256::ZERO, count: 64, ..request };
+        assert_eq!(collect_blocks_by_range(&request, by_hash, by_number).len(), 5);
+    }
+}
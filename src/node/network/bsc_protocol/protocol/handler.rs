@@ -5,30 +5,42 @@ use std::net::SocketAddr;
 use tokio::sync::mpsc;
 
 use super::proto::{BscProtoMessage};
-use crate::node::network::bsc_protocol::stream::{BscProtocolConnection};
+use crate::node::network::bsc_protocol::{registry, stream::BscProtocolConnection};
 
-#[derive(Clone, Debug, Default)]
-pub struct BscProtocolHandler;
+/// Handler of one `bsc` protocol version, one is registered per version in
+/// [`BSC_PROTOCOL_VERSIONS`](super::proto::BSC_PROTOCOL_VERSIONS) and only the highest version
+/// shared with a peer gets a connection.
+#[derive(Clone, Debug)]
+pub struct BscProtocolHandler {
+    version: u64,
+}
+
+impl BscProtocolHandler {
+    /// Creates the handler of the given `bsc` protocol version.
+    pub const fn new(version: u64) -> Self { Self { version } }
+}
 
 #[derive(Clone, Debug)]
-pub struct BscConnectionHandler;
+pub struct BscConnectionHandler {
+    version: u64,
+}
 
 impl ProtocolHandler for BscProtocolHandler {
     type ConnectionHandler = BscConnectionHandler;
 
     fn on_incoming(&self, _socket_addr: SocketAddr) -> Option<Self::ConnectionHandler> {
-        Some(BscConnectionHandler)
+        Some(BscConnectionHandler { version: self.version })
     }
 
     fn on_outgoing(&self, _socket_addr: SocketAddr, _peer_id: PeerId) -> Option<Self::ConnectionHandler> {
-        Some(BscConnectionHandler)
+        Some(BscConnectionHandler { version: self.version })
     }
 }
 
 impl ConnectionHandler for BscConnectionHandler {
     type Connection = BscProtocolConnection;
 
-    fn protocol(&self) -> Protocol { BscProtoMessage::protocol() }
+    fn protocol(&self) -> Protocol { BscProtoMessage::protocol(self.version) }
 
     fn on_unsupported_by_peer(
         self,
@@ -42,11 +54,12 @@ impl ConnectionHandler for BscConnectionHandler {
     fn into_connection(
         self,
         direction: Direction,
-        _peer_id: PeerId,
+        peer_id: PeerId,
         conn: ProtocolConnection,
     ) -> Self::Connection {
-        let (_tx, rx) = mpsc::unbounded_channel();
-        BscProtocolConnection::new(conn, rx, direction.is_outgoing())
+        let (tx, rx) = mpsc::unbounded_channel();
+        registry::register_peer(peer_id, self.version, tx.clone());
+        BscProtocolConnection::new(conn, rx, tx, peer_id, direction.is_outgoing(), self.version)
     }
 }
 
@@ -1,38 +1,56 @@
 use reth_eth_wire::{protocol::Protocol, Capability};
 
-/// BSC protocol version we support
-pub const BSC_PROTOCOL_VERSION: u64 = 1;
+/// `bsc/1` protocol version, carrying the votes
+pub const BSC_PROTOCOL_VERSION_1: u64 = 1;
+
+/// `bsc/2` protocol version, adding the block range messages to `bsc/1`
+pub const BSC_PROTOCOL_VERSION_2: u64 = 2;
+
+/// BSC protocol versions we support, the highest one shared with a peer is used
+pub const BSC_PROTOCOL_VERSIONS: [u64; 2] = [BSC_PROTOCOL_VERSION_2, BSC_PROTOCOL_VERSION_1];
 
 /// BSC protocol name
 pub const BSC_PROTOCOL_NAME: &str = "bsc";
 
-/// Number of message types supported (0x00, 0x01)
-pub const BSC_MESSAGE_COUNT: u8 = 2;
+/// Number of message types of `bsc/1` (0x00 to 0x01)
+pub const BSC_1_MESSAGE_COUNT: u8 = 2;
+
+/// Number of message types of `bsc/2` (0x00 to 0x03)
+pub const BSC_2_MESSAGE_COUNT: u8 = 4;
 
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum BscProtoMessageId {
     Capability = 0x00,
     Votes = 0x01,
-    // TODO: Add these back in when we have a use case for them
-    // GetBlocksByRange = 0x02,
-    // BlocksByRange = 0x03,
+    /// Requests the blocks ending at a start block, walking back through the parents
+    GetBlocksByRange = 0x02,
+    /// The blocks replied to a `GetBlocksByRange` request
+    BlocksByRange = 0x03,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct BscProtoMessage;
 
 impl BscProtoMessage {
-    /// Returns the capability for the `bsc` protocol version 1.
-    pub fn capability() -> Capability { Capability::new_static(BSC_PROTOCOL_NAME, BSC_PROTOCOL_VERSION as usize) }
+    /// Returns the capability for the given `bsc` protocol version.
+    pub fn capability(version: u64) -> Capability { Capability::new_static(BSC_PROTOCOL_NAME, version as usize) }
 
-    /// Returns the protocol for the `bsc` protocol.
-    pub fn protocol() -> Protocol { Protocol::new(Self::capability(), BSC_MESSAGE_COUNT) }
+    /// Returns the protocol for the given `bsc` protocol version.
+    pub fn protocol(version: u64) -> Protocol { Protocol::new(Self::capability(version), Self::message_count(version)) }
+
+    /// Returns the number of message types of the given `bsc` protocol version.
+    pub const fn message_count(version: u64) -> u8 {
+        if version >= BSC_PROTOCOL_VERSION_2 { BSC_2_MESSAGE_COUNT } else { BSC_1_MESSAGE_COUNT }
+    }
+
+    /// Returns whether the given `bsc` protocol version carries the block range messages.
+    pub const fn supports_block_ranges(version: u64) -> bool { version >= BSC_PROTOCOL_VERSION_2 }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::BscProtoMessageId;
+    use super::{BscProtoMessage, BscProtoMessageId, BSC_PROTOCOL_VERSION_1, BSC_PROTOCOL_VERSION_2};
     use alloy_primitives::{hex, B256, FixedBytes};
     use alloy_rlp::{RlpEncodable, RlpDecodable};
     use crate::consensus::parlia::vote::{VoteData, VoteEnvelope};
@@ -57,6 +75,19 @@ mod tests {
         FixedBytes::from_slice(&v)
     }
 
+    #[test]
+    fn test_block_range_messages_only_in_bsc2() {
+        let bsc1 = BscProtoMessage::protocol(BSC_PROTOCOL_VERSION_1);
+        assert_eq!(bsc1.cap.version, 1);
+        assert_eq!(bsc1.messages(), BscProtoMessageId::Votes as u8 + 1);
+        assert!(!BscProtoMessage::supports_block_ranges(BSC_PROTOCOL_VERSION_1));
+
+        let bsc2 = BscProtoMessage::protocol(BSC_PROTOCOL_VERSION_2);
+        assert_eq!(bsc2.cap.version, 2);
+        assert_eq!(bsc2.messages(), BscProtoMessageId::BlocksByRange as u8 + 1);
+        assert!(BscProtoMessage::supports_block_ranges(BSC_PROTOCOL_VERSION_2));
+    }
+
     #[test]
     fn test_bsc1_votes_messages_rlp_matches_reference() {
         let bls_pub = "b32d4d46a7127dcc865f0d30f2ee3dcd5983b686f4e3a9202afc8b608652001c9938906ae1ff1417486096e32511f1bc";
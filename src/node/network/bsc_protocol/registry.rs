@@ -0,0 +1,342 @@
+//! Registry of the open `bsc` protocol connections.
+//!
+//! Every connection registers the sender of its command channel so other components, like the
+//! vote manager, can push messages to the peers. The votes each peer is known to have are
+//! tracked so votes are relayed at most once per peer and never echoed back to their sender.
+//! Votes of other validators are relayed to a square root of the peers only, like geth does with
+//! the transactions, and the other peers get them from those.
+//! Block range requests sent to the peers are tracked until the peer responds.
+//! Misbehaving peers are reported to the network once its handle is registered, and the handle is
+//! shared with the components that send requests to the peers. Peers that keep sending invalid
+//! blocks or votes are banned.
+use super::{protocol::proto::BscProtoMessage, stream::BscCommand};
+use crate::{
+    consensus::parlia::vote::VoteEnvelope,
+    node::network::{
+        block_range::{BlocksByRangePacket, GetBlocksByRangePacket},
+        BscNetworkPrimitives,
+    },
+    BscBlock,
+};
+use alloy_primitives::B256;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use rand::seq::SliceRandom;
+use reth_network::NetworkHandle;
+use reth_network_api::{PeerId, Peers, ReputationChangeKind};
+use schnellru::{ByLength, LruMap};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        OnceLock,
+    },
+};
+use tokio::sync::{mpsc::UnboundedSender, oneshot};
+
+/// Maximum number of vote hashes remembered per peer, same as bsc-geth.
+const MAX_KNOWN_VOTES: u32 = 7680;
+
+/// Number of offences after which a peer is banned.
+const MAX_OFFENCES: u32 = 3;
+
+/// Maximum number of peers whose offences are remembered.
+const MAX_OFFENDERS: u32 = 1024;
+
+/// Reputation change that bans a peer, the banned reputation of the peers manager.
+const BAN_REPUTATION: i32 = -51_200;
+
+/// A connected `bsc` peer.
+struct BscPeer {
+    /// The negotiated `bsc` protocol version.
+    version: u64,
+    /// Sender of the command channel of the connection.
+    commands: UnboundedSender<BscCommand>,
+    /// Hashes of the votes the peer sent us or we sent to it.
+    known_votes: LruMap<B256, (), ByLength>,
+}
+
+impl BscPeer {
+    /// Creates a peer speaking the given `bsc` protocol version that knows no vote yet.
+    fn new(version: u64, commands: UnboundedSender<BscCommand>) -> Self {
+        Self { version, commands, known_votes: LruMap::new(ByLength::new(MAX_KNOWN_VOTES)) }
+    }
+}
+
+/// The connected `bsc` peers.
+static BSC_PEERS: Lazy<Mutex<HashMap<PeerId, BscPeer>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// A block range request waiting for the response of a peer.
+struct PendingRange {
+    /// The peer the request was sent to.
+    peer_id: PeerId,
+    /// Sender of the blocks of the response.
+    response: oneshot::Sender<Vec<BscBlock>>,
+}
+
+/// Block range requests by request id.
+static PENDING_RANGES: Lazy<Mutex<HashMap<u64, PendingRange>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Id of the next block range request.
+static NEXT_REQUEST_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Number of offences of the peers that sent invalid messages.
+static OFFENCES: Lazy<Mutex<LruMap<PeerId, u32, ByLength>>> =
+    Lazy::new(|| Mutex::new(LruMap::new(ByLength::new(MAX_OFFENDERS))));
+
+/// Handle of the network, used to change the reputation of the peers and to request blocks.
+static NETWORK: OnceLock<NetworkHandle<BscNetworkPrimitives>> = OnceLock::new();
+
+/// Registers the handle of the started network.
+pub fn set_network(network: NetworkHandle<BscNetworkPrimitives>) {
+    let _ = NETWORK.set(network);
+}
+
+/// Returns the handle of the network, if it was started.
+pub fn network() -> Option<NetworkHandle<BscNetworkPrimitives>> {
+    NETWORK.get().cloned()
+}
+
+/// Applies a reputation change to a peer that sent an invalid message and records the offence.
+pub fn penalize_peer(peer_id: PeerId, kind: ReputationChangeKind) {
+    if let Some(network) = NETWORK.get() {
+        network.reputation_change(peer_id, kind);
+    }
+    record_offence(peer_id);
+}
+
+/// Records an offence of a peer whose reputation was already lowered, banning the peer once it
+/// reached [`MAX_OFFENCES`]. Returns whether the peer got banned.
+pub fn record_offence(peer_id: PeerId) -> bool {
+    let offences = {
+        let mut offenders = OFFENCES.lock();
+        let Some(offences) = offenders.get_or_insert(peer_id, || 0) else { return false };
+        *offences += 1;
+        *offences
+    };
+    if offences < MAX_OFFENCES {
+        return false
+    }
+
+    OFFENCES.lock().remove(&peer_id);
+    tracing::debug!(target: "bsc_protocol", %peer_id, offences, "Banning misbehaving peer");
+    if let Some(network) = NETWORK.get() {
+        network.reputation_change(peer_id, ReputationChangeKind::Other(BAN_REPUTATION));
+    }
+    true
+}
+
+/// Registers the command sender of a new connection speaking the given `bsc` protocol version,
+/// replacing the one of a previous session.
+pub fn register_peer(peer_id: PeerId, version: u64, commands: UnboundedSender<BscCommand>) {
+    BSC_PEERS.lock().insert(peer_id, BscPeer::new(version, commands));
+}
+
+/// Records that `peer_id` has the vote with the given hash, so it is not sent back.
+pub fn mark_vote_known(peer_id: PeerId, vote_hash: B256) {
+    if let Some(peer) = BSC_PEERS.lock().get_mut(&peer_id) {
+        peer.known_votes.insert(vote_hash, ());
+    }
+}
+
+/// Requests `count` blocks ending at the block `start_hash` at `start_height` from a peer.
+///
+/// Returns the receiver of the blocks, from the start block down, or `None` if the peer has no
+/// open `bsc/2` connection. Requests whose receiver was dropped are forgotten.
+pub fn request_blocks_by_range(
+    peer_id: PeerId,
+    start_height: u64,
+    start_hash: B256,
+    count: u64,
+) -> Option<oneshot::Receiver<Vec<BscBlock>>> {
+    let request_id = NEXT_REQUEST_ID.fetch_add(1, Ordering::Relaxed);
+    let request = GetBlocksByRangePacket {
+        request_id,
+        start_block_height: start_height,
+        start_block_hash: start_hash,
+        count,
+    };
+    let sent = BSC_PEERS.lock().get(&peer_id).is_some_and(|peer| {
+        BscProtoMessage::supports_block_ranges(peer.version) &&
+            peer.commands.send(BscCommand::GetBlocksByRange(request)).is_ok()
+    });
+    if !sent {
+        return None
+    }
+
+    let (response, rx) = oneshot::channel();
+    let mut pending = PENDING_RANGES.lock();
+    pending.retain(|_, request| !request.response.is_closed());
+    pending.insert(request_id, PendingRange { peer_id, response });
+    Some(rx)
+}
+
+/// Delivers the blocks of a `BlocksByRange` response to the request it answers.
+///
+/// Returns `false` if no request with the id of the response was sent to the peer.
+pub fn on_blocks_by_range(peer_id: PeerId, response: BlocksByRangePacket) -> bool {
+    let mut pending = PENDING_RANGES.lock();
+    if pending.get(&response.request_id).is_none_or(|request| request.peer_id != peer_id) {
+        return false
+    }
+    if let Some(request) = pending.remove(&response.request_id) {
+        let _ = request.response.send(response.blocks);
+    }
+    true
+}
+
+/// Sends `vote` to the connected peers that don't know it yet, returns the number of peers the
+/// vote was sent to.
+///
+/// Our own votes, without `origin`, go to every such peer. The votes relayed from `origin` go to a
+/// random square root of them, never back to `origin`. Peers whose connection is closed are
+/// dropped from the registry.
+pub fn relay_vote(vote: &VoteEnvelope, origin: Option<PeerId>) -> usize {
+    relay_vote_to(&mut BSC_PEERS.lock(), vote, origin)
+}
+
+/// Sends `vote` to the given peers as described in [`relay_vote`].
+fn relay_vote_to(
+    peers: &mut HashMap<PeerId, BscPeer>,
+    vote: &VoteEnvelope,
+    origin: Option<PeerId>,
+) -> usize {
+    let vote_hash = vote.hash();
+    peers.retain(|_, peer| !peer.commands.is_closed());
+    let mut targets = peers
+        .iter()
+        .filter(|(peer_id, peer)| {
+            Some(**peer_id) != origin && peer.known_votes.peek(&vote_hash).is_none()
+        })
+        .map(|(peer_id, _)| *peer_id)
+        .collect::<Vec<_>>();
+    if origin.is_some() {
+        targets.shuffle(&mut rand::rng());
+        targets.truncate(targets.len().isqrt());
+    }
+
+    let mut sent = 0;
+    for peer_id in targets {
+        let Some(peer) = peers.get_mut(&peer_id) else { continue };
+        peer.known_votes.insert(vote_hash, ());
+        if peer.commands.send(BscCommand::SendVotes(vec![vote.clone()])).is_ok() {
+            sent += 1;
+        } else {
+            peers.remove(&peer_id);
+        }
+    }
+    sent
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        consensus::parlia::vote::VoteData,
+        node::network::bsc_protocol::protocol::proto::{
+            BSC_PROTOCOL_VERSION_1, BSC_PROTOCOL_VERSION_2,
+        },
+    };
+    use tokio::sync::mpsc;
+
+    #[test]
+    fn test_relay_vote_once_per_peer() {
+        let vote = VoteEnvelope {
+            vote_address: Default::default(),
+            signature: Default::default(),
+            data: VoteData { target_number: 42, ..Default::default() },
+        };
+        let mut peers = HashMap::new();
+        let known = PeerId::random();
+        let other = PeerId::random();
+        let closed = PeerId::random();
+        let (tx, mut known_rx) = mpsc::unbounded_channel();
+        peers.insert(known, BscPeer::new(BSC_PROTOCOL_VERSION_2, tx));
+        let (tx, mut other_rx) = mpsc::unbounded_channel();
+        peers.insert(other, BscPeer::new(BSC_PROTOCOL_VERSION_1, tx));
+        let (tx, _) = mpsc::unbounded_channel();
+        peers.insert(closed, BscPeer::new(BSC_PROTOCOL_VERSION_2, tx));
+
+        peers.get_mut(&known).unwrap().known_votes.insert(vote.hash(), ());
+        assert_eq!(relay_vote_to(&mut peers, &vote, None), 1);
+        assert!(known_rx.try_recv().is_err());
+        assert!(matches!(
+            other_rx.try_recv(),
+            Ok(BscCommand::SendVotes(votes)) if votes == [vote.clone()]
+        ));
+        assert!(!peers.contains_key(&closed));
+
+        assert_eq!(relay_vote_to(&mut peers, &vote, None), 0);
+        assert!(other_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_relay_vote_to_square_root_of_peers() {
+        let vote = VoteEnvelope {
+            vote_address: Default::default(),
+            signature: Default::default(),
+            data: VoteData { target_number: 42, ..Default::default() },
+        };
+        let mut peers = HashMap::new();
+        let mut receivers = HashMap::new();
+        for _ in 0..17 {
+            let peer_id = PeerId::random();
+            let (tx, rx) = mpsc::unbounded_channel();
+            peers.insert(peer_id, BscPeer::new(BSC_PROTOCOL_VERSION_2, tx));
+            receivers.insert(peer_id, rx);
+        }
+        let origin = *peers.keys().next().unwrap();
+
+        // the 16 peers other than the origin get 4 copies, never the origin
+        assert_eq!(relay_vote_to(&mut peers, &vote, Some(origin)), 4);
+        assert!(receivers.get_mut(&origin).unwrap().try_recv().is_err());
+        let received = receivers.values_mut().filter(|rx| rx.try_recv().is_ok()).count();
+        assert_eq!(received, 4);
+
+        // the remaining 12 peers get the next copies
+        assert_eq!(relay_vote_to(&mut peers, &vote, Some(origin)), 3);
+        assert!(receivers.get_mut(&origin).unwrap().try_recv().is_err());
+    }
+
+    #[test]
+    fn test_blocks_by_range_request_and_response() {
+        let peer = PeerId::random();
+        let (tx, mut commands) = mpsc::unbounded_channel();
+        register_peer(peer, BSC_PROTOCOL_VERSION_2, tx);
+        let legacy = PeerId::random();
+        let (tx, mut legacy_commands) = mpsc::unbounded_channel();
+        register_peer(legacy, BSC_PROTOCOL_VERSION_1, tx);
+
+        // `bsc/1` peers don't know the block range messages
+        assert!(request_blocks_by_range(legacy, 10, B256::ZERO, 2).is_none());
+        assert!(legacy_commands.try_recv().is_err());
+
+        assert!(request_blocks_by_range(PeerId::random(), 10, B256::ZERO, 2).is_none());
+        let mut rx = request_blocks_by_range(peer, 10, B256::ZERO, 2).unwrap();
+        let Ok(BscCommand::GetBlocksByRange(request)) = commands.try_recv() else {
+            panic!("block range request not sent")
+        };
+        assert_eq!((request.start_block_height, request.count), (10, 2));
+
+        // a response of another peer or to another request is unsolicited
+        let response = BlocksByRangePacket { request_id: request.request_id, blocks: vec![] };
+        assert!(!on_blocks_by_range(PeerId::random(), response.clone()));
+        let other = BlocksByRangePacket { request_id: request.request_id + 1000, blocks: vec![] };
+        assert!(!on_blocks_by_range(peer, other));
+
+        assert!(on_blocks_by_range(peer, response.clone()));
+        assert_eq!(rx.try_recv(), Ok(vec![]));
+        assert!(!on_blocks_by_range(peer, response));
+    }
+
+    #[test]
+    fn test_repeated_offences_ban_peer() {
+        let peer = PeerId::random();
+        for _ in 1..MAX_OFFENCES {
+            assert!(!record_offence(peer));
+        }
+        assert!(record_offence(peer));
+        assert!(OFFENCES.lock().peek(&peer).is_none());
+    }
+}
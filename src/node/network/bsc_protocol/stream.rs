@@ -1,10 +1,15 @@
 use alloy_primitives::bytes::BytesMut;
 use alloy_rlp::{Decodable, Encodable};
+use bytes::BufMut;
 use futures::{Stream, StreamExt};
-use std::{pin::Pin, task::{Context, Poll, ready}};
+use std::{pin::Pin, sync::Arc, task::{Context, Poll, ready}};
 use reth_eth_wire::multiplex::ProtocolConnection;
+use reth_network_api::{PeerId, ReputationChangeKind};
 use bytes::Bytes;
-use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::sync::{
+    mpsc::{UnboundedReceiver, UnboundedSender},
+    Semaphore,
+};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use tokio::time::{Duration, Sleep};
 use futures::Future;
@@ -12,8 +17,17 @@ use futures::Future;
 /// Handshake timeout, mirroring the Go implementation.
 const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
 
-use crate::node::network::votes::{VotesPacket, BscCapPacket, handle_votes_broadcast};
-use super::protocol::proto::{BscProtoMessageId, BSC_PROTOCOL_VERSION};
+/// Maximum number of block range requests of a peer served at once, later ones are dropped.
+const MAX_SERVED_RANGE_REQUESTS: usize = 4;
+
+use crate::node::network::{
+    block_range::{
+        handle_blocks_by_range, handle_get_blocks_by_range, BlocksByRangePacket,
+        GetBlocksByRangePacket,
+    },
+    votes::{VotesPacket, BscCapPacket, handle_votes_broadcast},
+};
+use super::{protocol::proto::{BscProtoMessage, BscProtoMessageId}, registry};
 
 /// Commands that can be sent to the BSC connection.
 #[allow(dead_code)]
@@ -21,35 +35,55 @@ use super::protocol::proto::{BscProtoMessageId, BSC_PROTOCOL_VERSION};
 pub enum BscCommand {
     SendCapability { protocol_version: u64, extra: Bytes },
     SendVotes(Vec<crate::consensus::parlia::vote::VoteEnvelope>),
+    GetBlocksByRange(GetBlocksByRangePacket),
+    SendBlocksByRange(BlocksByRangePacket),
 }
 
 /// Stream that handles incoming BSC protocol messages and returns outgoing messages to send.
 pub struct BscProtocolConnection {
     conn: ProtocolConnection,
     commands: UnboundedReceiverStream<BscCommand>,
+    /// Sender of the command channel, used to send the responses served off the network task.
+    responses: UnboundedSender<BscCommand>,
+    /// Permits of the block range requests being served.
+    served_ranges: Arc<Semaphore>,
+    peer_id: PeerId,
     handshake_deadline: Option<std::pin::Pin<Box<Sleep>>>,
     handshake_completed: bool,
     is_dialer: bool,
     initial_capability: Option<BscCommand>,
+    /// The negotiated `bsc` protocol version.
+    version: u64,
 }
 
 impl BscProtocolConnection {
-    pub fn new(conn: ProtocolConnection, commands: UnboundedReceiver<BscCommand>, is_dialer: bool) -> Self {
+    pub fn new(
+        conn: ProtocolConnection,
+        commands: UnboundedReceiver<BscCommand>,
+        responses: UnboundedSender<BscCommand>,
+        peer_id: PeerId,
+        is_dialer: bool,
+        version: u64,
+    ) -> Self {
         let handshake_deadline = Some(Box::pin(tokio::time::sleep(HANDSHAKE_TIMEOUT)));
         // Both sides should send initial capability in BSC protocol
         // BSC sends []byte{00} which in RLP is encoded as a single byte 0x00
         let initial_capability = Some(BscCommand::SendCapability { 
-            protocol_version: BSC_PROTOCOL_VERSION, 
+            protocol_version: version,
             extra: Bytes::from_static(&[0x00u8]) // Raw RLP: single 0x00 byte represents []byte{00}
         });
         
         Self { 
             conn, 
             commands: UnboundedReceiverStream::new(commands), 
+            responses,
+            served_ranges: Arc::new(Semaphore::new(MAX_SERVED_RANGE_REQUESTS)),
+            peer_id,
             handshake_deadline, 
             handshake_completed: false,
             is_dialer,
             initial_capability,
+            version,
         }
     }
 
@@ -86,6 +120,24 @@ impl BscProtocolConnection {
                 
                 buf
             }
+            BscCommand::GetBlocksByRange(request) => {
+                let mut buf = BytesMut::new();
+                buf.put_u8(BscProtoMessageId::GetBlocksByRange as u8);
+                request.encode(&mut buf);
+                buf
+            }
+            BscCommand::SendBlocksByRange(response) => {
+                let mut buf = BytesMut::new();
+                buf.put_u8(BscProtoMessageId::BlocksByRange as u8);
+                response.encode(&mut buf);
+                tracing::debug!(
+                    target: "bsc_protocol",
+                    block_count = response.blocks.len(),
+                    encoded_len = buf.len(),
+                    "Encoded BSC blocks by range packet"
+                );
+                buf
+            }
         }
     }
 
@@ -149,8 +201,8 @@ impl BscProtocolConnection {
         
         match BscCapPacket::decode(&mut &slice[..]) {
             Ok(pkt) => {
-                if pkt.protocol_version != BSC_PROTOCOL_VERSION {
-                    tracing::warn!(target: "bsc_protocol", "Protocol version mismatch: {} != {}", pkt.protocol_version, BSC_PROTOCOL_VERSION);
+                if pkt.protocol_version != self.version {
+                    tracing::warn!(target: "bsc_protocol", "Protocol version mismatch: {} != {}", pkt.protocol_version, self.version);
                     return Poll::Ready(None);
                 }
 
@@ -168,7 +220,7 @@ impl BscProtocolConnection {
                 if !self.is_dialer {
                     // Responder sends capability response
                     let response = Self::encode_command(BscCommand::SendCapability {
-                        protocol_version: BSC_PROTOCOL_VERSION,
+                        protocol_version: self.version,
                         extra: Bytes::from_static(&[0x00u8]) // Raw RLP: single 0x00 byte represents []byte{00}
                     });
                     tracing::debug!(target: "bsc_protocol", "BSC handshake completed (responder) - sending response");
@@ -186,8 +238,8 @@ impl BscProtocolConnection {
         }
     }
 
-    /// Handle normal protocol messages after handshake
-    fn handle_protocol_message(&self, frame: &BytesMut) {
+    /// Handle normal protocol messages after handshake, returns the response to send if any
+    fn handle_protocol_message(&self, frame: &BytesMut) -> Option<BytesMut> {
         tracing::debug!(target: "bsc_protocol", "Handshake completed, processing normal message");
         let slice = frame.as_ref();
         let msg_id = slice[0];
@@ -198,18 +250,55 @@ impl BscProtocolConnection {
                 match VotesPacket::decode(&mut &slice[..]) {
                     Ok(packet) => {
                         let count = packet.0.len();
-                        handle_votes_broadcast(packet);
+                        handle_votes_broadcast(self.peer_id, packet);
                         tracing::debug!(target: "bsc_protocol", count, "Processed votes packet");
                     }
                     Err(e) => {
                         tracing::warn!(target: "bsc_protocol", error = %e, "Failed to decode VotesPacket");
+                        registry::penalize_peer(self.peer_id, ReputationChangeKind::BadMessage);
+                    }
+                }
+            }
+            x if x == BscProtoMessageId::GetBlocksByRange as u8 &&
+                BscProtoMessage::supports_block_ranges(self.version) =>
+            {
+                match GetBlocksByRangePacket::decode(&mut &slice[1..]) {
+                    Ok(request) => {
+                        let Ok(permit) = self.served_ranges.clone().try_acquire_owned() else {
+                            tracing::debug!(target: "bsc_protocol", peer_id = %self.peer_id, "Too many block range requests in flight, dropping request");
+                            return None;
+                        };
+                        // Up to 64 blocks are read from the database, off the network task
+                        let (peer_id, responses) = (self.peer_id, self.responses.clone());
+                        tokio::task::spawn_blocking(move || {
+                            if let Some(response) = handle_get_blocks_by_range(peer_id, request) {
+                                let _ = responses.send(BscCommand::SendBlocksByRange(response));
+                            }
+                            drop(permit);
+                        });
+                    }
+                    Err(e) => {
+                        tracing::warn!(target: "bsc_protocol", error = %e, "Failed to decode GetBlocksByRangePacket");
+                        registry::penalize_peer(self.peer_id, ReputationChangeKind::BadMessage);
+                    }
+                }
+            }
+            x if x == BscProtoMessageId::BlocksByRange as u8 &&
+                BscProtoMessage::supports_block_ranges(self.version) =>
+            {
+                match BlocksByRangePacket::decode(&mut &slice[1..]) {
+                    Ok(response) => handle_blocks_by_range(self.peer_id, response),
+                    Err(e) => {
+                        tracing::warn!(target: "bsc_protocol", error = %e, "Failed to decode BlocksByRangePacket");
+                        registry::penalize_peer(self.peer_id, ReputationChangeKind::BadMessage);
                     }
                 }
             }
             _ => {
-                tracing::debug!(target: "bsc_protocol", msg_id = format_args!("{:#04x}", msg_id), "Unknown BSC message id");
+                tracing::debug!(target: "bsc_protocol", msg_id = format_args!("{:#04x}", msg_id), version = self.version, "Unknown BSC message id");
             }
         }
+        None
     }
 }
 
@@ -231,9 +320,11 @@ impl Stream for BscProtocolConnection {
 
 
         loop {
-            // Check for outgoing commands first
-            if let Some(encoded_command) = this.poll_outgoing_commands(cx) {
-                return Poll::Ready(Some(encoded_command));
+            // Check for outgoing commands first, they are held back until the handshake is done
+            if this.handshake_completed {
+                if let Some(encoded_command) = this.poll_outgoing_commands(cx) {
+                    return Poll::Ready(Some(encoded_command));
+                }
             }
 
             // Get next incoming frame
@@ -253,7 +344,9 @@ impl Stream for BscProtocolConnection {
                     Poll::Pending => return Poll::Pending,
                 }
             } else {
-                this.handle_protocol_message(&raw_frame);
+                if let Some(response) = this.handle_protocol_message(&raw_frame) {
+                    return Poll::Ready(Some(response));
+                }
                 // After handshake, check if there are more messages to process
                 // If not, we'll loop back and check for commands/incoming frames
                 continue;
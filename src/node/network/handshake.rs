@@ -1,4 +1,7 @@
-use super::upgrade_status::{UpgradeStatus, UpgradeStatusExtension};
+use super::{
+    tx_broadcast,
+    upgrade_status::{UpgradeStatus, UpgradeStatusExtension},
+};
 use alloy_rlp::Decodable;
 use futures::SinkExt;
 use reth_eth_wire::{
@@ -16,18 +19,31 @@ use tracing::debug;
 #[derive(Debug, Default)]
 /// The Binance Smart Chain (BSC) P2P handshake.
 #[non_exhaustive]
-pub struct BscHandshake;
+pub struct BscHandshake {
+    /// Whether to ask the peers not to broadcast transactions to us.
+    disable_peer_tx_broadcast: bool,
+}
 
 impl BscHandshake {
+    /// Creates the handshake, asking the peers not to broadcast transactions to us if
+    /// `disable_peer_tx_broadcast` is set.
+    pub const fn new(disable_peer_tx_broadcast: bool) -> Self {
+        Self { disable_peer_tx_broadcast }
+    }
+
     /// Negotiate the upgrade status message.
+    ///
+    /// The flag the peer sent is recorded so transactions are not propagated to peers that
+    /// disabled the broadcast.
     pub async fn upgrade_status(
         unauth: &mut dyn UnauthEth,
         negotiated_status: UnifiedStatus,
+        disable_peer_tx_broadcast: bool,
     ) -> Result<UnifiedStatus, EthStreamError> {
         if negotiated_status.version > EthVersion::Eth66 {
-            // Send upgrade status message allowing peer to broadcast transactions
+            // Send upgrade status message telling the peer whether to broadcast transactions
             let upgrade_msg = UpgradeStatus {
-                extension: UpgradeStatusExtension { disable_peer_tx_broadcast: false },
+                extension: UpgradeStatusExtension { disable_peer_tx_broadcast },
             };
             unauth.start_send_unpin(upgrade_msg.into_rlpx())?;
 
@@ -46,8 +62,12 @@ impl BscHandshake {
                 debug!("Decode error in BSC handshake: msg={their_msg:x}");
                 EthStreamError::InvalidMessage(e.into())
             }) {
-                Ok(_) => {
+                Ok(their_status) => {
                     // Successful handshake
+                    tx_broadcast::on_upgrade_status(
+                        negotiated_status,
+                        their_status.extension.disable_peer_tx_broadcast,
+                    );
                     return Ok(negotiated_status);
                 }
                 Err(_) => {
@@ -55,6 +75,7 @@ impl BscHandshake {
                     // Accept this specific payload leniency but still disconnect on all other errors.
                     if their_msg.as_ref() == [0x0b, 0xc2, 0xc1, 0x80] {
                         debug!("Tolerating legacy empty upgrade-status 0x0bc2c180 message");
+                        tx_broadcast::on_upgrade_status(negotiated_status, false);
                         return Ok(negotiated_status);
                     }
                     unauth.disconnect(DisconnectReason::ProtocolBreach).await?;
@@ -81,7 +102,8 @@ impl EthRlpxHandshake for BscHandshake {
             let fut = async {
                 let negotiated_status =
                     EthereumEthHandshake(unauth).eth_handshake(status, fork_filter).await?;
-                Self::upgrade_status(unauth, negotiated_status).await
+                Self::upgrade_status(unauth, negotiated_status, self.disable_peer_tx_broadcast)
+                    .await
             };
             timeout(timeout_limit, fut).await.map_err(|_| EthStreamError::StreamTimeout)?
         })
@@ -9,6 +9,7 @@ use crate::{
     BscBlock,
 };
 use alloy_rlp::{Decodable, Encodable};
+use bsc_protocol::protocol::{handler::BscProtocolHandler, proto::BSC_PROTOCOL_VERSIONS};
 use handshake::BscHandshake;
 use reth::{
     api::{FullNodeTypes, TxTy},
@@ -21,14 +22,18 @@ use reth_eth_wire::{BasicNetworkPrimitives, NewBlock, NewBlockPayload};
 use reth_ethereum_primitives::PooledTransactionVariant;
 use reth_engine_primitives::BeaconConsensusEngineHandle;
 use reth_network::{NetworkConfig, NetworkHandle, NetworkManager};
+use clap::Args;
 use reth_network_api::PeersInfo;
 use std::{sync::Arc, time::Duration};
 use tokio::sync::{mpsc, oneshot, Mutex};
 use tracing::info;
+use tx_broadcast::BscTxPropagationPolicy;
 
 pub mod block_import;
+pub(crate) mod block_range;
 pub mod bootnodes;
 pub mod handshake;
+pub mod tx_broadcast;
 pub(crate) mod upgrade_status;
 pub(crate) mod votes;
 pub(crate) mod bsc_protocol {
@@ -36,6 +41,7 @@ pub(crate) mod bsc_protocol {
         pub mod handler;
         pub mod proto;
     }
+    pub mod registry;
     pub mod stream;
 }
 /// BSC `NewBlock` message value.
@@ -145,19 +151,37 @@ impl NewBlockPayload for BscNewBlock {
 pub type BscNetworkPrimitives =
     BasicNetworkPrimitives<BscPrimitives, PooledTransactionVariant, BscNewBlock>;
 
+/// Networking parameters of the BSC node.
+#[derive(Debug, Clone, Default, Args)]
+#[command(next_help_heading = "Networking")]
+pub struct BscNetworkArgs {
+    /// Ask the peers not to broadcast transactions to this node, as validators behind sentries
+    /// do.
+    #[arg(long = "network.disable-peer-tx-broadcast", default_value_t = false)]
+    pub disable_peer_tx_broadcast: bool,
+}
+
 /// A basic bsc network builder.
 #[derive(Debug)]
 pub struct BscNetworkBuilder {
     engine_handle_rx: Arc<
         Mutex<Option<oneshot::Receiver<BeaconConsensusEngineHandle<BscPayloadTypes>>>>,
     >,
+    /// Whether to ask the peers not to broadcast transactions to us.
+    disable_peer_tx_broadcast: bool,
 }
 
 impl BscNetworkBuilder {
     pub fn new(
         engine_handle_rx: Arc<Mutex<Option<oneshot::Receiver<BeaconConsensusEngineHandle<BscPayloadTypes>>>>>,
     ) -> Self {
-        Self { engine_handle_rx }
+        Self { engine_handle_rx, disable_peer_tx_broadcast: false }
+    }
+
+    /// Asks the peers not to broadcast transactions to us if `disable` is set.
+    pub const fn with_disable_peer_tx_broadcast(mut self, disable: bool) -> Self {
+        self.disable_peer_tx_broadcast = disable;
+        self
     }
 }
 
@@ -179,7 +203,7 @@ impl BscNetworkBuilder {
     where
         Node: FullNodeTypes<Types = BscNode>,
     {
-        let Self { engine_handle_rx } = self;
+        let Self { engine_handle_rx, disable_peer_tx_broadcast } = self;
 
         let network_builder = ctx.network_config_builder()?;
         let mut discv4 = Discv4Config::builder();
@@ -199,7 +223,8 @@ impl BscNetworkBuilder {
         use crate::node::network::block_import::service::ImportService;
         
         // Create consensus instance for ImportService
-        let consensus = Arc::new(ParliaConsensus { provider: ctx.provider().clone() });
+        let consensus =
+            Arc::new(ParliaConsensus::new(ctx.provider().clone(), ctx.chain_spec()));
         
         // Spawn the critical ImportService task exactly like the official implementation
         ctx.task_executor().spawn_critical("block import", async move {
@@ -214,15 +239,19 @@ impl BscNetworkBuilder {
             ImportService::new(consensus, handle, from_network, to_network).await.unwrap();
         });
 
-        let network_builder = network_builder
+        let mut network_builder = network_builder
             .boot_nodes(ctx.chain_spec().bootnodes().unwrap_or_default())
             .set_head(ctx.chain_spec().head())
             .with_pow()
             .block_import(Box::new(BscBlockImport::new(handle)))
             .discovery(discv4)
-            .eth_rlpx_handshake(Arc::new(BscHandshake::default()))
-            .add_rlpx_sub_protocol(bsc_protocol::protocol::handler::BscProtocolHandler);
-        
+            .eth_rlpx_handshake(Arc::new(BscHandshake::new(disable_peer_tx_broadcast)));
+        // `bsc/1` and `bsc/2` are both advertised, the highest version shared with a peer is used
+        for version in BSC_PROTOCOL_VERSIONS {
+            network_builder =
+                network_builder.add_rlpx_sub_protocol(BscProtocolHandler::new(version));
+        }
+
         let mut network_config = ctx.build_network_config(network_builder);
         // Ensure our advertised fork ID matches the fork filter we validate against.
         network_config.status.forkid = network_config.fork_filter.current();
@@ -251,7 +280,15 @@ where
     ) -> eyre::Result<Self::Network> {
         let network_config = self.network_config(ctx)?;
         let network = NetworkManager::builder(network_config).await?;
-        let handle = ctx.start_network(network, pool);
+        let handle = ctx.start_network_with(
+            network,
+            pool,
+            ctx.config().network.transactions_manager_config(),
+            BscTxPropagationPolicy(ctx.config().network.tx_propagation_policy),
+        );
+        bsc_protocol::registry::set_network(handle.clone());
+        ctx.task_executor().spawn(tx_broadcast::track_sessions(handle.clone()));
+        ctx.task_executor().spawn(votes::track_canonical_head(ctx.provider().clone()));
         info!(target: "reth::cli", enode=%handle.local_node_record(), "P2P networking initialized");
 
         Ok(handle)
@@ -0,0 +1,220 @@
+//! Transaction broadcast preference of the peers, exchanged in the `UpgradeStatus` handshake.
+//!
+//! A peer sets `disable_peer_tx_broadcast` to ask not to be sent transactions, validators do so
+//! to keep their sentries from pushing them the mempool. The eth handshake does not know which
+//! peer it runs for, so the flag is kept with the status the peer sent until the network reports
+//! the established session of the peer together with that status. Every handshake is matched with
+//! a single session.
+//!
+//! The matching is best-effort: peers at the same head send identical statuses and can't be told
+//! apart. When their flags differ, all of their sessions are treated as having disabled the
+//! broadcast, so a peer that asked not to be sent transactions never gets them.
+use super::BscNetworkPrimitives;
+use futures::StreamExt;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use reth_eth_wire::{NetworkPrimitives, UnifiedStatus};
+use reth_network::{
+    transactions::{
+        config::{TransactionPropagationKind, TransactionPropagationPolicy},
+        PeerMetadata,
+    },
+    NetworkHandle,
+};
+use reth_network_api::{
+    events::{NetworkEvent, PeerEvent},
+    NetworkEventListenerProvider, PeerId,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+/// Time within which a handshake is matched with the established session of its peer.
+const HANDSHAKE_MATCH_WINDOW: Duration = Duration::from_secs(30);
+
+/// Maximum number of handshakes waiting for their session.
+const MAX_PENDING_HANDSHAKES: usize = 1024;
+
+/// The peers that asked not to be sent transactions.
+#[derive(Debug, Default)]
+pub(crate) struct TxBroadcastFlags {
+    /// Statuses and flags of the handshakes whose session is not established yet, with the time
+    /// of the handshake.
+    pending: VecDeque<(UnifiedStatus, bool, Instant)>,
+    /// Flag of the peers with an established session.
+    sessions: HashMap<PeerId, bool>,
+}
+
+impl TxBroadcastFlags {
+    /// Records the flag a peer sent in its `UpgradeStatus` along with its status.
+    pub(crate) fn on_upgrade_status(
+        &mut self,
+        status: UnifiedStatus,
+        disabled: bool,
+        now: Instant,
+    ) {
+        if self.pending.len() >= MAX_PENDING_HANDSHAKES {
+            self.pending.pop_front();
+        }
+        self.pending.push_back((status, disabled, now));
+    }
+
+    /// Records the flag of a peer whose session got established with the given status.
+    ///
+    /// A handshake with that status is used up, the network reporting the session again does not
+    /// change the flag.
+    ///
+    /// If the handshakes with that status disagree, the session is taken as disabled and a
+    /// handshake that did not disable the broadcast is used up, so the ones that did still apply
+    /// to the other sessions with that status.
+    pub(crate) fn on_session_established(
+        &mut self,
+        peer_id: PeerId,
+        status: &UnifiedStatus,
+        now: Instant,
+    ) {
+        if self.sessions.contains_key(&peer_id) {
+            return
+        }
+        self.pending.retain(|(_, _, at)| now.duration_since(*at) < HANDSHAKE_MATCH_WINDOW);
+        let disabled =
+            self.pending.iter().any(|(pending, disabled, _)| pending == status && *disabled);
+        let index = self
+            .pending
+            .iter()
+            .position(|(pending, disabled, _)| pending == status && !disabled)
+            .or_else(|| self.pending.iter().position(|(pending, _, _)| pending == status));
+        if let Some(index) = index {
+            self.pending.remove(index);
+        }
+        self.sessions.insert(peer_id, disabled);
+    }
+
+    /// Forgets the flag of a peer whose session closed.
+    pub(crate) fn on_session_closed(&mut self, peer_id: &PeerId) {
+        self.sessions.remove(peer_id);
+    }
+
+    /// Returns whether the peer asked not to be sent transactions.
+    pub(crate) fn is_disabled(&self, peer_id: &PeerId) -> bool {
+        self.sessions.get(peer_id).copied().unwrap_or_default()
+    }
+}
+
+/// Transaction broadcast preference of the connected peers.
+static TX_BROADCAST_FLAGS: Lazy<Mutex<TxBroadcastFlags>> =
+    Lazy::new(|| Mutex::new(TxBroadcastFlags::default()));
+
+/// Records the `disable_peer_tx_broadcast` flag a peer sent in the handshake.
+pub(crate) fn on_upgrade_status(status: UnifiedStatus, disabled: bool) {
+    TX_BROADCAST_FLAGS.lock().on_upgrade_status(status, disabled, Instant::now());
+}
+
+/// Returns whether the peer asked not to be sent transactions.
+pub fn tx_broadcast_disabled(peer_id: &PeerId) -> bool {
+    TX_BROADCAST_FLAGS.lock().is_disabled(peer_id)
+}
+
+/// Assigns the flags received in the handshakes to the sessions of the network as they are
+/// established, until the network shuts down.
+pub(crate) async fn track_sessions(network: NetworkHandle<BscNetworkPrimitives>) {
+    let mut events = network.event_listener();
+    while let Some(event) = events.next().await {
+        match event {
+            NetworkEvent::ActivePeer(info) |
+            NetworkEvent::Peer(PeerEvent::SessionEstablished(info)) => {
+                TX_BROADCAST_FLAGS.lock().on_session_established(
+                    info.peer_id,
+                    &info.status,
+                    Instant::now(),
+                );
+            }
+            NetworkEvent::Peer(PeerEvent::SessionClosed { peer_id, .. }) => {
+                TX_BROADCAST_FLAGS.lock().on_session_closed(&peer_id);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Propagation policy that honours the configured [`TransactionPropagationKind`] and skips the
+/// peers that disabled the transaction broadcast.
+#[derive(Debug, Clone)]
+pub struct BscTxPropagationPolicy(pub TransactionPropagationKind);
+
+impl TransactionPropagationPolicy for BscTxPropagationPolicy {
+    fn can_propagate<N: NetworkPrimitives>(&self, peer: &mut PeerMetadata<N>) -> bool {
+        !tx_broadcast_disabled(&peer.request_tx().peer_id) && self.0.can_propagate(peer)
+    }
+
+    fn on_session_established<N: NetworkPrimitives>(&mut self, peer: &mut PeerMetadata<N>) {
+        self.0.on_session_established(peer)
+    }
+
+    fn on_session_closed<N: NetworkPrimitives>(&mut self, peer: &mut PeerMetadata<N>) {
+        self.0.on_session_closed(peer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+
+    #[test]
+    fn test_flags_follow_sessions() {
+        let mut flags = TxBroadcastFlags::default();
+        let now = Instant::now();
+        let validator = UnifiedStatus { blockhash: B256::repeat_byte(1), ..Default::default() };
+        let other = UnifiedStatus { blockhash: B256::repeat_byte(2), ..Default::default() };
+        let (validator_id, other_id) = (PeerId::random(), PeerId::random());
+
+        flags.on_upgrade_status(validator, true, now);
+        flags.on_upgrade_status(other, false, now);
+        flags.on_session_established(other_id, &other, now);
+        flags.on_session_established(validator_id, &validator, now);
+        assert!(flags.is_disabled(&validator_id));
+        assert!(!flags.is_disabled(&other_id));
+
+        // the session is reported twice, the handshake is only used once
+        flags.on_session_established(validator_id, &validator, now);
+        assert!(flags.is_disabled(&validator_id));
+        assert!(flags.pending.is_empty());
+
+        flags.on_session_closed(&validator_id);
+        assert!(!flags.is_disabled(&validator_id));
+
+        // two peers with the same status can't be told apart, whatever the order of their
+        // sessions both are taken as disabled
+        let (first_id, second_id) = (PeerId::random(), PeerId::random());
+        for (established, other) in [(first_id, second_id), (second_id, first_id)] {
+            flags.on_upgrade_status(validator, true, now);
+            flags.on_upgrade_status(validator, false, now);
+            flags.on_session_established(established, &validator, now);
+            flags.on_session_established(established, &validator, now);
+            flags.on_session_established(other, &validator, now);
+            assert!(flags.is_disabled(&first_id));
+            assert!(flags.is_disabled(&second_id));
+            assert!(flags.pending.is_empty());
+            flags.on_session_closed(&first_id);
+            flags.on_session_closed(&second_id);
+        }
+
+        // identical statuses agreeing on the flag keep it
+        flags.on_upgrade_status(validator, false, now);
+        flags.on_upgrade_status(validator, false, now);
+        flags.on_session_established(first_id, &validator, now);
+        flags.on_session_established(second_id, &validator, now);
+        assert!(!flags.is_disabled(&first_id));
+        assert!(!flags.is_disabled(&second_id));
+        flags.on_session_closed(&first_id);
+        flags.on_session_closed(&second_id);
+
+        // a handshake is not matched once the window elapsed
+        flags.on_upgrade_status(validator, true, now);
+        let later = now + HANDSHAKE_MATCH_WINDOW;
+        flags.on_session_established(validator_id, &validator, later);
+        assert!(!flags.is_disabled(&validator_id));
+    }
+}
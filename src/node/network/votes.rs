@@ -1,8 +1,17 @@
 use alloy_rlp::{Decodable, Encodable, RlpDecodable, RlpEncodable};
 use bytes::{BufMut, Bytes};
 
-use crate::consensus::parlia::{vote::VoteEnvelope, votes};
-use crate::node::network::bsc_protocol::protocol::proto::BscProtoMessageId;
+use crate::consensus::parlia::{
+    vote::{VoteAddress, VoteEnvelope},
+    votes::{self, LOWER_LIMIT_OF_VOTE_BLOCK_NUMBER, UPPER_LIMIT_OF_VOTE_BLOCK_NUMBER},
+    Snapshot,
+};
+use crate::node::network::bsc_protocol::{protocol::proto::BscProtoMessageId, registry};
+use crate::BscPrimitives;
+use alloy_primitives::BlockNumber;
+use reth_network_api::{PeerId, ReputationChangeKind};
+use reth_provider::CanonStateSubscriptions;
+use tokio::sync::broadcast::error::RecvError;
 
 /// BSC capability packet: version + extra RLP value (opaque), message id 0x00
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -111,11 +120,186 @@ impl Decodable for VotesPacket {
     }
 }
 
+/// Reasons a vote received from a peer is not accepted in the pool.
+#[derive(Debug, thiserror::Error)]
+pub enum VoteError {
+    /// The target of the vote is too far from the head.
+    #[error("vote target {target} is out of range of head {head}")]
+    OutOfRange {
+        /// Target block number of the vote.
+        target: BlockNumber,
+        /// Number of the canonical head.
+        head: BlockNumber,
+    },
+    /// The canonical head is not known yet.
+    #[error("canonical head unknown")]
+    UnknownHead,
+    /// The snapshot the vote is checked against is not available.
+    #[error("snapshot of block {0} not found")]
+    SnapshotNotFound(BlockNumber),
+    /// The vote address does not belong to the validator set.
+    #[error("vote address {0} is not a validator")]
+    UnknownValidator(VoteAddress),
+    /// The BLS signature does not match the vote.
+    #[error("invalid vote signature")]
+    InvalidSignature,
+}
+
+impl VoteError {
+    /// Returns `true` if the vote could not have been sent by an honest peer.
+    pub const fn is_bad_message(&self) -> bool {
+        matches!(self, Self::UnknownValidator(_) | Self::InvalidSignature)
+    }
+}
+
+/// Checks that a vote targets a block near the head, comes from a validator of the snapshot of
+/// the head and is signed by it.
+pub fn verify_vote(
+    vote: &VoteEnvelope,
+    head: BlockNumber,
+    snap: &Snapshot,
+) -> Result<(), VoteError> {
+    let target = vote.data.target_number;
+    // the target is chosen by the peer, it must not overflow
+    if target.saturating_add(LOWER_LIMIT_OF_VOTE_BLOCK_NUMBER - 1) < head ||
+        target > head.saturating_add(UPPER_LIMIT_OF_VOTE_BLOCK_NUMBER)
+    {
+        return Err(VoteError::OutOfRange { target, head })
+    }
+    if !snap.validators_map.values().any(|info| info.vote_addr == vote.vote_address) {
+        return Err(VoteError::UnknownValidator(vote.vote_address))
+    }
+    if !vote.verify() {
+        return Err(VoteError::InvalidSignature)
+    }
+    Ok(())
+}
+
 /// Handle an incoming `VotesPacket` from a peer.
 /// To avoid DoS from massive batches, only enqueue the first vote if present,
 /// mirroring Geth's logic.
-pub fn handle_votes_broadcast(packet: VotesPacket) {
-    if let Some(first) = packet.0.into_iter().next() {
-        votes::put_vote(first);
+///
+/// The vote only enters the pool once verified, peers sending forged votes or empty packets are
+/// penalised and banned once they repeat it.
+/// Votes accepted by the pool are relayed to some of the other peers that don't have them yet.
+pub fn handle_votes_broadcast(peer_id: PeerId, packet: VotesPacket) {
+    let Some(vote) = packet.0.into_iter().next() else {
+        // an empty packet carries nothing to relay
+        registry::penalize_peer(peer_id, ReputationChangeKind::BadMessage);
+        return
+    };
+    let vote_hash = vote.hash();
+    registry::mark_vote_known(peer_id, vote_hash);
+    if votes::contains(&vote_hash) {
+        return;
+    }
+
+    match verify_pooled_vote(&vote) {
+        Ok(()) => {
+            // votes the pool has no room for are not relayed either
+            if !votes::put_vote(vote.clone()) {
+                return
+            }
+            let peers = registry::relay_vote(&vote, Some(peer_id));
+            tracing::trace!(target: "bsc_protocol", %peer_id, ?vote_hash, peers, "Relayed vote");
+        }
+        Err(err) => {
+            tracing::debug!(target: "bsc_protocol", %peer_id, %err, "Discarding vote");
+            if err.is_bad_message() {
+                registry::penalize_peer(peer_id, ReputationChangeKind::BadMessage);
+            }
+        }
+    }
+}
+
+/// Promotes the future votes of the pool and prunes the old ones every time the canonical head
+/// moves, until the canonical state stream is closed.
+///
+/// The head moves for the blocks received from the peers, the pipeline and the local validator
+/// alike.
+pub async fn track_canonical_head<Provider>(provider: Provider)
+where
+    Provider: CanonStateSubscriptions<Primitives = BscPrimitives>,
+{
+    let mut canon_events = provider.subscribe_to_canonical_state();
+    loop {
+        let head = match canon_events.recv().await {
+            Ok(notification) => notification.tip().clone_sealed_header(),
+            Err(RecvError::Lagged(_)) => continue,
+            Err(RecvError::Closed) => return,
+        };
+        votes::on_block_imported(head.number, head.hash());
+    }
+}
+
+/// Verifies a vote against the validator set that votes on its target, the one of the snapshot
+/// of the parent of the target.
+///
+/// Votes for a block not imported yet are checked against the snapshot of the canonical head.
+fn verify_pooled_vote(vote: &VoteEnvelope) -> Result<(), VoteError> {
+    let head = crate::shared::get_best_block_number().ok_or(VoteError::UnknownHead)?;
+    let provider = crate::shared::get_snapshot_provider();
+    let snap = match crate::shared::get_header_by_hash(&vote.data.target_hash)
+        .filter(|target| target.number == vote.data.target_number && target.number > 0)
+    {
+        Some(target) => provider
+            .and_then(|provider| provider.snapshot_by_hash(target.number - 1, target.parent_hash))
+            .ok_or(VoteError::SnapshotNotFound(target.number - 1))?,
+        None => provider
+            .and_then(|provider| provider.snapshot(head))
+            .ok_or(VoteError::SnapshotNotFound(head))?,
+    };
+    verify_vote(vote, head, &snap)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus::parlia::vote::{VoteData, VoteSignature, BLST_DST};
+    use alloy_primitives::{Address, B256};
+    use blst::min_pk::SecretKey;
+
+    fn signed_vote(secret_key: &SecretKey, target_number: u64) -> VoteEnvelope {
+        let data =
+            VoteData { source_number: target_number - 1, target_number, ..Default::default() };
+        VoteEnvelope {
+            vote_address: VoteAddress::from(secret_key.sk_to_pk().to_bytes()),
+            signature: VoteSignature::from(
+                secret_key.sign(data.hash().as_slice(), BLST_DST, &[]).to_bytes(),
+            ),
+            data,
+        }
+    }
+
+    #[test]
+    fn test_verify_vote() {
+        let validator = SecretKey::key_gen(&[1u8; 32], &[]).unwrap();
+        let stranger = SecretKey::key_gen(&[2u8; 32], &[]).unwrap();
+        let snap = Snapshot::new(
+            vec![Address::with_last_byte(1)],
+            1000,
+            B256::ZERO,
+            200,
+            Some(vec![VoteAddress::from(validator.sk_to_pk().to_bytes())]),
+        );
+
+        assert!(verify_vote(&signed_vote(&validator, 1000), 1000, &snap).is_ok());
+        assert!(verify_vote(&signed_vote(&validator, 745), 1000, &snap).is_ok());
+        assert!(verify_vote(&signed_vote(&validator, 1011), 1000, &snap).is_ok());
+
+        let err = verify_vote(&signed_vote(&validator, 744), 1000, &snap).unwrap_err();
+        assert!(matches!(err, VoteError::OutOfRange { .. }) && !err.is_bad_message());
+        let err = verify_vote(&signed_vote(&validator, 1012), 1000, &snap).unwrap_err();
+        assert!(matches!(err, VoteError::OutOfRange { .. }) && !err.is_bad_message());
+        let err = verify_vote(&signed_vote(&validator, u64::MAX), 1000, &snap).unwrap_err();
+        assert!(matches!(err, VoteError::OutOfRange { .. }));
+
+        let err = verify_vote(&signed_vote(&stranger, 1000), 1000, &snap).unwrap_err();
+        assert!(matches!(err, VoteError::UnknownValidator(_)) && err.is_bad_message());
+
+        let mut forged = signed_vote(&validator, 1000);
+        forged.data.target_hash = B256::with_last_byte(1);
+        let err = verify_vote(&forged, 1000, &snap).unwrap_err();
+        assert!(matches!(err, VoteError::InvalidSignature) && err.is_bad_message());
     }
 }
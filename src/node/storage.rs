@@ -1,11 +1,123 @@
-use crate::{BscBlock, BscBlockBody, BscPrimitives};
+use crate::{
+    consensus::parlia::db::ParliaSnapshotsByHash, node::primitives::BscBlobTransactionSidecar,
+    BscBlock, BscBlockBody, BscPrimitives,
+};
+use alloy_rlp::{Decodable, Encodable};
+use clap::Args;
 use reth_chainspec::EthereumHardforks;
-use reth_db::transaction::{DbTx, DbTxMut};
+use reth_db::{
+    cursor::{DbCursorRO, DbCursorRW},
+    table::{Compress, Decompress, Table, TableInfo, TableSet},
+    transaction::{DbTx, DbTxMut},
+    DatabaseError,
+};
 use reth_provider::{
     providers::{ChainStorage, NodeTypesForProvider},
     BlockBodyReader, BlockBodyWriter, ChainSpecProvider, ChainStorageReader, ChainStorageWriter,
     DBProvider, DatabaseProvider, EthStorage, ProviderResult, ReadBodyInput, StorageLocation,
 };
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Number of blocks the blob sidecars are kept for, the `MinBlocksForBlobRequests` of bsc-geth.
+pub const DEFAULT_SIDECAR_RETENTION_BLOCKS: u64 = 524_288;
+
+/// Number of blocks the blob sidecars are kept for, see [`set_sidecar_retention_blocks`].
+static SIDECAR_RETENTION_BLOCKS: AtomicU64 = AtomicU64::new(DEFAULT_SIDECAR_RETENTION_BLOCKS);
+
+/// Sets the number of blocks the blob sidecars are kept for.
+///
+/// Sidecars of older blocks are deleted as new blocks are written.
+pub fn set_sidecar_retention_blocks(blocks: u64) {
+    SIDECAR_RETENTION_BLOCKS.store(blocks, Ordering::Relaxed);
+}
+
+/// Storage parameters of the node.
+#[derive(Debug, Clone, Args)]
+#[command(next_help_heading = "Storage")]
+pub struct BscStorageArgs {
+    /// Number of blocks the blob sidecars are kept for.
+    #[arg(
+        long = "sidecars.retention-blocks",
+        value_name = "BLOCKS",
+        default_value_t = DEFAULT_SIDECAR_RETENTION_BLOCKS
+    )]
+    pub sidecar_retention_blocks: u64,
+}
+
+impl Default for BscStorageArgs {
+    fn default() -> Self {
+        Self { sidecar_retention_blocks: DEFAULT_SIDECAR_RETENTION_BLOCKS }
+    }
+}
+
+/// Table: block number -> blob sidecars of the block.
+#[derive(Debug)]
+pub struct BlobSidecars;
+
+impl Table for BlobSidecars {
+    const NAME: &'static str = "BlobSidecars";
+    const DUPSORT: bool = false;
+    type Key = u64;
+    type Value = BscBlobSidecars;
+}
+
+/// Sidecars of the blob transactions of a block, RLP encoded in the database.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BscBlobSidecars(pub Vec<BscBlobTransactionSidecar>);
+
+impl Compress for BscBlobSidecars {
+    type Compressed = Vec<u8>;
+
+    fn compress_to_buf<B: bytes::BufMut + AsMut<[u8]>>(&self, buf: &mut B) {
+        self.0.encode(buf);
+    }
+}
+
+impl Decompress for BscBlobSidecars {
+    fn decompress(value: &[u8]) -> Result<Self, DatabaseError> {
+        Vec::<BscBlobTransactionSidecar>::decode(&mut &value[..])
+            .map(Self)
+            .map_err(|_| DatabaseError::Decode)
+    }
+}
+
+/// Tables the node adds to the reth database.
+#[derive(Debug)]
+pub struct BscTables;
+
+impl TableSet for BscTables {
+    fn tables() -> Box<dyn Iterator<Item = Box<dyn TableInfo>>> {
+        Box::new(
+            [BscTable::BlobSidecars, BscTable::ParliaSnapshotsByHash]
+                .into_iter()
+                .map(|table| Box::new(table) as Box<dyn TableInfo>),
+        )
+    }
+}
+
+/// Table of [`BscTables`].
+#[derive(Debug, Clone, Copy)]
+enum BscTable {
+    BlobSidecars,
+    ParliaSnapshotsByHash,
+}
+
+impl TableInfo for BscTable {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::BlobSidecars => BlobSidecars::NAME,
+            Self::ParliaSnapshotsByHash => ParliaSnapshotsByHash::NAME,
+        }
+    }
+
+    fn is_dupsort(&self) -> bool {
+        match self {
+            Self::BlobSidecars => BlobSidecars::DUPSORT,
+            Self::ParliaSnapshotsByHash => ParliaSnapshotsByHash::DUPSORT,
+        }
+    }
+}
 
 #[derive(Debug, Clone, Default)]
 #[non_exhaustive]
@@ -21,7 +133,7 @@ where
         bodies: Vec<(u64, Option<BscBlockBody>)>,
         write_to: StorageLocation,
     ) -> ProviderResult<()> {
-        let (eth_bodies, _sidecars) = bodies
+        let (eth_bodies, sidecars) = bodies
             .into_iter()
             .map(|(block_number, body)| {
                 if let Some(BscBlockBody { inner, sidecars }) = body {
@@ -33,7 +145,30 @@ where
             .unzip::<_, _, Vec<_>, Vec<_>>();
         self.0.write_block_bodies(provider, eth_bodies, write_to)?;
 
-        // TODO: Write sidecars
+        if !write_to.database() {
+            return Ok(())
+        }
+        let tx = provider.tx_ref();
+        let mut highest = None;
+        for (block_number, sidecars) in sidecars {
+            highest = Some(block_number);
+            if let Some(sidecars) = sidecars.flatten().filter(|sidecars| !sidecars.is_empty()) {
+                tx.put::<BlobSidecars>(block_number, BscBlobSidecars(sidecars))?;
+            }
+        }
+
+        // drop the sidecars that left the retention window
+        if let Some(highest) = highest {
+            let retention = SIDECAR_RETENTION_BLOCKS.load(Ordering::Relaxed);
+            let mut cursor = tx.cursor_write::<BlobSidecars>()?;
+            while let Some((block_number, _)) = cursor.first()? {
+                // a retention of `u64::MAX` keeps the sidecars forever
+                if block_number.saturating_add(retention) > highest {
+                    break
+                }
+                cursor.delete_current()?;
+            }
+        }
 
         Ok(())
     }
@@ -46,7 +181,13 @@ where
     ) -> ProviderResult<()> {
         self.0.remove_block_bodies_above(provider, block, remove_from)?;
 
-        // TODO: Remove sidecars
+        if remove_from.database() {
+            let mut cursor = provider.tx_ref().cursor_write::<BlobSidecars>()?;
+            let mut walker = cursor.walk_range(block + 1..)?;
+            while walker.next().transpose()?.is_some() {
+                walker.delete_current()?;
+            }
+        }
 
         Ok(())
     }
@@ -63,11 +204,18 @@ where
         provider: &Provider,
         inputs: Vec<ReadBodyInput<'_, Self::Block>>,
     ) -> ProviderResult<Vec<BscBlockBody>> {
+        let numbers = inputs.iter().map(|(header, _)| header.number).collect::<Vec<_>>();
         let eth_bodies = self.0.read_block_bodies(provider, inputs)?;
 
-        // TODO: Read sidecars
-
-        Ok(eth_bodies.into_iter().map(|inner| BscBlockBody { inner, sidecars: None }).collect())
+        let tx = provider.tx_ref();
+        eth_bodies
+            .into_iter()
+            .zip(numbers)
+            .map(|(inner, number)| {
+                let sidecars = tx.get::<BlobSidecars>(number)?.map(|sidecars| sidecars.0);
+                Ok(BscBlockBody { inner, sidecars })
+            })
+            .collect()
     }
 }
 
@@ -92,3 +240,66 @@ impl ChainStorage<BscPrimitives> for BscStorage {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        chainspec::{bsc::bsc_mainnet, BscChainSpec},
+        node::BscNode,
+    };
+    use alloy_consensus::{BlobTransactionSidecar, Header};
+    use alloy_primitives::B256;
+    use reth_provider::test_utils::create_test_provider_factory_with_node_types;
+    use std::sync::Arc;
+
+    fn sidecar(block_number: u64) -> BscBlobTransactionSidecar {
+        BscBlobTransactionSidecar {
+            inner: BlobTransactionSidecar::default(),
+            block_number,
+            block_hash: B256::with_last_byte(block_number as u8),
+            tx_index: 2,
+            tx_hash: B256::with_last_byte(3),
+        }
+    }
+
+    #[test]
+    fn test_sidecars_roundtrip() {
+        let sidecars = BscBlobSidecars(vec![sidecar(1)]);
+        let compressed = sidecars.clone().compress();
+        assert_eq!(BscBlobSidecars::decompress(&compressed).unwrap(), sidecars);
+    }
+
+    #[test]
+    fn test_sidecars_write_unwind_read() {
+        let factory = create_test_provider_factory_with_node_types::<BscNode>(Arc::new(
+            BscChainSpec::from(bsc_mainnet()),
+        ));
+        factory.db_ref().db().create_tables_for::<BscTables>().unwrap();
+        // the sidecars are kept forever, without overflowing the retention window
+        set_sidecar_retention_blocks(u64::MAX);
+
+        let storage = BscStorage::default();
+        let provider = factory.provider_rw().unwrap();
+        let bodies = (1..=3)
+            .map(|number| {
+                let sidecars = Some(vec![sidecar(number)]);
+                let body = BscBlockBody { inner: Default::default(), sidecars };
+                (number, Some(body))
+            })
+            .collect();
+        storage.write_block_bodies(&*provider, bodies, StorageLocation::Database).unwrap();
+        storage.remove_block_bodies_above(&*provider, 2, StorageLocation::Database).unwrap();
+
+        let headers =
+            (1..=3).map(|number| Header { number, ..Default::default() }).collect::<Vec<_>>();
+        let inputs = headers.iter().map(|header| (header, Vec::new())).collect();
+        let sidecars = storage
+            .read_block_bodies(&*provider, inputs)
+            .unwrap()
+            .into_iter()
+            .map(|body| body.sidecars)
+            .collect::<Vec<_>>();
+        assert_eq!(sidecars, [Some(vec![sidecar(1)]), Some(vec![sidecar(2)]), None]);
+    }
+}
@@ -1,2 +1,4 @@
 pub mod parlia;
-pub use parlia::*;
\ No newline at end of file
+pub mod sidecar;
+pub use parlia::*;
+pub use sidecar::*;
@@ -1,10 +1,38 @@
 
-use jsonrpsee::{core::RpcResult, proc_macros::rpc, types::ErrorObject};
+use alloy_consensus::Sealable;
+use alloy_eips::BlockNumberOrTag;
+use alloy_rpc_types_eth::Header as RpcHeader;
+use jsonrpsee::{
+    core::{RpcResult, SubscriptionResult},
+    proc_macros::rpc,
+    types::{
+        error::{INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE},
+        ErrorObject, ErrorObjectOwned,
+    },
+    PendingSubscriptionSink, SubscriptionMessage, SubscriptionSink,
+};
+use reth_provider::CanonStateSubscriptions;
 use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::{self, error::RecvError};
 
-use crate::consensus::parlia::{Snapshot, SnapshotProvider};
+use crate::{
+    chainspec::BscChainSpec,
+    consensus::parlia::{
+        double_sign::{self, DoubleSign},
+        votes, Parlia, Snapshot, SnapshotProvider, VoteAddress, VoteData, VoteEnvelope,
+        VoteSignature, DEFAULT_TURN_LENGTH,
+    },
+    BscPrimitives,
+};
+use alloy_primitives::{hex, Address, Bytes, B256};
 
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
+
+/// Error code of geth for the queries of blocks it does not know
+const UNKNOWN_BLOCK_CODE: i32 = -32000;
+
+/// Number of finalized headers buffered for the slow subscribers
+const FINALIZED_HEADERS_BUFFER: usize = 64;
 
 /// Validator information in the snapshot (matches BSC official format)
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,7 +51,100 @@ impl Default for ValidatorInfo {
     }
 }
 
-/// Official BSC Parlia snapshot response structure matching bsc-erigon
+impl From<&crate::consensus::parlia::snapshot::ValidatorInfo> for ValidatorInfo {
+    fn from(info: &crate::consensus::parlia::snapshot::ValidatorInfo) -> Self {
+        Self { index: info.index, vote_address: info.vote_addr.to_vec() }
+    }
+}
+
+/// Latest attested vote data of the snapshot, bsc-geth encodes it without json tags
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct AttestationResult {
+    pub source_number: u64,
+    pub source_hash: B256,
+    pub target_number: u64,
+    pub target_hash: B256,
+}
+
+impl From<VoteData> for AttestationResult {
+    fn from(data: VoteData) -> Self {
+        Self {
+            source_number: data.source_number,
+            source_hash: data.source_hash,
+            target_number: data.target_number,
+            target_hash: data.target_hash,
+        }
+    }
+}
+
+/// Vote of a validator, bsc-geth encodes it without json tags
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct VoteResult {
+    pub vote_address: VoteAddress,
+    pub signature: VoteSignature,
+    pub data: AttestationResult,
+}
+
+impl From<VoteEnvelope> for VoteResult {
+    fn from(vote: VoteEnvelope) -> Self {
+        Self { vote_address: vote.vote_address, signature: vote.signature, data: vote.data.into() }
+    }
+}
+
+/// Double sign found in the headers received from the network
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DoubleSignResult {
+    pub signer: Address,
+    pub number: u64,
+    pub hash1: B256,
+    pub hash2: B256,
+    /// First argument of `submitDoubleSignEvidence`
+    pub header1: Bytes,
+    /// Second argument of `submitDoubleSignEvidence`
+    pub header2: Bytes,
+    /// Input of the double sign evidence precompile
+    pub evidence: Bytes,
+}
+
+impl From<DoubleSign> for DoubleSignResult {
+    fn from(double_sign: DoubleSign) -> Self {
+        let (header1, header2) = double_sign.header_bytes();
+        Self {
+            signer: double_sign.signer,
+            number: double_sign.number(),
+            hash1: double_sign.header1.hash_slow(),
+            hash2: double_sign.header2.hash_slow(),
+            header1,
+            header2,
+            evidence: double_sign.evidence(),
+        }
+    }
+}
+
+/// Events of the Parlia subscriptions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ParliaSubscriptionKind {
+    /// Votes accepted by the vote pool for imported blocks
+    NewVotes,
+    /// Header of the finalized block, every time it advances
+    FinalizedHeaders,
+}
+
+/// Item of the Parlia subscriptions
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum ParliaSubscriptionResult {
+    Vote(VoteResult),
+    Header(Box<RpcHeader>),
+}
+
+/// Official BSC Parlia snapshot response structure matching bsc-geth.
+///
+/// Maps are ordered since go sorts the keys of the maps it encodes.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SnapshotResult {
     pub number: u64,
@@ -31,78 +152,117 @@ pub struct SnapshotResult {
     pub epoch_length: u64,
     pub block_interval: u64,
     pub turn_length: u8,
-    pub validators: std::collections::HashMap<String, ValidatorInfo>,
-    pub recents: std::collections::HashMap<String, String>,
-    pub recent_fork_hashes: std::collections::HashMap<String, String>,
+    pub validators: BTreeMap<String, ValidatorInfo>,
+    pub recents: BTreeMap<String, String>,
+    pub recent_fork_hashes: BTreeMap<String, String>,
     #[serde(rename = "attestation:omitempty")]
-    pub attestation: Option<serde_json::Value>,
+    pub attestation: Option<AttestationResult>,
 }
 
 impl From<Snapshot> for SnapshotResult {
     fn from(snapshot: Snapshot) -> Self {
         // Convert validators to the expected format: address -> ValidatorInfo
-        let validators: std::collections::HashMap<String, ValidatorInfo> = snapshot
+        let validators = snapshot
             .validators
             .iter()
             .map(|addr| {
-                (
-                    format!("0x{addr:040x}"), // 40-char hex address
-                    ValidatorInfo::default(),
-                )
+                let info = snapshot.validators_map.get(addr).map(Into::into).unwrap_or_default();
+                (format!("0x{addr:040x}"), info)
             })
             .collect();
 
         // Convert recent proposers to string format: block_number -> address
-        let recents: std::collections::HashMap<String, String> = snapshot
+        let recents = snapshot
             .recent_proposers
             .iter()
-            .map(|(block_num, addr)| {
-                (
-                    block_num.to_string(),
-                    format!("0x{addr:040x}"),
-                )
-            })
+            .map(|(block_num, addr)| (block_num.to_string(), format!("0x{addr:040x}")))
             .collect();
 
-        // Generate recent fork hashes (simplified - all zeros like in BSC example)
-        let recent_fork_hashes: std::collections::HashMap<String, String> = snapshot
-            .recent_proposers
-            .keys()
-            .map(|block_num| {
-                (
-                    block_num.to_string(),
-                    "00000000".to_string(), // Simplified fork hash
-                )
-            })
+        // Fork hashes are hex encoded without prefix: block_number -> fork hash
+        let recent_fork_hashes = snapshot
+            .recent_fork_hashes
+            .iter()
+            .map(|(block_num, fork_hash)| (block_num.to_string(), hex::encode(fork_hash)))
             .collect();
 
+        // No attestation has been recorded until the vote data is first updated
+        let attestation =
+            (snapshot.vote_data != VoteData::default()).then(|| snapshot.vote_data.into());
+
         Self {
             number: snapshot.block_number,
             hash: format!("0x{:064x}", snapshot.block_hash),
-            epoch_length: 200, // BSC epoch length
-            block_interval: 3000, // BSC block interval in milliseconds
+            epoch_length: snapshot.epoch_num,
+            block_interval: snapshot.block_interval,
             turn_length: snapshot.turn_length.unwrap_or(1),
             validators,
             recents,
             recent_fork_hashes,
-            attestation: None,
+            attestation,
         }
     }
 }
 
 /// Parlia snapshot RPC API (matches BSC official standard)
+///
+/// Blocks are given as hex or decimal numbers, or as one of the `latest`, `earliest`, `safe`,
+/// `finalized` and `pending` tags. A missing block parameter means `latest`.
 #[rpc(server, namespace = "parlia")]
 pub trait ParliaApi {
     /// Get snapshot at a specific block (official BSC API method)
     /// Params: block number as hex string (e.g., "0x123132")
     #[method(name = "getSnapshot")]
     async fn get_snapshot(&self, block_number: String) -> RpcResult<Option<SnapshotResult>>;
+
+    /// Get snapshot at the block with the given hash
+    #[method(name = "getSnapshotAtHash")]
+    async fn get_snapshot_at_hash(&self, block_hash: B256) -> RpcResult<Option<SnapshotResult>>;
+
+    /// Get the sorted validator set of the snapshot at a block
+    #[method(name = "getValidators")]
+    async fn get_validators(&self, block_number: Option<String>) -> RpcResult<Vec<Address>>;
+
+    /// Get the highest justified block number as of a block
+    #[method(name = "getJustifiedNumber")]
+    async fn get_justified_number(&self, block_number: Option<String>) -> RpcResult<u64>;
+
+    /// Get the highest finalized block number as of a block
+    #[method(name = "getFinalizedNumber")]
+    async fn get_finalized_number(&self, block_number: Option<String>) -> RpcResult<u64>;
+
+    /// Get the number of consecutive blocks proposed by a validator as of a block
+    #[method(name = "getTurnLength")]
+    async fn get_turn_length(&self, block_number: Option<String>) -> RpcResult<u8>;
+
+    /// Get the validator that sealed a block
+    #[method(name = "getSigner")]
+    async fn get_signer(&self, block_number: Option<String>) -> RpcResult<Address>;
+
+    /// Get the votes of the vote pool for the block with the given hash
+    #[method(name = "getVotes")]
+    async fn get_votes(&self, block_hash: B256) -> RpcResult<Vec<VoteResult>>;
+
+    /// Get the latest double signs found in the headers received from the network
+    #[method(name = "getDoubleSignEvidences")]
+    async fn get_double_sign_evidences(&self) -> RpcResult<Vec<DoubleSignResult>>;
+
+    /// Subscribe to the `newVotes` or the `finalizedHeaders` events
+    #[subscription(
+        name = "subscribe" => "subscription",
+        unsubscribe = "unsubscribe",
+        item = ParliaSubscriptionResult
+    )]
+    async fn subscribe(&self, kind: ParliaSubscriptionKind) -> SubscriptionResult;
 }
 
 /// Implementation of the Parlia snapshot RPC API
 pub struct ParliaApiImpl<P: SnapshotProvider> {
     /// Snapshot provider for accessing validator snapshots
     snapshot_provider: Arc<P>,
+    /// Parlia consensus, used to recover the signers of the blocks
+    parlia: Parlia<BscChainSpec>,
+    /// Finalized headers, fed by [`track_finalized_headers`]
+    finalized_headers: broadcast::Sender<RpcHeader>,
 }
 
 /// Wrapper for trait object to work around Sized requirement
@@ -121,6 +281,14 @@ impl SnapshotProvider for DynSnapshotProvider {
         self.inner.snapshot(block_number)
     }
 
+    fn snapshot_by_hash(
+        &self,
+        block_number: u64,
+        block_hash: B256,
+    ) -> Option<crate::consensus::parlia::snapshot::Snapshot> {
+        self.inner.snapshot_by_hash(block_number, block_hash)
+    }
+
     fn insert(&self, snapshot: crate::consensus::parlia::snapshot::Snapshot) {
         self.inner.insert(snapshot)
     }
@@ -128,6 +296,10 @@ impl SnapshotProvider for DynSnapshotProvider {
     fn get_header(&self, block_number: u64) -> Option<alloy_consensus::Header> {
         self.inner.get_header(block_number)
     }
+
+    fn get_header_by_hash(&self, block_hash: &B256) -> Option<alloy_consensus::Header> {
+        self.inner.get_header_by_hash(block_hash)
+    }
 }
 
 /// Convenience type alias for ParliaApiImpl using the wrapper
@@ -135,61 +307,157 @@ pub type ParliaApiDyn = ParliaApiImpl<DynSnapshotProvider>;
 
 impl<P: SnapshotProvider> ParliaApiImpl<P> {
     /// Create a new Parlia API instance
-    pub fn new(snapshot_provider: Arc<P>) -> Self {
-        Self { snapshot_provider }
+    pub fn new(snapshot_provider: Arc<P>, chain_spec: Arc<BscChainSpec>) -> Self {
+        let parlia = Parlia::new(chain_spec.clone(), chain_spec.parlia.base.epoch_length);
+        Self { snapshot_provider, parlia, finalized_headers: finalized_headers_channel() }
+    }
+
+    /// Streams the finalized headers of the given channel to the `finalizedHeaders` subscribers
+    pub fn with_finalized_headers(
+        mut self,
+        finalized_headers: broadcast::Sender<RpcHeader>,
+    ) -> Self {
+        self.finalized_headers = finalized_headers;
+        self
+    }
+
+    /// Resolves a block parameter to a block number, `None` when the block is not known yet.
+    ///
+    /// `safe` and `finalized` are the justified and finalized blocks as of the canonical head.
+    fn resolve_block_number(&self, block: Option<&str>) -> RpcResult<Option<u64>> {
+        let block = match block {
+            Some(block) => parse_block(block)?,
+            None => BlockNumberOrTag::Latest,
+        };
+        let latest = || {
+            crate::shared::get_best_block_number()
+                .ok_or_else(|| internal_error("best block number is not available"))
+        };
+        Ok(match block {
+            BlockNumberOrTag::Number(number) => Some(number),
+            BlockNumberOrTag::Earliest => Some(0),
+            BlockNumberOrTag::Latest => Some(latest()?),
+            BlockNumberOrTag::Safe => {
+                self.snapshot_provider.snapshot(latest()?).map(|snap| snap.vote_data.target_number)
+            }
+            BlockNumberOrTag::Finalized => {
+                self.snapshot_provider.snapshot(latest()?).map(|snap| snap.get_finalized_number())
+            }
+            BlockNumberOrTag::Pending => None,
+        })
+    }
+
+    /// Returns the snapshot as of the given block parameter.
+    fn snapshot_at(&self, block: Option<&str>) -> RpcResult<Option<Snapshot>> {
+        Ok(self
+            .resolve_block_number(block)?
+            .and_then(|number| self.snapshot_provider.snapshot(number)))
+    }
+}
+
+/// Parses a block tag, or a hex or decimal block number.
+fn parse_block(block: &str) -> RpcResult<BlockNumberOrTag> {
+    block
+        .parse::<BlockNumberOrTag>()
+        .or_else(|_| block.parse().map(BlockNumberOrTag::Number))
+        .map_err(|_| {
+            tracing::error!("Failed to parse block number '{}'", block);
+            ErrorObject::owned(INVALID_PARAMS_CODE, "Invalid block number format", None::<()>)
+        })
+}
+
+/// Creates the channel of the finalized headers fed by [`track_finalized_headers`].
+pub fn finalized_headers_channel() -> broadcast::Sender<RpcHeader> {
+    broadcast::channel(FINALIZED_HEADERS_BUFFER).0
+}
+
+/// Sends the header of the finalized block every time the finalized number of the canonical head
+/// advances, until the canonical state stream is closed.
+pub async fn track_finalized_headers<Provider>(
+    provider: Provider,
+    snapshot_provider: Arc<dyn SnapshotProvider + Send + Sync>,
+    finalized_headers: broadcast::Sender<RpcHeader>,
+) where
+    Provider: CanonStateSubscriptions<Primitives = BscPrimitives>,
+{
+    let mut canon_events = provider.subscribe_to_canonical_state();
+    let mut last_finalized = None;
+    loop {
+        let head = match canon_events.recv().await {
+            Ok(notification) => notification.tip().clone_sealed_header(),
+            Err(RecvError::Lagged(_)) => continue,
+            Err(RecvError::Closed) => return,
+        };
+        let Some(snap) = snapshot_provider.snapshot_by_hash(head.number, head.hash()) else {
+            continue
+        };
+        let finalized = snap.get_finalized_number();
+        if last_finalized.is_some_and(|last| finalized <= last) {
+            continue
+        }
+        let Some(header) = snapshot_provider.get_header(finalized) else { continue };
+        last_finalized = Some(finalized);
+        // sending only fails when nobody listens
+        let _ = finalized_headers.send(RpcHeader::from_consensus(header.seal_slow(), None, None));
+    }
+}
+
+/// Forwards the items of `receiver` to the subscriber as `R` until either side is closed.
+async fn pipe_to_subscriber<T, R>(sink: SubscriptionSink, mut receiver: broadcast::Receiver<T>)
+where
+    T: Clone + Into<R>,
+    R: Serialize,
+{
+    loop {
+        let item = tokio::select! {
+            _ = sink.closed() => return,
+            item = receiver.recv() => item,
+        };
+        let item: R = match item {
+            Ok(item) => item.into(),
+            Err(RecvError::Lagged(skipped)) => {
+                tracing::debug!(target: "rpc::parlia", skipped, "Subscriber lagged");
+                continue
+            }
+            Err(RecvError::Closed) => return,
+        };
+        let message =
+            match SubscriptionMessage::new(sink.method_name(), sink.subscription_id(), &item) {
+                Ok(message) => message,
+                Err(err) => {
+                    tracing::error!(target: "rpc::parlia", %err, "Failed to encode item");
+                    return
+                }
+            };
+        if sink.send(message).await.is_err() {
+            return
+        }
     }
 }
 
+fn internal_error(message: impl Into<String>) -> ErrorObjectOwned {
+    ErrorObject::owned(INTERNAL_ERROR_CODE, message, None::<()>)
+}
+
+fn unknown_block() -> ErrorObjectOwned {
+    ErrorObject::owned(UNKNOWN_BLOCK_CODE, "unknown block", None::<()>)
+}
+
 #[async_trait::async_trait]
 impl<P: SnapshotProvider + Send + Sync + 'static> ParliaApiServer for ParliaApiImpl<P> {
     /// Get snapshot at a specific block (matches BSC official API.GetSnapshot)
     /// Accepts block number as hex string like "0x123132"
     async fn get_snapshot(&self, block_number: String) -> RpcResult<Option<SnapshotResult>> {
-        // parlia_getSnapshot called
-        
-        // Parse hex block number (like BSC API does)
-        let block_num = if let Some(stripped) = block_number.strip_prefix("0x") {
-            match u64::from_str_radix(stripped, 16) {
-                Ok(num) => {
-                    // Parsed hex block number
-                    num
-                },
-                Err(e) => {
-                    tracing::error!("Failed to parse hex block number '{}': {}", block_number, e);
-                    return Err(ErrorObject::owned(
-                        -32602, 
-                        "Invalid block number format", 
-                        None::<()>
-                    ));
-                }
-            }
-        } else {
-            match block_number.parse::<u64>() {
-                Ok(num) => {
-                    // Parsed decimal block number
-                    num
-                },
-                Err(e) => {
-                    tracing::error!("Failed to parse decimal block number '{}': {}", block_number, e);
-                    return Err(ErrorObject::owned(
-                        -32602, 
-                        "Invalid block number format", 
-                        None::<()>
-                    ));
-                }
-            }
+        let Some(block_num) = self.resolve_block_number(Some(&block_number))? else {
+            return Ok(None)
         };
-        
-        // Querying snapshot provider
-        
+
         // Get snapshot from provider (equivalent to api.parlia.snapshot call in BSC)
         match self.snapshot_provider.snapshot(block_num) {
             Some(snapshot) => {
                 tracing::info!("Found snapshot for block {}: validators={}, epoch_num={}, block_hash=0x{:x}", 
                     block_num, snapshot.validators.len(), snapshot.epoch_num, snapshot.block_hash);
-                let result: SnapshotResult = snapshot.into();
-                // Snapshot result prepared
-                Ok(Some(result))
+                Ok(Some(snapshot.into()))
             },
             None => {
                 tracing::warn!("No snapshot found for block {}", block_num);
@@ -197,6 +465,68 @@ impl<P: SnapshotProvider + Send + Sync + 'static> ParliaApiServer for ParliaApiI
             }
         }
     }
+
+    async fn get_snapshot_at_hash(&self, block_hash: B256) -> RpcResult<Option<SnapshotResult>> {
+        let Some(header) = self.snapshot_provider.get_header_by_hash(&block_hash) else {
+            return Ok(None)
+        };
+        Ok(self.snapshot_provider.snapshot_by_hash(header.number, block_hash).map(Into::into))
+    }
+
+    async fn get_validators(&self, block_number: Option<String>) -> RpcResult<Vec<Address>> {
+        let snapshot = self.snapshot_at(block_number.as_deref())?.ok_or_else(unknown_block)?;
+        Ok(snapshot.validators)
+    }
+
+    async fn get_justified_number(&self, block_number: Option<String>) -> RpcResult<u64> {
+        let snapshot = self.snapshot_at(block_number.as_deref())?.ok_or_else(unknown_block)?;
+        Ok(snapshot.vote_data.target_number)
+    }
+
+    async fn get_finalized_number(&self, block_number: Option<String>) -> RpcResult<u64> {
+        let snapshot = self.snapshot_at(block_number.as_deref())?.ok_or_else(unknown_block)?;
+        Ok(snapshot.get_finalized_number())
+    }
+
+    async fn get_turn_length(&self, block_number: Option<String>) -> RpcResult<u8> {
+        let snapshot = self.snapshot_at(block_number.as_deref())?.ok_or_else(unknown_block)?;
+        Ok(snapshot.turn_length.unwrap_or(DEFAULT_TURN_LENGTH))
+    }
+
+    async fn get_signer(&self, block_number: Option<String>) -> RpcResult<Address> {
+        let header = self
+            .resolve_block_number(block_number.as_deref())?
+            .and_then(|number| self.snapshot_provider.get_header(number))
+            .ok_or_else(unknown_block)?;
+        self.parlia.recover_proposer(&header).map_err(|err| internal_error(err.to_string()))
+    }
+
+    async fn get_votes(&self, block_hash: B256) -> RpcResult<Vec<VoteResult>> {
+        Ok(votes::fetch_vote_by_block_hash(block_hash).into_iter().map(Into::into).collect())
+    }
+
+    async fn get_double_sign_evidences(&self) -> RpcResult<Vec<DoubleSignResult>> {
+        Ok(double_sign::recent_double_signs().into_iter().map(Into::into).collect())
+    }
+
+    async fn subscribe(
+        &self,
+        pending: PendingSubscriptionSink,
+        kind: ParliaSubscriptionKind,
+    ) -> SubscriptionResult {
+        let sink = pending.accept().await?;
+        match kind {
+            ParliaSubscriptionKind::NewVotes => {
+                let receiver = votes::subscribe_new_votes();
+                tokio::spawn(pipe_to_subscriber::<_, VoteResult>(sink, receiver));
+            }
+            ParliaSubscriptionKind::FinalizedHeaders => {
+                let receiver = self.finalized_headers.subscribe();
+                tokio::spawn(pipe_to_subscriber::<_, RpcHeader>(sink, receiver));
+            }
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -215,7 +545,7 @@ mod tests {
         let snapshot_provider = Arc::new(EnhancedDbSnapshotProvider::new(
             db.clone(),
             2048,
-            chain_spec,
+            chain_spec.clone(),
         ));
         
         // Insert a test snapshot
@@ -228,7 +558,7 @@ mod tests {
         };
         snapshot_provider.insert(test_snapshot.clone());
 
-        let api = ParliaApiImpl::new(snapshot_provider);
+        let api = ParliaApiImpl::new(snapshot_provider, chain_spec);
         
         // Test snapshot retrieval with hex block number (BSC official format)
         let result = api.get_snapshot("0x64".to_string()).await.unwrap(); // 0x64 = 100
@@ -243,5 +573,66 @@ mod tests {
         // Test with decimal format too
         let result = api.get_snapshot("100".to_string()).await.unwrap();
         assert!(result.is_some());
+
+        let validators = api.get_validators(Some("0x64".to_string())).await.unwrap();
+        assert_eq!(validators, test_snapshot.validators);
+        assert_eq!(api.get_turn_length(Some("100".to_string())).await.unwrap(), 1);
+        assert_eq!(api.get_justified_number(Some("0x64".to_string())).await.unwrap(), 0);
+        assert!(api.get_validators(Some("pending".to_string())).await.is_err());
+        assert!(api.get_snapshot("0xzz".to_string()).await.is_err());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_snapshot_result_json() {
+        let validator = alloy_primitives::Address::repeat_byte(0x11);
+        let mut snapshot = Snapshot::new(
+            vec![validator],
+            7,
+            B256::repeat_byte(0x22),
+            1000,
+            Some(vec![crate::consensus::parlia::VoteAddress::repeat_byte(0x01)]),
+        );
+        snapshot.block_interval = 750;
+        snapshot.recent_proposers.insert(7, validator);
+        snapshot.recent_fork_hashes.insert(7, alloy_primitives::FixedBytes::repeat_byte(0xab));
+        snapshot.vote_data = VoteData {
+            source_number: 5,
+            source_hash: B256::repeat_byte(0x05),
+            target_number: 6,
+            target_hash: B256::repeat_byte(0x06),
+        };
+
+        let json = serde_json::to_string(&SnapshotResult::from(snapshot)).unwrap();
+        assert_eq!(
+            json,
+            format!(
+                r#"{{"number":7,"hash":"0x{}","epoch_length":1000,"block_interval":750,"turn_length":1,"validators":{{"0x{}":{{"index:omitempty":1,"vote_address":[{}]}}}},"recents":{{"7":"0x{}"}},"recent_fork_hashes":{{"7":"abababab"}},"attestation:omitempty":{{"SourceNumber":5,"SourceHash":"0x{}","TargetNumber":6,"TargetHash":"0x{}"}}}}"#,
+                "22".repeat(32),
+                "11".repeat(20),
+                ["1"; 48].join(","),
+                "11".repeat(20),
+                "05".repeat(32),
+                "06".repeat(32),
+            )
+        );
+
+        let json = serde_json::to_value(SnapshotResult::from(Snapshot::default())).unwrap();
+        assert!(json["attestation:omitempty"].is_null());
+    }
+
+    #[test]
+    fn test_vote_result_json() {
+        let vote = VoteEnvelope {
+            vote_address: VoteAddress::repeat_byte(0x01),
+            signature: VoteSignature::repeat_byte(0x02),
+            data: VoteData { target_number: 6, ..Default::default() },
+        };
+        let json = serde_json::to_value(VoteResult::from(vote)).unwrap();
+        assert_eq!(json["VoteAddress"], format!("0x{}", "01".repeat(48)));
+        assert_eq!(json["Signature"], format!("0x{}", "02".repeat(96)));
+        assert_eq!(json["Data"]["TargetNumber"], 6);
+
+        let kind: ParliaSubscriptionKind = serde_json::from_str(r#""finalizedHeaders""#).unwrap();
+        assert_eq!(kind, ParliaSubscriptionKind::FinalizedHeaders);
+    }
+}
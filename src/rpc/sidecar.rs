@@ -0,0 +1,177 @@
+use alloy_eips::BlockId;
+use alloy_primitives::{Bytes, FixedBytes, B256, U64};
+use jsonrpsee::{
+    core::RpcResult,
+    proc_macros::rpc,
+    types::{error::INTERNAL_ERROR_CODE, ErrorObject, ErrorObjectOwned},
+};
+use reth_provider::{BlockReaderIdExt, ProviderError, TransactionsProvider};
+use serde::{Deserialize, Serialize};
+
+use crate::{BscBlobTransactionSidecar, BscBlock};
+
+/// Blobs, commitments and proofs of a sidecar (matches BSC official format)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlobSidecarFields {
+    /// Full blobs, or only their first 32 bytes when the full blobs are not requested
+    pub blobs: Vec<Bytes>,
+    pub commitments: Vec<FixedBytes<48>>,
+    pub proofs: Vec<FixedBytes<48>>,
+}
+
+/// Blob sidecar RPC response structure matching bsc-geth, fields are sorted like its JSON output
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobSidecarResult {
+    pub blob_sidecar: BlobSidecarFields,
+    pub block_hash: B256,
+    pub block_number: U64,
+    pub tx_hash: B256,
+    pub tx_index: U64,
+}
+
+impl BlobSidecarResult {
+    /// Builds the response of a sidecar, `full_blob` selects whether the blobs are returned whole
+    pub fn new(sidecar: BscBlobTransactionSidecar, full_blob: bool) -> Self {
+        let BscBlobTransactionSidecar { inner, block_number, block_hash, tx_index, tx_hash } =
+            sidecar;
+        let blobs = inner
+            .blobs
+            .iter()
+            .map(|blob| {
+                let blob = if full_blob { &blob[..] } else { &blob[..32] };
+                Bytes::copy_from_slice(blob)
+            })
+            .collect();
+
+        Self {
+            blob_sidecar: BlobSidecarFields {
+                blobs,
+                commitments: inner.commitments,
+                proofs: inner.proofs,
+            },
+            block_hash,
+            block_number: U64::from(block_number),
+            tx_hash,
+            tx_index: U64::from(tx_index),
+        }
+    }
+}
+
+/// Blob sidecar RPC API (matches BSC official standard)
+#[rpc(server, namespace = "eth")]
+pub trait BlobSidecarApi {
+    /// Get the blob sidecars of a block, blobs are returned whole unless `full_blob` is false
+    #[method(name = "getBlobSidecars")]
+    async fn get_blob_sidecars(
+        &self,
+        block_id: BlockId,
+        full_blob: Option<bool>,
+    ) -> RpcResult<Option<Vec<BlobSidecarResult>>>;
+
+    /// Get the blob sidecar of a transaction, blobs are returned whole unless `full_blob` is false
+    #[method(name = "getBlobSidecarByTxHash")]
+    async fn get_blob_sidecar_by_tx_hash(
+        &self,
+        tx_hash: B256,
+        full_blob: Option<bool>,
+    ) -> RpcResult<Option<BlobSidecarResult>>;
+}
+
+/// Implementation of the blob sidecar RPC API
+pub struct BlobSidecarApiImpl<Provider> {
+    /// Provider of the blocks and their sidecars
+    provider: Provider,
+}
+
+impl<Provider> BlobSidecarApiImpl<Provider> {
+    /// Create a new blob sidecar API instance
+    pub fn new(provider: Provider) -> Self {
+        Self { provider }
+    }
+}
+
+fn internal_error(err: ProviderError) -> ErrorObjectOwned {
+    ErrorObject::owned(INTERNAL_ERROR_CODE, err.to_string(), None::<()>)
+}
+
+#[async_trait::async_trait]
+impl<Provider> BlobSidecarApiServer for BlobSidecarApiImpl<Provider>
+where
+    Provider: BlockReaderIdExt<Block = BscBlock> + TransactionsProvider + 'static,
+{
+    async fn get_blob_sidecars(
+        &self,
+        block_id: BlockId,
+        full_blob: Option<bool>,
+    ) -> RpcResult<Option<Vec<BlobSidecarResult>>> {
+        let Some(block) = self.provider.block_by_id(block_id).map_err(internal_error)? else {
+            return Ok(None)
+        };
+        let full_blob = full_blob.unwrap_or(true);
+        Ok(block.body.sidecars.map(|sidecars| {
+            sidecars.into_iter().map(|sidecar| BlobSidecarResult::new(sidecar, full_blob)).collect()
+        }))
+    }
+
+    async fn get_blob_sidecar_by_tx_hash(
+        &self,
+        tx_hash: B256,
+        full_blob: Option<bool>,
+    ) -> RpcResult<Option<BlobSidecarResult>> {
+        let Some((_, meta)) =
+            self.provider.transaction_by_hash_with_meta(tx_hash).map_err(internal_error)?
+        else {
+            return Ok(None)
+        };
+        let Some(block) =
+            self.provider.block_by_id(meta.block_hash.into()).map_err(internal_error)?
+        else {
+            return Ok(None)
+        };
+        let full_blob = full_blob.unwrap_or(true);
+        Ok(block
+            .body
+            .sidecars
+            .and_then(|sidecars| sidecars.into_iter().find(|sidecar| sidecar.tx_hash == tx_hash))
+            .map(|sidecar| BlobSidecarResult::new(sidecar, full_blob)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::BlobTransactionSidecar;
+    use alloy_eips::eip4844::Blob;
+
+    #[test]
+    fn test_blob_sidecar_json() {
+        let sidecar = BscBlobTransactionSidecar {
+            inner: BlobTransactionSidecar {
+                blobs: vec![Blob::repeat_byte(0xab)],
+                commitments: vec![FixedBytes::repeat_byte(0x01)],
+                proofs: vec![FixedBytes::repeat_byte(0x02)],
+            },
+            block_number: 0x2a,
+            block_hash: B256::repeat_byte(0x03),
+            tx_index: 1,
+            tx_hash: B256::repeat_byte(0x04),
+        };
+
+        let full = serde_json::to_value(BlobSidecarResult::new(sidecar.clone(), true)).unwrap();
+        assert_eq!(full["blobSidecar"]["blobs"][0].as_str().unwrap().len(), 2 + 2 * 131072);
+
+        let result = BlobSidecarResult::new(sidecar, false);
+        assert_eq!(
+            serde_json::to_string(&result).unwrap(),
+            format!(
+                r#"{{"blobSidecar":{{"blobs":["0x{}"],"commitments":["0x{}"],"proofs":["0x{}"]}},"blockHash":"0x{}","blockNumber":"0x2a","txHash":"0x{}","txIndex":"0x1"}}"#,
+                "ab".repeat(32),
+                "01".repeat(48),
+                "02".repeat(48),
+                "03".repeat(32),
+                "04".repeat(32),
+            )
+        );
+    }
+}
@@ -1,8 +1,9 @@
 use crate::consensus::parlia::SnapshotProvider;
 use std::sync::{Arc, OnceLock};
 use alloy_consensus::Header;
-use alloy_primitives::B256;
-use reth_provider::HeaderProvider;
+use alloy_primitives::{B256, U256};
+use crate::BscBlock;
+use reth_provider::{BlockHashReader, BlockNumReader, BlockReader, HeaderProvider};
 
 /// Function type for HeaderProvider::header() access (by hash)
 type HeaderByHashFn = Arc<dyn Fn(&B256) -> Option<Header> + Send + Sync>;
@@ -10,6 +11,21 @@ type HeaderByHashFn = Arc<dyn Fn(&B256) -> Option<Header> + Send + Sync>;
 /// Function type for HeaderProvider::header_by_number() access (by number)  
 type HeaderByNumberFn = Arc<dyn Fn(u64) -> Option<Header> + Send + Sync>;
 
+/// Function type for BlockHashReader::block_hash() access (by number)
+type CanonicalHashFn = Arc<dyn Fn(u64) -> Option<B256> + Send + Sync>;
+
+/// Function type for HeaderProvider::header_td() access (by hash)
+type HeaderTdFn = Arc<dyn Fn(&B256) -> Option<U256> + Send + Sync>;
+
+/// Function type for BlockNumReader::best_block_number() access
+type BestBlockNumberFn = Arc<dyn Fn() -> Option<u64> + Send + Sync>;
+
+/// Function type for BlockReader::block_by_hash() access
+type BlockByHashFn = Arc<dyn Fn(&B256) -> Option<BscBlock> + Send + Sync>;
+
+/// Function type for BlockReader::block_by_number() access
+type BlockByNumberFn = Arc<dyn Fn(u64) -> Option<BscBlock> + Send + Sync>;
+
 /// Global shared access to the snapshot provider for RPC
 static SNAPSHOT_PROVIDER: OnceLock<Arc<dyn SnapshotProvider + Send + Sync>> = OnceLock::new();
 
@@ -19,6 +35,21 @@ static HEADER_BY_HASH_PROVIDER: OnceLock<HeaderByHashFn> = OnceLock::new();
 /// Global header provider function - HeaderProvider::header_by_number() by number  
 static HEADER_BY_NUMBER_PROVIDER: OnceLock<HeaderByNumberFn> = OnceLock::new();
 
+/// Global canonical hash provider function - BlockHashReader::block_hash() by number
+static CANONICAL_HASH_PROVIDER: OnceLock<CanonicalHashFn> = OnceLock::new();
+
+/// Global total difficulty provider function - HeaderProvider::header_td() by hash
+static HEADER_TD_PROVIDER: OnceLock<HeaderTdFn> = OnceLock::new();
+
+/// Global best block number provider function - BlockNumReader::best_block_number()
+static BEST_BLOCK_NUMBER_PROVIDER: OnceLock<BestBlockNumberFn> = OnceLock::new();
+
+/// Global block provider function - BlockReader::block_by_hash() by hash
+static BLOCK_BY_HASH_PROVIDER: OnceLock<BlockByHashFn> = OnceLock::new();
+
+/// Global block provider function - BlockReader::block_by_number() by number
+static BLOCK_BY_NUMBER_PROVIDER: OnceLock<BlockByNumberFn> = OnceLock::new();
+
 /// Store the snapshot provider globally
 pub fn set_snapshot_provider(provider: Arc<dyn SnapshotProvider + Send + Sync>) -> Result<(), Arc<dyn SnapshotProvider + Send + Sync>> {
     SNAPSHOT_PROVIDER.set(provider)
@@ -30,10 +61,11 @@ pub fn get_snapshot_provider() -> Option<&'static Arc<dyn SnapshotProvider + Sen
 }
 
 /// Store the header provider globally
-/// Creates functions that directly call HeaderProvider::header() and HeaderProvider::header_by_number()
+/// Creates functions that directly call HeaderProvider::header(),
+/// HeaderProvider::header_by_number(), BlockHashReader::block_hash() and HeaderProvider::header_td()
 pub fn set_header_provider<T>(provider: Arc<T>) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
 where
-    T: HeaderProvider<Header = Header> + Send + Sync + 'static,
+    T: HeaderProvider<Header = Header> + BlockHashReader + Send + Sync + 'static,
 {
     // Create function for header by hash
     let provider_clone = provider.clone();
@@ -53,9 +85,20 @@ where
         }
     });
     
-    // Set both functions
+    // Create function for canonical hash by number
+    let provider_clone3 = provider.clone();
+    let canonical_hash_fn =
+        Arc::new(move |block_number: u64| provider_clone3.block_hash(block_number).ok().flatten());
+
+    // Create function for total difficulty by hash
+    let header_td_fn =
+        Arc::new(move |block_hash: &B256| provider.header_td(block_hash).ok().flatten());
+
+    // Set the functions
     HEADER_BY_HASH_PROVIDER.set(header_by_hash_fn).map_err(|_| "Failed to set hash provider")?;
     HEADER_BY_NUMBER_PROVIDER.set(header_by_number_fn).map_err(|_| "Failed to set number provider")?;
+    CANONICAL_HASH_PROVIDER.set(canonical_hash_fn).map_err(|_| "Failed to set canonical hash provider")?;
+    HEADER_TD_PROVIDER.set(header_td_fn).map_err(|_| "Failed to set total difficulty provider")?;
     
     Ok(())
 }
@@ -92,4 +135,63 @@ pub fn get_header_by_hash(block_hash: &B256) -> Option<Header> {
 /// Get header by number - simplified interface
 pub fn get_header_by_number(block_number: u64) -> Option<Header> {
     get_header_by_number_from_provider(block_number)
-}
\ No newline at end of file
+}
+
+/// Get the hash of the canonical block at the given number from the global header provider
+/// Reads the stored hash rather than hashing the header
+pub fn get_canonical_hash(block_number: u64) -> Option<B256> {
+    let provider_fn = CANONICAL_HASH_PROVIDER.get()?;
+    provider_fn(block_number)
+}
+
+/// Get the total difficulty of the block with the given hash from the global header provider
+pub fn get_header_td(block_hash: &B256) -> Option<U256> {
+    let provider_fn = HEADER_TD_PROVIDER.get()?;
+    provider_fn(block_hash)
+}
+
+/// Store the best block number provider globally
+pub fn set_best_block_number_provider<T>(provider: Arc<T>) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+where
+    T: BlockNumReader + Send + Sync + 'static,
+{
+    let best_block_number_fn = Arc::new(move || provider.best_block_number().ok());
+    BEST_BLOCK_NUMBER_PROVIDER.set(best_block_number_fn).map_err(|_| "Failed to set best block number provider")?;
+    Ok(())
+}
+
+/// Get the number of the canonical head from the global provider
+pub fn get_best_block_number() -> Option<u64> {
+    let provider_fn = BEST_BLOCK_NUMBER_PROVIDER.get()?;
+    provider_fn()
+}
+
+/// Store the block provider globally, blocks are read with their sidecars
+pub fn set_block_provider<T>(provider: Arc<T>) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+where
+    T: BlockReader<Block = BscBlock> + Send + Sync + 'static,
+{
+    let provider_clone = provider.clone();
+    let block_by_hash_fn =
+        Arc::new(move |block_hash: &B256| provider_clone.block_by_hash(*block_hash).ok().flatten());
+    let block_by_number_fn =
+        Arc::new(move |block_number: u64| provider.block_by_number(block_number).ok().flatten());
+
+    BLOCK_BY_HASH_PROVIDER.set(block_by_hash_fn).map_err(|_| "Failed to set block hash provider")?;
+    BLOCK_BY_NUMBER_PROVIDER
+        .set(block_by_number_fn)
+        .map_err(|_| "Failed to set block number provider")?;
+    Ok(())
+}
+
+/// Get block by hash from the global block provider
+pub fn get_block_by_hash(block_hash: &B256) -> Option<BscBlock> {
+    let provider_fn = BLOCK_BY_HASH_PROVIDER.get()?;
+    provider_fn(block_hash)
+}
+
+/// Get block by number from the global block provider
+pub fn get_block_by_number(block_number: u64) -> Option<BscBlock> {
+    let provider_fn = BLOCK_BY_NUMBER_PROVIDER.get()?;
+    provider_fn(block_number)
+}